@@ -0,0 +1,40 @@
+//! Alias command - choose the directory name a skill has in tools
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::models::SkillId;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::AppConfig;
+
+pub fn run(skill: &str, alias: Option<&str>, clear: bool) -> anyhow::Result<()> {
+    let store = LocalStore::default_store()?;
+    let skill = store.resolve(skill)?;
+
+    if alias.is_none() && !clear {
+        println!(
+            "{} is projected as {}",
+            skill.as_str().bold(),
+            store.projection_name(&skill).to_string().cyan()
+        );
+        return Ok(());
+    }
+
+    let alias = alias.map(SkillId::new).transpose()?;
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.set_tool_profiles(AppConfig::load_or_default().tool_profiles());
+    engine.set_alias(&skill, alias)?;
+
+    println!(
+        "{} {} is now projected as {}",
+        "✓".green(),
+        skill.as_str().bold(),
+        engine.store().projection_name(&skill).to_string().cyan()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! Collect command - import skills found in tools into the hub
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::models::{ConflictResolution, SkillId, ToolType};
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::AppConfig;
+
+pub fn run(skill: Option<&str>, use_tool: Option<&str>, keep_both: bool) -> anyhow::Result<()> {
+    let config = AppConfig::load_or_default();
+    let mut engine = SyncEngine::new(LocalStore::default_store()?);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    if let Some(skill) = skill {
+        let skill_id = SkillId::new(skill)?;
+        let resolution = match use_tool {
+            Some(tool) => ConflictResolution::UseTool {
+                tool: ToolType::from_key(tool)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown tool '{}'", tool))?,
+            },
+            None if keep_both => ConflictResolution::KeepBoth,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Choose a copy with --use <tool>, or keep them all with --keep-both"
+                ))
+            }
+        };
+
+        let collected = engine.resolve_conflict(&skill_id, resolution, |tool| {
+            config.strategy_for_tool(tool.key())
+        })?;
+        for id in &collected {
+            println!("  {} Collected {}", "✓".green(), id);
+        }
+        super::restore::print_backups(&engine);
+        return Ok(());
+    }
+
+    println!("{} Collecting skills from tools...", "📥".cyan());
+    println!();
+    let report = engine.collect_to_hub()?;
+    for id in &report.collected {
+        println!("  {} Collected {}", "✓".green(), id);
+    }
+
+    for conflict in &report.conflicts {
+        println!();
+        println!(
+            "  {} {} differs between tools:",
+            "⚠️".yellow(),
+            conflict.skill_id.as_str().bold()
+        );
+        for (i, variant) in conflict.variants.iter().enumerate() {
+            let tools: Vec<&str> = variant.tools.iter().map(|t| t.display_name()).collect();
+            println!("    {}. {}", i + 1, tools.join(", "));
+            for file in &variant.diffs {
+                println!("       {} {}", file.change, file.path.display());
+                if let Some(diff) = &file.diff {
+                    for line in diff.lines() {
+                        let line = if line.starts_with('+') {
+                            line.green()
+                        } else if line.starts_with('-') {
+                            line.red()
+                        } else {
+                            line.dimmed()
+                        };
+                        println!("         {}", line);
+                    }
+                }
+            }
+        }
+        println!(
+            "    Resolve with {} or {}",
+            format!("skillshub collect {} --use <tool>", conflict.skill_id).cyan(),
+            format!("skillshub collect {} --keep-both", conflict.skill_id).cyan()
+        );
+    }
+
+    println!();
+    println!(
+        "{} Collected {} skills, {} conflicts",
+        "✓".green(),
+        report.collected.len(),
+        report.conflicts.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Enable/disable commands - keep a skill in the store without projecting it
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::AppConfig;
+
+fn engine() -> anyhow::Result<SyncEngine> {
+    let mut engine = SyncEngine::new(LocalStore::default_store()?);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    Ok(engine)
+}
+
+pub fn disable(skill: &str) -> anyhow::Result<()> {
+    let mut engine = engine()?;
+    let skill = engine.store().resolve(skill)?;
+
+    let removed = engine.disable_skill(&skill)?;
+    for tool in &removed {
+        println!(
+            "  {} Removed {} from {}",
+            "✓".green(),
+            skill,
+            tool.display_name()
+        );
+    }
+    println!(
+        "{} {} is disabled; it stays installed but is not synced to any tool",
+        "✓".green(),
+        skill.as_str().bold()
+    );
+
+    Ok(())
+}
+
+pub fn enable(skill: &str) -> anyhow::Result<()> {
+    let mut engine = engine()?;
+    let skill = engine.store().resolve(skill)?;
+
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    let distributed = engine.enable_skill(&skill, |tool| config.strategy_for_tool(tool.key()))?;
+    for (_, tool, success) in &distributed {
+        if *success {
+            println!("  {} {} → {}", "✓".green(), skill, tool.display_name());
+        } else {
+            println!("  {} {} → {}", "✗".red(), skill, tool.display_name());
+        }
+    }
+    println!("{} {} is enabled", "✓".green(), skill.as_str().bold());
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! GC command - delete store data, temp folders and caches nothing references
+
+use colored::Colorize;
+
+use skillshub_core::gc::collect_garbage;
+use skillshub_core::registry::RegistryManager;
+use skillshub_core::store::LocalStore;
+
+pub fn run(dry_run: bool) -> anyhow::Result<()> {
+    let mut store = LocalStore::default_store()?;
+    let registries = RegistryManager::new()?.list();
+    let report = collect_garbage(&mut store, &registries, dry_run)?;
+
+    if report.entries.is_empty() && report.failed.is_empty() {
+        println!("{} Nothing to clean up", "✓".green());
+        return Ok(());
+    }
+
+    for entry in &report.entries {
+        println!(
+            "  {} {} {} {}",
+            if dry_run { "•".cyan() } else { "✗".red() },
+            format!("[{}]", entry.kind).yellow(),
+            entry.path.display(),
+            format_size(entry.bytes).dimmed()
+        );
+    }
+    for (entry, error) in &report.failed {
+        println!(
+            "  {} {} {}: {}",
+            "⚠".yellow(),
+            format!("[{}]", entry.kind).yellow(),
+            entry.path.display(),
+            error.red()
+        );
+    }
+
+    println!();
+    let size = format_size(report.reclaimable_bytes()).bold();
+    if dry_run {
+        println!(
+            "{} {} reclaimable in {} entries. Run {} to delete them.",
+            "🧹".cyan(),
+            size,
+            report.entries.len(),
+            "skillshub gc".cyan()
+        );
+    } else {
+        println!(
+            "{} Reclaimed {} from {} entries",
+            "🧹".green(),
+            size,
+            report.entries.len()
+        );
+    }
+
+    if !report.failed.is_empty() {
+        anyhow::bail!("{} entries could not be deleted", report.failed.len());
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
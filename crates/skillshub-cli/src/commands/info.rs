@@ -7,23 +7,32 @@ use skillshub_core::store::LocalStore;
 
 pub async fn run(skill: &str) -> anyhow::Result<()> {
     let store = LocalStore::default_store()?;
-
-    if !store.is_installed(skill) {
-        return Err(anyhow::anyhow!("Skill '{}' is not installed", skill));
-    }
+    let name = skill;
+    let skill = match store.resolve(name) {
+        Ok(skill) => skill,
+        Err(skillshub_core::Error::SkillNotFound(_)) => {
+            return Err(anyhow::anyhow!("Skill '{}' is not installed", name));
+        }
+        Err(e) => return Err(e.into()),
+    };
 
     let record = store
-        .get_record(skill)
+        .get_record(&skill)
         .ok_or_else(|| anyhow::anyhow!("Skill record not found"))?;
 
-    let skill_path = store.skill_path(skill);
+    let skill_path = store.skill_path(&skill);
     let skill_md_path = skill_path.join("SKILL.md");
 
     println!("{}", "═".repeat(50).dimmed());
-    println!("{} {}", "📦".cyan(), skill.bold());
+    println!("{} {}", "📦".cyan(), skill.as_str().bold());
     println!("{}", "═".repeat(50).dimmed());
     println!();
 
+    if skill != name {
+        println!("  {} resolves to {}", name, skill.as_str().bold());
+        println!();
+    }
+
     // Basic info
     println!("{}", "Details:".bold());
     println!("  Version: {}", record.version.version);
@@ -31,6 +40,18 @@ pub async fn run(skill: &str) -> anyhow::Result<()> {
     println!("  Installed: {}", record.installed_at);
     println!("  Source: {}", record.source.display());
     println!("  Local Path: {}", skill_path.display());
+    println!(
+        "  Tool Directory Name: {}{}",
+        store.projection_name(&skill),
+        if record.alias.is_some() {
+            " (alias)"
+        } else {
+            ""
+        }
+    );
+    if record.disabled {
+        println!("  Status: {}", "Disabled".yellow());
+    }
     println!(
         "  Security Scan: {}",
         if record.scan_passed {
@@ -50,6 +71,20 @@ pub async fn run(skill: &str) -> anyhow::Result<()> {
         println!();
     }
 
+    // Versions available for rollback
+    if !record.history.is_empty() {
+        println!("{}", "Previous versions:".bold());
+        for version in &record.history {
+            println!(
+                "  {} {} ({})",
+                "•".cyan(),
+                version.version,
+                &version.content_hash[..version.content_hash.len().min(12)]
+            );
+        }
+        println!();
+    }
+
     // SKILL.md content preview
     if skill_md_path.exists() {
         println!("{}", "SKILL.md Preview:".bold());
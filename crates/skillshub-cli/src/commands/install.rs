@@ -5,8 +5,12 @@ use indicatif::{ProgressBar, ProgressStyle};
 use std::path::PathBuf;
 
 use skillshub_core::adapters::create_default_adapters;
-use skillshub_core::models::{SkillSource, SyncStrategy, ToolType};
-use skillshub_core::registry::{GitRegistry, LocalRegistry, RegistryProvider};
+use skillshub_core::config::AppConfig;
+use skillshub_core::gc::TEMP_INSTALL_PREFIX;
+use skillshub_core::lockfile::{self, Lockfile, LOCK_FILE};
+use skillshub_core::manifest::{self, ManifestSkillStatus, ProjectManifest, MANIFEST_FILE};
+use skillshub_core::models::{SkillId, SkillSource, SyncStrategy, ToolType};
+use skillshub_core::registry::{GitRegistry, LocalRegistry, RegistryManager, RegistryProvider};
 use skillshub_core::scanner::SecurityScanner;
 use skillshub_core::store::LocalStore;
 use skillshub_core::sync::SyncEngine;
@@ -22,29 +26,14 @@ pub async fn run(
 
     let strategy = match sync_strategy {
         "link" => SyncStrategy::Link,
+        "relative-link" => SyncStrategy::RelativeLink,
+        "hardlink" => SyncStrategy::Hardlink,
         "copy" => SyncStrategy::Copy,
         _ => SyncStrategy::Auto,
     };
 
-    let target_tools: Vec<ToolType> = if let Some(tools_str) = tools {
-        tools_str
-            .split(',')
-            .filter_map(|t| match t.trim().to_lowercase().as_str() {
-                "claude" => Some(ToolType::Claude),
-                "cursor" => Some(ToolType::Cursor),
-                "gemini" => Some(ToolType::Gemini),
-                "opencode" => Some(ToolType::OpenCode),
-                _ => None,
-            })
-            .collect()
-    } else {
-        vec![
-            ToolType::Claude,
-            ToolType::Cursor,
-            ToolType::Gemini,
-            ToolType::OpenCode,
-        ]
-    };
+    // Parsed before anything is installed so a typo does not leave a half-done install
+    let requested_tools = tools.map(super::tools::parse_tools).transpose()?;
 
     let pb = ProgressBar::new(100);
     pb.set_style(
@@ -59,38 +48,42 @@ pub async fn run(
 
     let mut store = LocalStore::default_store()?;
 
-    // Resolve/install source into local store and return installed skill_id.
-    let skill_id = if skill.starts_with("http://")
+    // Resolve the source into skill metadata and a local path to import from.
+    let (meta, source_path, temp_dir) = if skill.starts_with("http://")
         || skill.starts_with("https://")
         || skill.starts_with("git@")
         || skill.ends_with(".git")
     {
         let registry = GitRegistry::new("remote", skill, None);
-        let remote_skill_id = skill
-            .trim_end_matches('/')
-            .rsplit('/')
-            .next()
-            .unwrap_or("skill")
-            .trim_end_matches(".git")
-            .to_string();
+        let remote_skill_id = SkillId::new(
+            skill
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or("skill")
+                .trim_end_matches(".git"),
+        )?;
 
         let meta = registry.get_skill(&remote_skill_id).await?;
-        let temp_dir = std::env::temp_dir().join(format!("skillshub-install-{}", remote_skill_id));
+        let temp_dir = std::env::temp_dir().join(format!(
+            "{}{}",
+            TEMP_INSTALL_PREFIX,
+            remote_skill_id.file_name()
+        ));
         let source_path = registry.fetch(&remote_skill_id, &temp_dir).await?;
-
-        store.import_skill(&meta, &source_path).await?;
-        meta.id
+        (meta, source_path, Some(temp_dir))
     } else if PathBuf::from(skill).exists() {
         let source_path = PathBuf::from(skill);
-        let skill_id = source_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("local-skill")
-            .to_string();
+        let skill_id = SkillId::new(
+            source_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("local-skill"),
+        )?;
 
         let pseudo_skill = skillshub_core::models::Skill {
             id: skill_id.clone(),
-            name: skill_id.clone(),
+            name: skill_id.to_string(),
             description: String::new(),
             author: None,
             tags: Vec::new(),
@@ -103,26 +96,39 @@ pub async fn run(
             resources: Vec::new(),
             metadata: std::collections::HashMap::new(),
         };
-
-        store.import_skill(&pseudo_skill, &source_path).await?;
-        skill_id
+        (pseudo_skill, source_path, None)
     } else {
         let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let local_registry = home.join(".skillshub").join("local-registry");
         let registry = LocalRegistry::new("local", local_registry);
-        let meta = registry.get_skill(skill).await?;
-        let source_path = registry.fetch(skill, PathBuf::new().as_path()).await?;
-        store.import_skill(&meta, &source_path).await?;
-        meta.id
+        let skill_id = SkillId::new(skill)?;
+        let meta = registry.get_skill(&skill_id).await?;
+        let source_path = registry.fetch(&skill_id, PathBuf::new().as_path()).await?;
+        (meta, source_path, None)
     };
 
+    // Stage inside the store; nothing is visible until the scan passes.
+    let staged = store.stage_skill(&meta, &source_path);
+    if let Some(temp_dir) = temp_dir {
+        let _ = std::fs::remove_dir_all(temp_dir);
+    }
+    let staged = staged?;
+    let skill_id = meta.id.clone();
+
     pb.set_position(30);
     if !skip_scan {
         pb.set_message("Running security scan...");
         let scanner = SecurityScanner::new();
-        let report = scanner.scan(&skill_id, &store.skill_path(&skill_id))?;
+        let report = match scanner.scan(skill_id.as_str(), staged.path()) {
+            Ok(report) => report,
+            Err(e) => {
+                store.abort_staged(staged)?;
+                return Err(e.into());
+            }
+        };
 
         if !report.passed {
+            store.abort_staged(staged)?;
             pb.finish_with_message("Scan failed!");
             println!();
             println!("{} Security scan blocked installation:", "⚠️".red());
@@ -138,6 +144,9 @@ pub async fn run(
         }
     }
 
+    pb.set_message("Installing into store...");
+    store.commit_staged(staged)?;
+
     pb.set_position(70);
     pb.set_message("Syncing to tools...");
 
@@ -145,7 +154,11 @@ pub async fn run(
     for adapter in create_default_adapters() {
         engine.register_adapter(adapter);
     }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
 
+    let target_tools = requested_tools.unwrap_or_else(|| super::tools::registered_tools(&engine));
     let available_tools = engine.detect_tools();
     let tools_to_sync: Vec<ToolType> = target_tools
         .into_iter()
@@ -174,6 +187,8 @@ pub async fn run(
         }
     }
 
+    super::restore::print_backups(&engine);
+
     pb.set_position(100);
     pb.finish_with_message("Done!");
 
@@ -181,8 +196,193 @@ pub async fn run(
     println!(
         "{} Skill {} installed successfully!",
         "✓".green(),
-        skill_id.bold()
+        skill_id.as_str().bold()
     );
 
     Ok(())
 }
+
+/// Install and project every skill declared in the nearest skillshub.toml
+///
+/// With `locked`, skills are reproduced from skillshub.lock instead of being
+/// resolved, and any hash mismatch fails the install.
+pub async fn run_manifest(tools: Option<&str>, locked: bool) -> anyhow::Result<()> {
+    let requested_tools = tools.map(super::tools::parse_tools).transpose()?;
+    let cwd = std::env::current_dir()?;
+    let manifest_path = ProjectManifest::find(&cwd);
+    let project_root = manifest_path
+        .as_deref()
+        .and_then(|path| path.parent())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| cwd.clone());
+    let lock_path = project_root.join(LOCK_FILE);
+
+    let registries = RegistryManager::new()?;
+    let mut store = LocalStore::default_store()?;
+
+    let statuses = if locked {
+        if !lock_path.exists() {
+            return Err(anyhow::anyhow!(
+                "--locked given but no {} found in {}",
+                LOCK_FILE,
+                project_root.display()
+            ));
+        }
+
+        println!(
+            "{} Installing locked skills from {}",
+            "🔒".green(),
+            lock_path.display().to_string().bold()
+        );
+        println!();
+
+        let lockfile = Lockfile::load(&lock_path)?;
+        println!("{}", "Verifying locked skills...".dimmed());
+        lockfile::install_locked(
+            &lockfile,
+            &project_root,
+            &mut store,
+            &registries.aggregated(),
+        )
+        .await
+    } else {
+        let manifest_path = manifest_path.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "No skill given and no {} found in {} or its parents",
+                MANIFEST_FILE,
+                cwd.display()
+            )
+        })?;
+
+        println!(
+            "{} Installing skills from {}",
+            "📦".green(),
+            manifest_path.display().to_string().bold()
+        );
+        println!();
+
+        let manifest = ProjectManifest::load(manifest_path)?;
+        println!("{}", "Resolving skills...".dimmed());
+        let statuses =
+            manifest::install_missing(&manifest, &project_root, &mut store, &registries).await;
+
+        let installed: Vec<SkillId> = manifest
+            .skills
+            .keys()
+            .filter(|id| store.is_installed(id))
+            .cloned()
+            .collect();
+        Lockfile::from_store(&store, Some(&installed))?
+            .relative_to(&project_root)
+            .save(&lock_path)?;
+        statuses
+    };
+    print_statuses(&statuses);
+
+    let failed = statuses
+        .iter()
+        .filter(|(_, s)| matches!(s, ManifestSkillStatus::Failed { .. }))
+        .count();
+
+    // A lockfile without a manifest only reproduces the store
+    let Some(manifest_path) = manifest_path else {
+        println!();
+        return if failed == 0 {
+            println!("{} Store matches {}", "✓".green(), LOCK_FILE);
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "{} locked skills could not be installed",
+                failed
+            ))
+        };
+    };
+
+    let manifest = ProjectManifest::load(&manifest_path)?;
+    let target_tools: Vec<ToolType> = match requested_tools {
+        Some(tools) => tools,
+        None => manifest.tool_types()?,
+    };
+
+    println!();
+    println!("{}", "Projecting into project tool directories...".dimmed());
+
+    let mut engine = SyncEngine::for_project(store, &project_root);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    let report = manifest::project_skills(&manifest, &target_tools, &mut engine, |tool| {
+        config.strategy_for_tool(tool.key())
+    });
+
+    for (skill_id, tool) in &report.projected {
+        println!("  {} {} → {}", "✓".green(), skill_id, tool.display_name());
+    }
+    for (skill_id, tool, error) in &report.missing {
+        println!(
+            "  {} {} → {} ({})",
+            "✗".red(),
+            skill_id,
+            tool.display_name(),
+            error
+        );
+    }
+
+    if !report.extra.is_empty() {
+        println!();
+        println!(
+            "{} Skills not declared in {}:",
+            "⚠️".yellow(),
+            MANIFEST_FILE
+        );
+        for (skill_id, tool) in &report.extra {
+            println!(
+                "    {} {} in {}",
+                "•".yellow(),
+                skill_id,
+                tool.display_name()
+            );
+        }
+    }
+
+    println!();
+    if failed == 0 && report.missing.is_empty() {
+        println!("{} Project skills are up to date!", "✓".green());
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{} skills could not be resolved, {} projections failed",
+            failed,
+            report.missing.len()
+        ))
+    }
+}
+
+fn print_statuses(statuses: &[(SkillId, ManifestSkillStatus)]) {
+    for (skill_id, status) in statuses {
+        match status {
+            ManifestSkillStatus::Satisfied { version } => {
+                println!(
+                    "  {} {} v{} (already installed)",
+                    "✓".green(),
+                    skill_id,
+                    version
+                )
+            }
+            ManifestSkillStatus::Installed { version, registry } => println!(
+                "  {} {} v{} installed from {}",
+                "✓".green(),
+                skill_id,
+                version,
+                registry
+            ),
+            ManifestSkillStatus::Failed { error } => {
+                println!("  {} {}: {}", "✗".red(), skill_id, error)
+            }
+        }
+    }
+}
@@ -23,10 +23,13 @@ pub async fn run(detailed: bool) -> anyhow::Result<()> {
 
     for record in &installed {
         if detailed {
-            println!("  {}", record.skill_id.bold());
+            println!("  {}", record.skill_id.as_str().bold());
             println!("    Version: {}", record.version.version);
             println!("    Hash: {}", &record.version.content_hash[..16]);
             println!("    Installed: {}", record.installed_at);
+            if record.disabled {
+                println!("    Status: {}", "disabled".yellow());
+            }
             println!("    Source: {}", record.source.display().dimmed());
             if !record.projected_tools.is_empty() {
                 println!("    Tools: {}", record.projected_tools.join(", "));
@@ -42,10 +45,15 @@ pub async fn run(detailed: bool) -> anyhow::Result<()> {
             println!();
         } else {
             println!(
-                "  {} {} {}",
+                "  {} {} {}{}",
                 "•".cyan(),
-                record.skill_id.bold(),
-                format!("v{}", record.version.version).dimmed()
+                record.skill_id.as_str().bold(),
+                format!("v{}", record.version.version).dimmed(),
+                if record.disabled {
+                    format!(" {}", "(disabled)".yellow())
+                } else {
+                    String::new()
+                }
             );
         }
     }
@@ -0,0 +1,41 @@
+//! Lock command - pin installed skills to their resolved versions
+
+use colored::Colorize;
+use std::path::Path;
+
+use skillshub_core::lockfile::Lockfile;
+use skillshub_core::store::LocalStore;
+
+pub fn run(output: &Path) -> anyhow::Result<()> {
+    let store = LocalStore::default_store()?;
+    let root = std::env::current_dir()?;
+    let lockfile = Lockfile::from_store(&store, None)?.relative_to(&root);
+
+    lockfile.save(output)?;
+
+    println!(
+        "{} Locked {} skills to {}",
+        "🔒".green(),
+        lockfile.skills.len(),
+        output.display().to_string().bold()
+    );
+    for (skill_id, locked) in &lockfile.skills {
+        let pin = locked
+            .commit
+            .as_deref()
+            .map(|c| format!("@{}", &c[..c.len().min(12)]))
+            .unwrap_or_default();
+        println!(
+            "  {} {} v{} {}{}",
+            "•".cyan(),
+            skill_id,
+            locked.version,
+            locked.url.dimmed(),
+            pin.dimmed()
+        );
+    }
+    println!();
+    println!("{}", "Reproduce with: skillshub install --locked".dimmed());
+
+    Ok(())
+}
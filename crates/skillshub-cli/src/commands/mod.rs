@@ -1,12 +1,21 @@
 //! CLI command implementations
 
+pub mod alias;
+pub mod collect;
 pub mod discover;
+pub mod enable;
+pub mod gc;
 pub mod info;
 pub mod install;
 pub mod list;
+pub mod lock;
 pub mod registry;
+pub mod restore;
+pub mod rollback;
 pub mod scan;
 pub mod sync;
 pub mod tools;
 pub mod uninstall;
 pub mod update;
+pub mod verify;
+pub mod watch;
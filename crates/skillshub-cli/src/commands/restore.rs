@@ -0,0 +1,56 @@
+//! Restore command - put back tool directory entries moved aside by a sync
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::backup;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+
+pub fn run(id: Option<&str>) -> anyhow::Result<()> {
+    let store = LocalStore::default_store()?;
+
+    let Some(id) = id else {
+        let backups = backup::list(&store)?;
+        if backups.is_empty() {
+            println!("{}", "No backups.".dimmed());
+            return Ok(());
+        }
+        println!("{} Backups of entries replaced by syncs:", "🗄️".cyan());
+        println!();
+        for entry in &backups {
+            println!("  {}", entry.id.bold());
+            println!(
+                "    {} ({}, replaced by {})",
+                entry.original_path.display(),
+                entry.tool.display_name(),
+                entry.skill_id
+            );
+        }
+        println!();
+        println!("Restore one with {}", "skillshub restore <id>".cyan());
+        return Ok(());
+    };
+
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let entry = engine.restore_backup(id)?;
+    println!("{} Restored {}", "✓".green(), entry.original_path.display());
+
+    Ok(())
+}
+
+/// Report the backups a sync made, with how to undo them
+pub fn print_backups(engine: &SyncEngine) {
+    for entry in engine.backups() {
+        println!(
+            "  {} Moved {} aside (backup {}); undo with {}",
+            "!".yellow(),
+            entry.original_path.display(),
+            entry.id,
+            format!("skillshub restore {}", entry.id).cyan()
+        );
+    }
+}
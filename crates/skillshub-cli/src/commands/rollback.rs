@@ -0,0 +1,52 @@
+//! Rollback command - re-activate a previously installed skill version
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+
+pub fn run(skill: &str, version: Option<&str>) -> anyhow::Result<()> {
+    let mut store = LocalStore::default_store()?;
+    let skill = store.resolve(skill)?;
+    let current = store
+        .get_record(&skill)
+        .map(|r| r.version.version.clone())
+        .ok_or_else(|| anyhow::anyhow!("Skill '{}' is not installed", skill))?;
+
+    let record = store.rollback(&skill, version)?;
+
+    println!(
+        "{} Rolled back {} from v{} to v{} ({})",
+        "↩".green(),
+        skill.as_str().bold(),
+        current,
+        record.version.version,
+        &record.version.content_hash[..record.version.content_hash.len().min(12)]
+    );
+
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+
+    let refreshed = engine.refresh_skill(&skill)?;
+    for tool in &refreshed {
+        println!("  {} Updated {}", "✓".green(), tool.display_name());
+    }
+
+    if !record.history.is_empty() {
+        println!();
+        println!("{}", "Retained versions:".dimmed());
+        for v in &record.history {
+            println!(
+                "  {} v{} ({})",
+                "•".dimmed(),
+                v.version,
+                &v.content_hash[..v.content_hash.len().min(12)]
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -17,10 +17,9 @@ pub async fn run(target: &str, policy: &str) -> anyhow::Result<()> {
     } else {
         // Look in store
         let store = LocalStore::default_store()?;
-        if store.is_installed(target) {
-            store.skill_path(target)
-        } else {
-            return Err(anyhow::anyhow!("Skill or path not found: {}", target));
+        match store.resolve(target) {
+            Ok(skill_id) => store.skill_path(&skill_id),
+            Err(_) => return Err(anyhow::anyhow!("Skill or path not found: {}", target)),
         }
     };
 
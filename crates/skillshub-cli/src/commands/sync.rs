@@ -1,45 +1,83 @@
 //! Sync command - synchronize skills to tools
 
+use std::collections::HashMap;
+use std::path::Path;
+
 use colored::Colorize;
 
 use skillshub_core::adapters::create_default_adapters;
-use skillshub_core::models::{SyncStrategy, ToolType};
+use skillshub_core::models::{SyncActionType, SyncPlan, SyncStrategy, ToolType};
 use skillshub_core::store::LocalStore;
 use skillshub_core::sync::SyncEngine;
+use skillshub_core::AppConfig;
 
-pub async fn run(skill: Option<&str>, tools: Option<&str>, reconcile: bool) -> anyhow::Result<()> {
-    println!("{} Syncing skills...", "🔄".cyan());
-    println!();
-
+pub async fn run(
+    skill: Option<&str>,
+    tools: Option<&str>,
+    reconcile: bool,
+    project: Option<&Path>,
+    clean: bool,
+    dry_run: bool,
+    json: bool,
+) -> anyhow::Result<()> {
     let store = LocalStore::default_store()?;
-    let mut engine = SyncEngine::new(store);
+    let mut engine = match project {
+        Some(root) => {
+            if !root.is_dir() {
+                return Err(anyhow::anyhow!(
+                    "Project directory '{}' does not exist",
+                    root.display()
+                ));
+            }
+            SyncEngine::for_project(store, root)
+        }
+        None => SyncEngine::new(store),
+    };
 
     for adapter in create_default_adapters() {
         engine.register_adapter(adapter);
     }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
 
-    // Parse target tools
-    let target_tools: Vec<ToolType> = if let Some(tools_str) = tools {
-        tools_str
-            .split(',')
-            .filter_map(|t| match t.trim().to_lowercase().as_str() {
-                "claude" => Some(ToolType::Claude),
-                "cursor" => Some(ToolType::Cursor),
-                "gemini" => Some(ToolType::Gemini),
-                "opencode" => Some(ToolType::OpenCode),
-                _ => None,
-            })
-            .collect()
-    } else {
-        vec![
-            ToolType::Claude,
-            ToolType::Cursor,
-            ToolType::Gemini,
-            ToolType::OpenCode,
-        ]
+    if clean {
+        if dry_run {
+            return print_plan(&engine.plan_clean(), json);
+        }
+        println!(
+            "{} Removing projections ({})...",
+            "🧹".cyan(),
+            engine.scope()
+        );
+        println!();
+        let removed = engine.clean_projections()?;
+        for (skill_id, tool) in &removed {
+            println!(
+                "  {} Removed {} from {}",
+                "✓".green(),
+                skill_id,
+                tool.display_name()
+            );
+        }
+        println!();
+        println!("{} Removed {} projections", "✓".green(), removed.len());
+        return Ok(());
+    }
+
+    if !json {
+        println!("{} Syncing skills ({})...", "🔄".cyan(), engine.scope());
+        println!();
+    }
+
+    // Parse target tools; without --tools every tool with an adapter is considered
+    let target_tools: Vec<ToolType> = match tools {
+        Some(tools_str) => super::tools::parse_tools(tools_str)?,
+        None => super::tools::registered_tools(&engine),
     };
 
-    if reconcile {
+    // A dry run's plan already lists the repairs
+    if reconcile && !dry_run {
         println!("{}", "Checking for drift...".dimmed());
         let drifts = engine.check_drift();
 
@@ -55,12 +93,15 @@ pub async fn run(skill: Option<&str>, tools: Option<&str>, reconcile: bool) -> a
                     tool.display_name(),
                     drift.drift_type
                 );
+                for file in &drift.files {
+                    println!("        {}", file.display().to_string().dimmed());
+                }
             }
             println!();
             println!("{}", "Repairing drifts...".dimmed());
 
             for (skill_id, tool, _) in drifts {
-                match engine.sync_skill(&skill_id, tool, SyncStrategy::Auto) {
+                match engine.sync_skill(&skill_id, tool, config.strategy_for_tool(tool.key())) {
                     Ok(_) => println!(
                         "  {} Repaired {} in {}",
                         "✓".green(),
@@ -80,17 +121,49 @@ pub async fn run(skill: Option<&str>, tools: Option<&str>, reconcile: bool) -> a
         .into_iter()
         .cloned()
         .collect();
-    let skills_to_sync = if let Some(skill_id) = skill {
+    let skills_to_sync: Vec<_> = if let Some(skill) = skill {
+        let skill_id = engine.store().resolve(skill)?;
         installed
             .into_iter()
             .filter(|r| r.skill_id == skill_id)
             .collect()
     } else {
-        installed
+        // Disabled skills stay out of tools unless named explicitly
+        installed.into_iter().filter(|r| !r.disabled).collect()
     };
 
-    if skills_to_sync.is_empty() {
-        println!("{}", "No skills to sync.".dimmed());
+    let mut plan = SyncPlan::new();
+    let mut skill_targets = HashMap::new();
+    for record in &skills_to_sync {
+        // Without --tools, each skill only goes to its own targets
+        let targets = engine.target_tools(&record.skill_id);
+        let tools_for_skill = target_tools
+            .iter()
+            .filter(|t| tools.is_some() || targets.contains(t));
+        // Each tool is planned with its configured strategy, as watch and install do
+        for tool in tools_for_skill {
+            plan.merge(engine.plan_sync(
+                &record.skill_id,
+                &[*tool],
+                config.strategy_for_tool(tool.key()),
+            )?);
+        }
+        skill_targets.insert(record.skill_id.clone(), targets);
+    }
+    // Skills deleted from the hub or no longer assigned to a tool
+    if skill.is_none() && tools.is_none() {
+        plan.to_remove.extend(
+            engine
+                .plan_full_sync(|tool| config.strategy_for_tool(tool.key()))?
+                .to_remove,
+        );
+    }
+
+    if dry_run {
+        return print_plan(&plan, json);
+    }
+    if plan.is_empty() {
+        println!("{}", "Everything is up to date.".dimmed());
         return Ok(());
     }
 
@@ -102,32 +175,125 @@ pub async fn run(skill: Option<&str>, tools: Option<&str>, reconcile: bool) -> a
         target_tools.len()
     );
 
-    for record in skills_to_sync {
-        for tool in &target_tools {
-            match engine.sync_skill(&record.skill_id, *tool, SyncStrategy::Auto) {
-                Ok(_) => {
-                    println!(
-                        "  {} {} → {}",
-                        "✓".green(),
-                        record.skill_id,
-                        tool.display_name()
-                    );
-                }
-                Err(e) => {
-                    println!(
-                        "  {} {} → {} ({})",
-                        "✗".red(),
-                        record.skill_id,
-                        tool.display_name(),
-                        e
-                    );
-                }
+    for result in engine.execute_plan(&plan)? {
+        let tool = result.tool.display_name();
+        match (&result.error, result.action) {
+            (None, SyncActionType::Remove) => {
+                println!(
+                    "  {} Removed {} from {}",
+                    "✓".green(),
+                    result.skill_id,
+                    tool
+                )
             }
+            (None, _) => println!("  {} {} → {}", "✓".green(), result.skill_id, tool),
+            (Some(e), _) => println!("  {} {} → {} ({})", "✗".red(), result.skill_id, tool, e),
+        }
+
+        // Explicitly synced tools become assignments so later
+        // syncs keep the projection instead of pruning it
+        let Some(targets) = skill_targets.get(&result.skill_id) else {
+            continue;
+        };
+        if result.success && result.action == SyncActionType::Add && !targets.contains(&result.tool)
+        {
+            let Some(record) = engine.store().get_record(&result.skill_id) else {
+                continue;
+            };
+            let mut assigned = record.projected_tools.clone();
+            if assigned.is_empty() {
+                assigned = targets.iter().map(tool_key).collect();
+            }
+            assigned.push(tool_key(&result.tool));
+            engine
+                .store_mut()
+                .update_projected_tools(&result.skill_id, assigned)?;
         }
     }
 
+    super::restore::print_backups(&engine);
+
     println!();
     println!("{} Sync complete!", "✓".green());
 
     Ok(())
 }
+
+/// Show what a sync would do, as text or as the plan's JSON
+fn print_plan(plan: &SyncPlan, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(plan)?);
+        return Ok(());
+    }
+
+    println!("{} Dry run, nothing was changed", "📋".cyan());
+    println!();
+    if plan.is_empty() {
+        println!("{}", "Everything is up to date.".dimmed());
+        return Ok(());
+    }
+
+    for collect in &plan.to_collect {
+        println!(
+            "  {} collect {} from {}",
+            "↓".cyan(),
+            collect.skill_id,
+            collect.source_path.display()
+        );
+    }
+    for conflict in &plan.conflicts {
+        println!(
+            "  {} {} differs between tools and would not be collected (see skillshub collect)",
+            "⚠".yellow(),
+            conflict.skill_id
+        );
+    }
+    for action in plan.actions() {
+        let (symbol, verb) = match action.action {
+            SyncActionType::Add => ("+".green(), strategy_verb(action.strategy)),
+            SyncActionType::Update => ("~".cyan(), strategy_verb(action.strategy)),
+            SyncActionType::Repair => ("!".yellow(), "repair"),
+            SyncActionType::Remove => ("-".red(), "remove"),
+        };
+        println!(
+            "  {} {} {} → {}  {}",
+            symbol,
+            verb,
+            action.skill_id,
+            action.tool.display_name(),
+            action.target_path.display().to_string().dimmed()
+        );
+        if action.overwrites {
+            println!(
+                "      {}",
+                "replaces a folder SkillsHub did not create (moved to a backup)".yellow()
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{} to collect, {} to add, {} to update, {} to repair, {} to remove",
+        plan.to_collect.len(),
+        plan.to_add.len(),
+        plan.to_update.len(),
+        plan.to_repair.len(),
+        plan.to_remove.len()
+    );
+    Ok(())
+}
+
+fn strategy_verb(strategy: SyncStrategy) -> &'static str {
+    match strategy {
+        SyncStrategy::Auto => "link (or copy)",
+        SyncStrategy::Link => "link",
+        SyncStrategy::RelativeLink => "relative link",
+        SyncStrategy::Hardlink => "hardlink",
+        SyncStrategy::Copy => "copy",
+    }
+}
+
+/// Assignment key of a tool, as stored in `projected_tools`
+fn tool_key(tool: &ToolType) -> String {
+    tool.key().to_string()
+}
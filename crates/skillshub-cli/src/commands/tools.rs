@@ -3,7 +3,9 @@
 use colored::Colorize;
 
 use skillshub_core::adapters::create_default_adapters;
-use skillshub_core::models::ToolType;
+use skillshub_core::models::{RenderFormat, ToolType};
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::AppConfig;
 
 pub async fn list() -> anyhow::Result<()> {
     println!("{} Known Agent Tools:", "🔧".cyan());
@@ -108,3 +110,95 @@ pub async fn status() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+pub fn set_enabled(tool: &str, enabled: bool) -> anyhow::Result<()> {
+    let tool_type =
+        ToolType::from_key(tool).ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool))?;
+    let tool_key = tool_type.key().to_string();
+
+    let mut config = AppConfig::load()?;
+    config
+        .disabled_tools
+        .retain(|t| !t.eq_ignore_ascii_case(&tool_key));
+    if !enabled {
+        config.disabled_tools.push(tool_key);
+    }
+    config.save()?;
+
+    println!(
+        "{} {} is {} for full syncs",
+        "✓".green(),
+        tool_type.display_name().bold(),
+        if enabled { "enabled" } else { "disabled" }
+    );
+
+    Ok(())
+}
+
+pub fn set_format(tool: &str, format: &str) -> anyhow::Result<()> {
+    let tool_type =
+        ToolType::from_key(tool).ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool))?;
+    let tool_key = tool_type.key().to_string();
+
+    let render_format = match format {
+        "skill-folder" | "folder" => RenderFormat::SkillFolder,
+        "native" => RenderFormat::native_for(tool_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} has no rule format besides skill folders",
+                tool_type.display_name()
+            )
+        })?,
+        other => RenderFormat::native_for(tool_type)
+            .filter(|native| native.to_string() == other)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown format '{}' for {} (use native or skill-folder)",
+                    other,
+                    tool_type.display_name()
+                )
+            })?,
+    };
+
+    let mut config = AppConfig::load()?;
+    if render_format == RenderFormat::SkillFolder {
+        config.tool_render_formats.remove(&tool_key);
+    } else {
+        config
+            .tool_render_formats
+            .insert(tool_key.clone(), render_format);
+    }
+    config.save()?;
+
+    println!(
+        "{} {} skills are projected as {}",
+        "✓".green(),
+        tool_type.display_name().bold(),
+        render_format.to_string().cyan()
+    );
+    println!(
+        "  Run {} to move existing projections",
+        "skillshub sync".cyan()
+    );
+
+    Ok(())
+}
+
+/// Tools named in a comma-separated `--tools` list, by their keys
+pub fn parse_tools(tools: &str) -> anyhow::Result<Vec<ToolType>> {
+    tools
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| ToolType::from_key(t).ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", t.trim())))
+        .collect()
+}
+
+/// Every tool the engine has an adapter for, each once
+pub fn registered_tools(engine: &SyncEngine) -> Vec<ToolType> {
+    let mut tools = Vec::new();
+    for adapter in engine.adapters() {
+        if !tools.contains(&adapter.tool_type()) {
+            tools.push(adapter.tool_type());
+        }
+    }
+    tools
+}
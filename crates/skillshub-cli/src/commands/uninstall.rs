@@ -27,14 +27,17 @@ pub async fn run(skill: &str, tools: Option<&str>) -> anyhow::Result<()> {
     });
 
     let store = LocalStore::default_store()?;
-
-    if !store.is_installed(skill) {
-        println!(
-            "{}",
-            format!("Skill '{}' is not installed.", skill).yellow()
-        );
-        return Ok(());
-    }
+    let skill = match store.resolve(skill) {
+        Ok(skill) => skill,
+        Err(skillshub_core::Error::SkillNotFound(_)) => {
+            println!(
+                "{}",
+                format!("Skill '{}' is not installed.", skill).yellow()
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
 
     // Confirm uninstall
     let confirm_msg = if target_tools.is_some() {
@@ -56,25 +59,18 @@ pub async fn run(skill: &str, tools: Option<&str>) -> anyhow::Result<()> {
     if let Some(tools) = target_tools {
         // Remove from specific tools only
         for tool in tools {
-            match engine.unsync_skill(skill, tool) {
+            match engine.unsync_skill(&skill, tool) {
                 Ok(_) => println!("  {} Removed from {}", "✓".green(), tool.display_name()),
                 Err(e) => println!("  {} Failed for {}: {}", "✗".red(), tool.display_name(), e),
             }
         }
     } else {
-        // Complete uninstall
-        let all_tools = vec![
-            ToolType::Claude,
-            ToolType::Cursor,
-            ToolType::Gemini,
-            ToolType::OpenCode,
-        ];
-
-        for tool in all_tools {
-            let _ = engine.unsync_skill(skill, tool);
+        // Complete uninstall: remove every projection SkillsHub recorded
+        for tool in engine.unsync_all(&skill)? {
+            println!("  {} Removed from {}", "✓".green(), tool.display_name());
         }
 
-        engine.store_mut().remove_skill(skill)?;
+        engine.store_mut().remove_skill(&skill)?;
         println!("  {} Removed from local store", "✓".green());
     }
 
@@ -1,8 +1,8 @@
 //! Update command - update installed skills
 
 use colored::Colorize;
+use skillshub_core::hash;
 use skillshub_core::models::SkillSource;
-use skillshub_core::registry::calculate_dir_hash;
 use skillshub_core::store::LocalStore;
 
 pub async fn run(skill: Option<&str>) -> anyhow::Result<()> {
@@ -21,9 +21,10 @@ pub async fn run(skill: Option<&str>) -> anyhow::Result<()> {
     let skills_to_update: Vec<_> = if target == "all" {
         installed
     } else {
+        let skill_id = store.resolve(target)?;
         installed
             .into_iter()
-            .filter(|r| r.skill_id == target)
+            .filter(|r| r.skill_id == skill_id)
             .collect()
     };
 
@@ -42,7 +43,7 @@ pub async fn run(skill: Option<&str>) -> anyhow::Result<()> {
                     continue;
                 }
 
-                let current_hash = calculate_dir_hash(&path.to_path_buf())?;
+                let current_hash = hash::digest(path)?;
                 if current_hash != record.version.content_hash
                     && !record.version.content_hash.is_empty()
                 {
@@ -0,0 +1,85 @@
+//! Verify command - check the store against its install records
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::models::{IntegrityIssue, IntegrityIssueType};
+use skillshub_core::registry::RegistryManager;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+
+pub async fn run(repair: bool) -> anyhow::Result<()> {
+    let mut store = LocalStore::default_store()?;
+    let mut report = store.verify()?;
+
+    println!("{} Checked {} skills", "🔍".cyan(), report.checked);
+    if report.is_clean() {
+        println!("{} Store is consistent", "✓".green());
+        return Ok(());
+    }
+
+    if repair {
+        let registries = RegistryManager::new()?;
+        store.repair(&mut report, &registries.aggregated()).await;
+
+        // Copies in tool directories still hold the damaged files
+        let mut engine = SyncEngine::new(store);
+        for adapter in create_default_adapters() {
+            engine.register_adapter(adapter);
+        }
+        for issue in report
+            .issues
+            .iter()
+            .filter(|i| i.repaired && i.issue_type != IntegrityIssueType::Orphaned)
+        {
+            engine.refresh_skill(&issue.skill_id)?;
+        }
+    }
+
+    println!();
+    for issue in &report.issues {
+        print_issue(issue);
+    }
+
+    let unresolved = report.unresolved().count();
+    println!();
+    if unresolved == 0 {
+        println!("{} Repaired {} issues", "✓".green(), report.issues.len());
+    } else if repair {
+        anyhow::bail!("{} issues could not be repaired", unresolved);
+    } else {
+        println!(
+            "{} {} issues found. Run {} to fix them.",
+            "⚠".yellow(),
+            unresolved,
+            "skillshub verify --repair".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_issue(issue: &IntegrityIssue) {
+    let marker = if issue.repaired {
+        "✓".green()
+    } else {
+        "✗".red()
+    };
+    println!(
+        "  {} {} {}",
+        marker,
+        issue.skill_id.as_str().bold(),
+        format!("({})", issue.issue_type).yellow()
+    );
+    println!("    {}", issue.path.display().to_string().dimmed());
+    if let (Some(expected), Some(actual)) = (&issue.expected_hash, &issue.actual_hash) {
+        println!(
+            "    expected {} found {}",
+            &expected[..expected.len().min(12)],
+            &actual[..actual.len().min(12)]
+        );
+    }
+    if let Some(error) = &issue.repair_error {
+        println!("    {}", error.red());
+    }
+}
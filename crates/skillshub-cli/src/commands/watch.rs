@@ -0,0 +1,109 @@
+//! Watch command - keep tools in sync as the store and tool directories change
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::models::{SkillId, ToolType};
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::watch::{self, WatchReport};
+use skillshub_core::AppConfig;
+
+pub async fn run(project: Option<PathBuf>, debounce_ms: u64) -> anyhow::Result<()> {
+    let store = LocalStore::default_store()?;
+    let mut engine = match project {
+        Some(root) => {
+            if !root.is_dir() {
+                return Err(anyhow::anyhow!(
+                    "Project directory '{}' does not exist",
+                    root.display()
+                ));
+            }
+            SyncEngine::for_project(store, root)
+        }
+        None => SyncEngine::new(store),
+    };
+
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    println!(
+        "{} Watching skills ({}); press Ctrl+C to stop",
+        "👀".cyan(),
+        engine.scope()
+    );
+    for dir in engine.watched_dirs().iter().filter(|d| d.is_dir()) {
+        println!("  {}", dir.display().to_string().dimmed());
+    }
+    println!();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut watcher = {
+        let stop = stop.clone();
+        tokio::task::spawn_blocking(move || {
+            watch::watch(
+                &mut engine,
+                Duration::from_millis(debounce_ms),
+                &stop,
+                |tool| config.strategy_for_tool(tool.key()),
+                print_summary,
+            )
+        })
+    };
+
+    // Each action is logged by the watcher; only a summary per pass is printed
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            result?;
+            stop.store(true, Ordering::Relaxed);
+            watcher.await??;
+        }
+        finished = &mut watcher => finished??,
+    }
+    println!();
+    println!("{} Stopped watching", "✓".green());
+    Ok(())
+}
+
+fn print_summary(report: &WatchReport) {
+    if report.is_empty() {
+        return;
+    }
+    let count = |actions: &[(SkillId, ToolType, bool)]| actions.iter().filter(|a| a.2).count();
+    let summary = format!(
+        "{} collected, {} synced, {} removed, {} repaired",
+        report.collected.len(),
+        count(&report.distributed),
+        count(&report.removed),
+        count(&report.repaired)
+    );
+    let trigger = if report.changed.is_empty() {
+        "Initial sync".to_string()
+    } else {
+        format!("{} change(s)", report.changed.len())
+    };
+
+    if report.error.is_some() {
+        println!("{} {}: sync failed", "✗".red(), trigger);
+    } else if report.conflicts.is_empty() {
+        println!("{} {}: {}", "✓".green(), trigger, summary);
+    } else {
+        println!(
+            "{} {}: {}, {} conflict(s) to resolve with {}",
+            "⚠️".yellow(),
+            trigger,
+            summary,
+            report.conflicts.len(),
+            "skillshub collect".cyan()
+        );
+    }
+}
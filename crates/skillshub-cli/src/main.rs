@@ -2,6 +2,8 @@
 
 mod commands;
 
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -36,19 +38,29 @@ enum Commands {
         limit: usize,
     },
 
-    /// Install a skill
+    /// Install a skill, or every skill declared in the repository's skillshub.toml
     Install {
-        /// Skill ID or source URL
-        skill: String,
+        /// Skill ID or source URL (omit to install from skillshub.toml)
+        skill: Option<String>,
         /// Target tools (comma-separated)
         #[arg(short, long)]
         tools: Option<String>,
-        /// Sync strategy (auto/link/copy)
+        /// Sync strategy (auto/link/relative-link/hardlink/copy)
         #[arg(short, long, default_value = "auto")]
         sync: String,
         /// Skip security scan
         #[arg(long)]
         skip_scan: bool,
+        /// Reproduce the exact versions pinned in skillshub.lock
+        #[arg(long, conflicts_with = "skill")]
+        locked: bool,
+    },
+
+    /// Write a skillshub.lock pinning every installed skill
+    Lock {
+        /// Output path
+        #[arg(short, long, default_value = "skillshub.lock")]
+        output: PathBuf,
     },
 
     /// Update installed skills
@@ -57,6 +69,29 @@ enum Commands {
         skill: Option<String>,
     },
 
+    /// Roll a skill back to a previously installed version
+    Rollback {
+        /// Skill ID
+        skill: String,
+        /// Version or content hash prefix (defaults to the previous version)
+        #[arg(value_name = "VERSION")]
+        target: Option<String>,
+    },
+
+    /// Check installed skills against their recorded content hashes
+    Verify {
+        /// Restore damaged skills from their source and register orphans
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// Delete orphaned store data, leftover temp folders and unused git caches
+    Gc {
+        /// Only report what would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Uninstall a skill
     Uninstall {
         /// Skill ID to uninstall
@@ -76,6 +111,28 @@ enum Commands {
         /// Check and repair drift
         #[arg(long)]
         reconcile: bool,
+        /// Project into a repository's tool directories (e.g. <repo>/.claude/skills/)
+        #[arg(long, value_name = "REPO")]
+        project: Option<PathBuf>,
+        /// Remove all projections previously made in the selected scope
+        #[arg(long, conflicts_with = "reconcile")]
+        clean: bool,
+        /// Show what would be synced without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Print the dry run's plan as JSON
+        #[arg(long, requires = "dry_run")]
+        json: bool,
+    },
+
+    /// Keep tools in sync while the store and tool directories change
+    Watch {
+        /// Watch a repository's tool directories instead of the global ones
+        #[arg(long, value_name = "REPO")]
+        project: Option<PathBuf>,
+        /// Milliseconds without changes before a sync runs
+        #[arg(long, default_value_t = 2000)]
+        debounce: u64,
     },
 
     /// Scan a skill for security issues
@@ -111,6 +168,47 @@ enum Commands {
         /// Skill ID
         skill: String,
     },
+
+    /// Show or set the name a skill's directory has in tools
+    Alias {
+        /// Skill ID
+        skill: String,
+        /// New directory name (defaults to the last segment of the ID)
+        alias: Option<String>,
+        /// Go back to the default name
+        #[arg(long, conflicts_with = "alias")]
+        clear: bool,
+    },
+
+    /// Keep a skill installed but remove it from all tools
+    Disable {
+        /// Skill ID
+        skill: String,
+    },
+
+    /// Sync a disabled skill to tools again
+    Enable {
+        /// Skill ID
+        skill: String,
+    },
+
+    /// Import skills found in tools into the hub, or settle differing copies
+    Collect {
+        /// Skill whose differing copies to settle
+        skill: Option<String>,
+        /// Keep the copy found in this tool (e.g. claude, cursor)
+        #[arg(long = "use", value_name = "TOOL", requires = "skill")]
+        use_tool: Option<String>,
+        /// Keep every copy, the others as <skill>-<tool>
+        #[arg(long, requires = "skill", conflicts_with = "use_tool")]
+        keep_both: bool,
+    },
+
+    /// List backups of entries replaced by syncs, or restore one
+    Restore {
+        /// Backup ID (lists backups when omitted)
+        id: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -121,6 +219,23 @@ enum ToolsAction {
     Detect,
     /// Show tool status
     Status,
+    /// Include a tool in full syncs again
+    Enable {
+        /// Tool key (e.g. claude, cursor)
+        tool: String,
+    },
+    /// Leave a tool out of full syncs
+    Disable {
+        /// Tool key (e.g. claude, cursor)
+        tool: String,
+    },
+    /// Choose how skills are laid out for a tool
+    Format {
+        /// Tool key (e.g. cursor, copilot, windsurf, kiro)
+        tool: String,
+        /// native (the tool's rule format) or skill-folder
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -148,18 +263,20 @@ async fn main() -> anyhow::Result<()> {
     // Setup logging
     let log_level = if cli.verbose { "debug" } else { "info" };
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .with(tracing_subscriber::EnvFilter::new(log_level))
         .init();
 
-    // Print banner
-    println!("{}", "╔═══════════════════════════════════════════╗".cyan());
-    println!(
-        "{}",
-        "║      SkillsHub - Agent Skills Manager      ║".cyan()
-    );
-    println!("{}", "╚═══════════════════════════════════════════╝".cyan());
-    println!();
+    // Print banner, unless the output is meant for other programs
+    if !matches!(cli.command, Commands::Sync { json: true, .. }) {
+        println!("{}", "╔═══════════════════════════════════════════╗".cyan());
+        println!(
+            "{}",
+            "║      SkillsHub - Agent Skills Manager      ║".cyan()
+        );
+        println!("{}", "╚═══════════════════════════════════════════╝".cyan());
+        println!();
+    }
 
     // Execute command
     match cli.command {
@@ -171,12 +288,26 @@ async fn main() -> anyhow::Result<()> {
             tools,
             sync,
             skip_scan,
-        } => {
-            install::run(&skill, tools.as_deref(), &sync, skip_scan).await?;
+            locked,
+        } => match skill {
+            Some(skill) => install::run(&skill, tools.as_deref(), &sync, skip_scan).await?,
+            None => install::run_manifest(tools.as_deref(), locked).await?,
+        },
+        Commands::Rollback { skill, target } => {
+            rollback::run(&skill, target.as_deref())?;
+        }
+        Commands::Lock { output } => {
+            lock::run(&output)?;
         }
         Commands::Update { skill } => {
             update::run(skill.as_deref()).await?;
         }
+        Commands::Verify { repair } => {
+            verify::run(repair).await?;
+        }
+        Commands::Gc { dry_run } => {
+            gc::run(dry_run)?;
+        }
         Commands::Uninstall { skill, tools } => {
             uninstall::run(&skill, tools.as_deref()).await?;
         }
@@ -184,8 +315,21 @@ async fn main() -> anyhow::Result<()> {
             skill,
             tools,
             reconcile,
+            project,
+            clean,
+            dry_run,
+            json,
         } => {
-            sync::run(skill.as_deref(), tools.as_deref(), reconcile).await?;
+            sync::run(
+                skill.as_deref(),
+                tools.as_deref(),
+                reconcile,
+                project.as_deref(),
+                clean,
+                dry_run,
+                json,
+            )
+            .await?;
         }
         Commands::Scan { target, policy } => {
             scan::run(&target, &policy).await?;
@@ -194,6 +338,9 @@ async fn main() -> anyhow::Result<()> {
             ToolsAction::List => tools::list().await?,
             ToolsAction::Detect => tools::detect().await?,
             ToolsAction::Status => tools::status().await?,
+            ToolsAction::Enable { tool } => tools::set_enabled(&tool, true)?,
+            ToolsAction::Disable { tool } => tools::set_enabled(&tool, false)?,
+            ToolsAction::Format { tool, format } => tools::set_format(&tool, &format)?,
         },
         Commands::Registry { action } => match action {
             RegistryAction::List => registry::list().await?,
@@ -206,6 +353,32 @@ async fn main() -> anyhow::Result<()> {
         Commands::Info { skill } => {
             info::run(&skill).await?;
         }
+        Commands::Alias {
+            skill,
+            alias,
+            clear,
+        } => {
+            alias::run(&skill, alias.as_deref(), clear)?;
+        }
+        Commands::Disable { skill } => {
+            enable::disable(&skill)?;
+        }
+        Commands::Enable { skill } => {
+            enable::enable(&skill)?;
+        }
+        Commands::Collect {
+            skill,
+            use_tool,
+            keep_both,
+        } => {
+            collect::run(skill.as_deref(), use_tool.as_deref(), keep_both)?;
+        }
+        Commands::Restore { id } => {
+            restore::run(id.as_deref())?;
+        }
+        Commands::Watch { project, debounce } => {
+            watch::run(project, debounce).await?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,92 @@
+//! Projecting into tools that read their own rule formats, end to end
+//!
+//! Each run gets its own home, config and data directories, so nothing
+//! outside the temporary directory is touched.
+#![cfg(unix)]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn skillshub(home: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_skillshub"))
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local").join("share"))
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "skillshub {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn test_install_and_sync_render_native_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    let home = dir.path().join("home");
+    fs::create_dir_all(home.join(".copilot")).unwrap();
+    fs::create_dir_all(home.join(".codeium").join("windsurf")).unwrap();
+    let skill = dir.path().join("lint");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: lint\ndescription: Fix lint errors\nglobs: src/**/*.rs\n---\n\n# Lint\n",
+    )
+    .unwrap();
+
+    skillshub(&home, &["tools", "format", "copilot", "native"]);
+    skillshub(&home, &["tools", "format", "windsurf", "native"]);
+
+    // Installing into Copilot writes an instructions file, not a skill folder
+    skillshub(
+        &home,
+        &["install", skill.to_str().unwrap(), "--tools", "copilot"],
+    );
+    let instructions = home
+        .join(".copilot")
+        .join("instructions")
+        .join("lint.instructions.md");
+    let written = fs::read_to_string(&instructions).unwrap();
+    assert!(
+        written.starts_with("---\ndescription: \"Fix lint errors\"\napplyTo: \"src/**/*.rs\"\n")
+    );
+    assert!(!home.join(".copilot").join("skills").join("lint").exists());
+
+    // Syncing to Windsurf renders a rule next to its skills directory
+    skillshub(&home, &["sync", "--tools", "windsurf"]);
+    let rule = home
+        .join(".codeium")
+        .join("windsurf")
+        .join("rules")
+        .join("lint.md");
+    assert!(fs::read_to_string(&rule)
+        .unwrap()
+        .starts_with("---\ntrigger: glob\n"));
+}
+
+#[test]
+fn test_unknown_tool_is_rejected_before_installing() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_skillshub"))
+        .args(["install", "lint", "--tools", "copilot,vscode"])
+        .env("HOME", dir.path())
+        .env("XDG_CONFIG_HOME", dir.path().join(".config"))
+        .env("XDG_DATA_HOME", dir.path().join(".local").join("share"))
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown tool: vscode"));
+    assert!(!dir
+        .path()
+        .join(".local")
+        .join("share")
+        .join("skillshub")
+        .exists());
+}
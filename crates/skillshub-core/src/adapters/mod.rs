@@ -6,7 +6,7 @@
 mod openclaw;
 mod trae;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::Result;
 use crate::models::ToolType;
@@ -25,11 +25,24 @@ pub trait ToolAdapter: Send + Sync {
     /// Get the primary skills directory for this tool (used for syncing/writing)
     fn skills_dir(&self) -> Result<PathBuf>;
 
+    /// Where [`Self::skills_dir`] is, without creating it (used for planning)
+    fn skills_dir_path(&self) -> Result<PathBuf> {
+        self.skills_dir()
+    }
+
     /// Get all skills directories for this tool (used for scanning)
     /// Some tools may have multiple directories (e.g., workspace + installation path)
     /// By default, returns only the primary skills_dir
     fn skills_dirs(&self) -> Vec<PathBuf> {
-        self.skills_dir().into_iter().collect()
+        self.skills_dir_path().into_iter().collect()
+    }
+
+    /// Get the project-level skills directory for this tool inside a repository
+    /// By default, resolves `ToolType::default_project_dir()` against the project root
+    fn project_skills_dir(&self, project_root: &Path) -> Option<PathBuf> {
+        self.tool_type()
+            .default_project_dir()
+            .map(|dir| project_root.join(dir))
     }
 
     /// Get custom configuration directory if any
@@ -58,7 +71,8 @@ pub trait ToolAdapter: Send + Sync {
 pub struct SkillStructure {
     /// Whether the tool expects an index file
     pub needs_index: bool,
-    /// Index file name if needed
+    /// Index file name if needed, relative to the skills directory (e.g.
+    /// `../AGENTS.md`); see [`crate::index`] for what is written to it
     pub index_file: Option<String>,
     /// Whether skills should be in subdirectories
     pub use_subdirectories: bool,
@@ -113,15 +127,18 @@ macro_rules! define_adapter {
             }
 
             fn skills_dir(&self) -> Result<PathBuf> {
-                let path = self
-                    .custom_path
-                    .clone()
-                    .or_else(Self::default_path)
-                    .ok_or_else(|| crate::error::Error::ToolNotFound($tool_name.to_string()))?;
+                let path = self.skills_dir_path()?;
                 std::fs::create_dir_all(&path)?;
                 Ok(path)
             }
 
+            fn skills_dir_path(&self) -> Result<PathBuf> {
+                self.custom_path
+                    .clone()
+                    .or_else(Self::default_path)
+                    .ok_or_else(|| crate::error::Error::ToolNotFound($tool_name.to_string()))
+            }
+
             fn config_dir(&self) -> Option<PathBuf> {
                 dirs::home_dir().map(|h| h.join($config_path))
             }
@@ -56,14 +56,16 @@ impl ToolAdapter for OpenClawAdapter {
     }
 
     fn skills_dir(&self) -> Result<PathBuf> {
-        let path = self
-            .primary_path()
-            .ok_or_else(|| crate::error::Error::ToolNotFound("OpenClaw".to_string()))?;
-
+        let path = self.skills_dir_path()?;
         std::fs::create_dir_all(&path)?;
         Ok(path)
     }
 
+    fn skills_dir_path(&self) -> Result<PathBuf> {
+        self.primary_path()
+            .ok_or_else(|| crate::error::Error::ToolNotFound("OpenClaw".to_string()))
+    }
+
     /// Get all skills directories (workspace only)
     fn skills_dirs(&self) -> Vec<PathBuf> {
         let mut dirs = Vec::new();
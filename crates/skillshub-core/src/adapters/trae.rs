@@ -41,14 +41,16 @@ impl ToolAdapter for TraeAdapter {
     }
 
     fn skills_dir(&self) -> Result<PathBuf> {
+        let path = self.skills_dir_path()?;
+        std::fs::create_dir_all(&path)?;
+        Ok(path)
+    }
+
+    fn skills_dir_path(&self) -> Result<PathBuf> {
         // Trae only supports project-level skills
-        if let Some(path) = &self.custom_path {
-            std::fs::create_dir_all(path)?;
-            return Ok(path.clone());
-        }
-        Err(crate::error::Error::ToolNotFound(
-            "Trae (no global path available)".to_string(),
-        ))
+        self.custom_path.clone().ok_or_else(|| {
+            crate::error::Error::ToolNotFound("Trae (no global path available)".to_string())
+        })
     }
 
     fn config_dir(&self) -> Option<PathBuf> {
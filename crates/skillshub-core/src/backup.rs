@@ -0,0 +1,219 @@
+//! Backups of tool directory entries SkillsHub did not create
+//!
+//! When a sync would write over something in a tool's skills directory that
+//! is not one of its own projections (a hand-written skill, a link somewhere
+//! else), the entry is moved to `backups/<id>/content` in the store instead
+//! of being deleted. `backups/<id>/backup.json` records where it came from so
+//! it can be put back with [`restore`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::models::{SkillId, ToolType};
+use crate::schema::{self, Versioned};
+use crate::store::LocalStore;
+
+/// Name of the metadata file inside a backup
+const BACKUP_FILE: &str = "backup.json";
+
+/// Name of the moved entry inside a backup
+const CONTENT_DIR: &str = "content";
+
+/// An entry moved out of a tool directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// Backup identifier (`<unix secs>-<tool>-<name>`)
+    pub id: String,
+    /// Where the entry was
+    pub original_path: PathBuf,
+    /// Tool whose directory held the entry
+    pub tool: ToolType,
+    /// Skill whose projection needed the path
+    pub skill_id: SkillId,
+    /// Unix timestamp (seconds) of the backup
+    pub created_at: u64,
+}
+
+impl Versioned for BackupEntry {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(_from: u32, document: serde_json::Value) -> Result<serde_json::Value> {
+        Ok(document)
+    }
+}
+
+/// Directory holding all backups of a store
+pub fn backups_dir(store: &LocalStore) -> PathBuf {
+    store.root_dir().join("backups")
+}
+
+/// Move `path` into a new backup, leaving the path free
+pub fn create(
+    store: &LocalStore,
+    path: &Path,
+    tool: ToolType,
+    skill_id: &SkillId,
+) -> Result<BackupEntry> {
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let base = format!("{}-{}-{}", created_at, tool.key(), name);
+
+    let dir = backups_dir(store);
+    fs::create_dir_all(&dir)?;
+    let mut id = base.clone();
+    let mut n = 1;
+    while dir.join(&id).exists() {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+
+    let entry = BackupEntry {
+        id,
+        original_path: path.to_path_buf(),
+        tool,
+        skill_id: skill_id.clone(),
+        created_at,
+    };
+    let backup_dir = dir.join(&entry.id);
+    fs::create_dir_all(&backup_dir)?;
+    schema::save(&backup_dir.join(BACKUP_FILE), &entry)?;
+    if let Err(e) = move_entry(path, &backup_dir.join(CONTENT_DIR)) {
+        let _ = fs::remove_dir_all(&backup_dir);
+        return Err(e);
+    }
+    Ok(entry)
+}
+
+/// List backups, newest first
+pub fn list(store: &LocalStore) -> Result<Vec<BackupEntry>> {
+    let dir = backups_dir(store);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for item in fs::read_dir(&dir)? {
+        let item = item?;
+        match schema::load::<BackupEntry>(&item.path().join(BACKUP_FILE)) {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Skipping backup {}: {}", item.path().display(), e),
+        }
+    }
+    entries.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+    Ok(entries)
+}
+
+/// Get a backup by ID
+pub fn get(store: &LocalStore, id: &str) -> Result<BackupEntry> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        return Err(Error::StoreError(format!("Backup not found: {}", id)));
+    }
+    let path = backups_dir(store).join(id).join(BACKUP_FILE);
+    schema::load(&path)?.ok_or_else(|| Error::StoreError(format!("Backup not found: {}", id)))
+}
+
+/// Move a backup back to its original path and delete it
+///
+/// Fails if the original path is occupied; callers free it first.
+pub fn restore(store: &LocalStore, id: &str) -> Result<BackupEntry> {
+    let entry = get(store, id)?;
+    let target = &entry.original_path;
+    if target.exists() || target.is_symlink() {
+        return Err(Error::SyncError(format!(
+            "{} is in use; move it away before restoring backup {}",
+            target.display(),
+            id
+        )));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let backup_dir = backups_dir(store).join(id);
+    move_entry(&backup_dir.join(CONTENT_DIR), target)?;
+    fs::remove_dir_all(&backup_dir)?;
+    Ok(entry)
+}
+
+/// Rename an entry, falling back to copy and delete across file systems
+fn move_entry(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_entry(from, to)?;
+    if from.is_symlink() || from.is_file() {
+        fs::remove_file(from)?;
+    } else {
+        fs::remove_dir_all(from)?;
+    }
+    Ok(())
+}
+
+/// Copy a file, link or directory tree as it is, keeping links as links
+fn copy_entry(from: &Path, to: &Path) -> Result<()> {
+    let file_type = fs::symlink_metadata(from)?.file_type();
+    if file_type.is_symlink() {
+        let link = fs::read_link(from)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&link, to)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&link, to)?;
+    } else if file_type.is_dir() {
+        fs::create_dir_all(to)?;
+        for item in fs::read_dir(from)? {
+            let item = item?;
+            copy_entry(&item.path(), &to.join(item.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::StoreConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let temp = TempDir::new().unwrap();
+        let store = LocalStore::new(StoreConfig {
+            root_dir: temp.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let target = temp.path().join("claude").join("demo");
+        fs::create_dir_all(target.join("docs")).unwrap();
+        fs::write(target.join("docs").join("notes.md"), "mine").unwrap();
+
+        let id = SkillId::new("demo").unwrap();
+        let entry = create(&store, &target, ToolType::Claude, &id).unwrap();
+        assert!(!target.exists());
+        assert_eq!(list(&store).unwrap(), vec![entry.clone()]);
+
+        // Nothing is overwritten on restore
+        fs::create_dir_all(&target).unwrap();
+        assert!(restore(&store, &entry.id).is_err());
+        fs::remove_dir(&target).unwrap();
+
+        assert_eq!(restore(&store, &entry.id).unwrap(), entry);
+        assert_eq!(
+            fs::read_to_string(target.join("docs").join("notes.md")).unwrap(),
+            "mine"
+        );
+        assert!(list(&store).unwrap().is_empty());
+        assert!(get(&store, "../store").is_err());
+    }
+}
@@ -254,6 +254,7 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
             copy_dir_all(&src_path, &dst_path)?;
         } else {
             fs::copy(&src_path, &dst_path)?;
+            crate::sync::make_writable(&dst_path)?;
         }
     }
     Ok(())
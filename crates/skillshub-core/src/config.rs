@@ -7,7 +7,9 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
-use crate::models::SyncStrategy;
+use crate::models::{RenderFormat, SyncStrategy, ToolProfile, ToolType};
+use crate::schema::{self, Versioned};
+use crate::store::DEFAULT_RETAINED_VERSIONS;
 
 /// Cloud storage provider
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -86,9 +88,32 @@ pub struct AppConfig {
     #[serde(default)]
     pub tool_sync_strategies: HashMap<String, SyncStrategy>,
 
+    /// Tools left out of full syncs (tool_type_lowercase)
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+
+    /// Per-tool layout overrides (tool_type_lowercase -> format), e.g.
+    /// `"cursor": "cursor-rule"` to render skills as Cursor rules
+    #[serde(default)]
+    pub tool_render_formats: HashMap<String, RenderFormat>,
+
+    /// Move entries SkillsHub did not create out of the way when syncing over
+    /// them; when off, such syncs fail and the entry is left alone
+    #[serde(default = "default_true")]
+    pub backup_unmanaged: bool,
+
+    /// Start the desktop app's background watcher, which keeps tools in sync
+    /// as the store and tool directories change
+    #[serde(default)]
+    pub watch_in_background: bool,
+
     /// Cloud sync configuration
     #[serde(default)]
     pub cloud_sync: CloudSyncConfig,
+
+    /// Number of previous versions kept per skill for rollback
+    #[serde(default = "default_retained_versions")]
+    pub retained_versions: usize,
 }
 
 fn default_true() -> bool {
@@ -99,6 +124,10 @@ fn default_false() -> bool {
     false
 }
 
+fn default_retained_versions() -> usize {
+    DEFAULT_RETAINED_VERSIONS
+}
+
 fn default_trusted_sources() -> Vec<String> {
     vec![
         "github.com/official-skills".to_string(),
@@ -119,11 +148,25 @@ impl Default for AppConfig {
             auto_approve_low: false,
             trusted_sources: default_trusted_sources(),
             tool_sync_strategies: HashMap::new(),
+            tool_render_formats: HashMap::new(),
+            disabled_tools: Vec::new(),
+            backup_unmanaged: true,
+            watch_in_background: false,
             cloud_sync: CloudSyncConfig::default(),
+            retained_versions: DEFAULT_RETAINED_VERSIONS,
         }
     }
 }
 
+/// `config.json`; v1 only adds the version stamp
+impl Versioned for AppConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(_from: u32, document: serde_json::Value) -> Result<serde_json::Value> {
+        Ok(document)
+    }
+}
+
 impl AppConfig {
     /// Get the config file path
     pub fn config_path() -> Result<PathBuf> {
@@ -142,39 +185,29 @@ impl AppConfig {
     }
 
     /// Load configuration from file
+    ///
+    /// Older files are upgraded in place (see [`crate::schema`]); a file that
+    /// cannot be read is an error.
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
-
-        if !config_path.exists() {
-            // Return default config if file doesn't exist
-            return Ok(Self::default());
-        }
-
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| Error::StoreError(format!("Failed to read config: {}", e)))?;
-
-        let config: AppConfig = serde_json::from_str(&content)
-            .map_err(|e| Error::StoreError(format!("Failed to parse config: {}", e)))?;
-
-        Ok(config)
+        // Return default config if file doesn't exist
+        Ok(schema::load(&config_path)?.unwrap_or_default())
     }
 
     /// Save configuration to file
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
-
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| Error::StoreError(format!("Failed to serialize config: {}", e)))?;
-
-        fs::write(&config_path, content)
-            .map_err(|e| Error::StoreError(format!("Failed to write config: {}", e)))?;
-
-        Ok(())
+        schema::save(&Self::config_path()?, self)
     }
 
     /// Load or create default configuration
+    ///
+    /// Falls back to the defaults when the file cannot be read, without
+    /// touching it, so the problem stays visible to [`Self::load`].
     pub fn load_or_default() -> Self {
-        Self::load().unwrap_or_default()
+        Self::load().unwrap_or_else(|e| {
+            tracing::warn!("Using default config: {}", e);
+            Self::default()
+        })
     }
 
     /// Get the effective sync strategy for a specific tool.
@@ -185,6 +218,28 @@ impl AppConfig {
             .copied()
             .unwrap_or(self.default_sync_strategy)
     }
+
+    /// Per-tool settings for the sync engine, one profile per built-in tool
+    pub fn tool_profiles(&self) -> Vec<ToolProfile> {
+        ToolType::all_builtin()
+            .into_iter()
+            .map(|tool| {
+                let tool_key = tool.key();
+                let mut profile = ToolProfile::new(tool);
+                profile.enabled = !self
+                    .disabled_tools
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(tool_key));
+                profile.sync_strategy = self.strategy_for_tool(tool_key);
+                profile.render_format = self
+                    .tool_render_formats
+                    .get(tool_key)
+                    .copied()
+                    .unwrap_or_default();
+                profile
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -14,12 +14,21 @@ pub enum Error {
     #[error("Invalid skill format: {0}")]
     InvalidSkillFormat(String),
 
+    #[error("Invalid skill ID {0}")]
+    InvalidSkillId(String),
+
+    #[error("Ambiguous skill name {0}")]
+    AmbiguousSkill(String),
+
     #[error("Registry error: {0}")]
     RegistryError(String),
 
     #[error("Store error: {0}")]
     StoreError(String),
 
+    #[error("Store is busy: {0}")]
+    StoreBusy(String),
+
     #[error("Sync error: {0}")]
     SyncError(String),
 
@@ -32,6 +41,12 @@ pub enum Error {
     #[error("Security policy violation: {0}")]
     PolicyViolation(String),
 
+    #[error("Integrity check failed: {0}")]
+    IntegrityError(String),
+
+    #[error("Schema error: {0}")]
+    SchemaError(String),
+
     #[error("Config not found: {0}")]
     ConfigNotFound(String),
 
@@ -0,0 +1,426 @@
+//! Garbage collection - reclaim space held by data nothing references
+//!
+//! Collected entries are:
+//! - install records (`metadata/*.json`) whose skill has no files left
+//! - skill directories under `skills/` or `versions/` without a record
+//! - stored versions that are neither active nor in a record's history
+//! - objects no retained version's tree manifest refers to
+//! - `skillshub-install-*` folders left in the system temp directory
+//! - git caches of registries and skill sources that are no longer configured
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::error::Result;
+use crate::models::{InstallRecord, SkillId, SkillSource};
+use crate::objects::SkillTree;
+use crate::registry::{GitRegistry, RegistryConfig, RegistryType};
+use crate::store::{is_plugin_record, remove_link, version_dir_name, LocalStore};
+
+/// Prefix of the temporary folders used while fetching skills
+pub const TEMP_INSTALL_PREFIX: &str = "skillshub-install-";
+
+/// Temporary install folders younger than this may belong to a running install
+const TEMP_INSTALL_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Kind of reclaimable entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GarbageKind {
+    /// Install record without skill files
+    OrphanedRecord,
+    /// Skill files without an install record
+    OrphanedSkill,
+    /// Stored version that fell out of the history
+    UnusedVersion,
+    /// Object no tree manifest refers to
+    UnusedObject,
+    /// Leftover temporary install folder
+    TempInstall,
+    /// Git cache of a registry or source nothing refers to
+    GitCache,
+}
+
+impl std::fmt::Display for GarbageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GarbageKind::OrphanedRecord => write!(f, "orphaned record"),
+            GarbageKind::OrphanedSkill => write!(f, "orphaned skill"),
+            GarbageKind::UnusedVersion => write!(f, "unused version"),
+            GarbageKind::UnusedObject => write!(f, "unused object"),
+            GarbageKind::TempInstall => write!(f, "temp install"),
+            GarbageKind::GitCache => write!(f, "git cache"),
+        }
+    }
+}
+
+/// A reclaimable file or directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GarbageEntry {
+    /// Entry kind
+    pub kind: GarbageKind,
+    /// Path that is (or would be) deleted
+    pub path: PathBuf,
+    /// Size on disk in bytes
+    pub bytes: u64,
+}
+
+/// Result of a garbage collection run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GcReport {
+    /// Whether entries were only reported
+    pub dry_run: bool,
+    /// Entries deleted (or that would be deleted)
+    pub entries: Vec<GarbageEntry>,
+    /// Entries that could not be deleted, with the reason
+    pub failed: Vec<(GarbageEntry, String)>,
+}
+
+impl GcReport {
+    /// Total size of the collected entries
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.entries.iter().map(|e| e.bytes).sum()
+    }
+}
+
+/// Find (and unless `dry_run` is set, delete) everything nothing references
+///
+/// `registries` are the configured registries; git caches that belong to
+/// none of them and to no installed skill's source are collected. The store
+/// stays exclusively locked for the whole run.
+pub fn collect_garbage(
+    store: &mut LocalStore,
+    registries: &[RegistryConfig],
+    dry_run: bool,
+) -> Result<GcReport> {
+    let _guard = store.lock_exclusive()?;
+    store.reload()?;
+
+    let mut garbage = store_garbage(store)?;
+    garbage.extend(temp_install_garbage());
+    garbage.extend(git_cache_garbage(
+        &GitRegistry::cache_root(),
+        registries,
+        &store.list_installed(),
+    )?);
+
+    let report = sweep(garbage, dry_run);
+    if !dry_run {
+        store.reload()?;
+    }
+    Ok(report)
+}
+
+/// Delete the given entries (only list them on a dry run)
+fn sweep(garbage: Vec<GarbageEntry>, dry_run: bool) -> GcReport {
+    let mut report = GcReport {
+        dry_run,
+        ..Default::default()
+    };
+    for entry in garbage {
+        if dry_run {
+            report.entries.push(entry);
+            continue;
+        }
+        match remove_entry(&entry.path) {
+            Ok(()) => report.entries.push(entry),
+            Err(e) => {
+                let reason = e.to_string();
+                report.failed.push((entry, reason));
+            }
+        }
+    }
+    report
+}
+
+/// Records without files, files without records, unused versions and
+/// unreferenced objects
+fn store_garbage(store: &LocalStore) -> Result<Vec<GarbageEntry>> {
+    let mut garbage = Vec::new();
+    let mut used_objects = HashSet::new();
+
+    let mut records = store.list_installed();
+    records.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
+    for record in records {
+        if is_plugin_record(record) {
+            continue;
+        }
+        let skill_dir = store.skill_path(&record.skill_id);
+        let versions_dir = store.versions_dir(&record.skill_id);
+        if !skill_dir.exists() && !skill_dir.is_symlink() && !versions_dir.exists() {
+            let path = store.metadata_path(&record.skill_id);
+            garbage.push(entry(GarbageKind::OrphanedRecord, path));
+            let manifests_dir = store.manifests_dir(&record.skill_id);
+            if manifests_dir.exists() {
+                garbage.push(entry(GarbageKind::OrphanedRecord, manifests_dir));
+            }
+            continue;
+        }
+
+        let keep: HashSet<&str> = std::iter::once(&record.version)
+            .chain(&record.history)
+            .map(|v| version_dir_name(&v.content_hash))
+            .collect();
+        for version in list_dir(&versions_dir) {
+            let name = version.file_name().unwrap_or_default().to_string_lossy();
+            if !keep.contains(name.as_ref()) {
+                garbage.push(entry(GarbageKind::UnusedVersion, version));
+            }
+        }
+        for manifest in list_dir(&store.manifests_dir(&record.skill_id)) {
+            let name = manifest.file_stem().unwrap_or_default().to_string_lossy();
+            if !keep.contains(name.as_ref()) {
+                garbage.push(entry(GarbageKind::UnusedVersion, manifest));
+            } else if let Ok(tree) = SkillTree::load(&manifest) {
+                used_objects.extend(tree.files.into_values().map(|e| e.file.hash));
+            }
+        }
+    }
+
+    let root = store.root_dir();
+    for dir in [
+        store.skills_dir(),
+        root.join("versions"),
+        root.join("manifests"),
+    ] {
+        for path in list_dir(&dir) {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if name.starts_with('.') {
+                continue;
+            }
+            // Names that do not decode to a skill ID cannot belong to a record
+            let recorded = SkillId::from_file_name(&name)
+                .is_ok_and(|skill_id| store.get_record(&skill_id).is_some());
+            if !recorded {
+                garbage.push(entry(GarbageKind::OrphanedSkill, path));
+            }
+        }
+    }
+
+    // Installs being staged may hold objects no manifest mentions yet
+    if !store.has_staged_installs() {
+        for (hash, path) in store.objects().list() {
+            if !used_objects.contains(&hash) {
+                garbage.push(entry(GarbageKind::UnusedObject, path));
+            }
+        }
+    }
+
+    Ok(garbage)
+}
+
+/// Temporary install folders old enough not to belong to a running install
+fn temp_install_garbage() -> Vec<GarbageEntry> {
+    let now = SystemTime::now();
+    list_dir(&std::env::temp_dir())
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with(TEMP_INSTALL_PREFIX))
+        })
+        .filter(|path| {
+            fs::symlink_metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= TEMP_INSTALL_MIN_AGE)
+        })
+        .map(|path| entry(GarbageKind::TempInstall, path))
+        .collect()
+}
+
+/// Git caches not used by a configured registry or an installed skill
+fn git_cache_garbage(
+    cache_root: &Path,
+    registries: &[RegistryConfig],
+    records: &[&InstallRecord],
+) -> Result<Vec<GarbageEntry>> {
+    let mut used: HashSet<String> = registries
+        .iter()
+        .filter(|r| r.registry_type == RegistryType::Git)
+        .map(|r| GitRegistry::cache_name(&r.url, None))
+        .collect();
+
+    for record in records {
+        if let SkillSource::Git { url, .. } = &record.source {
+            used.insert(GitRegistry::cache_name(url, None));
+            for version in std::iter::once(&record.version).chain(&record.history) {
+                if let Some(commit) = &version.commit {
+                    used.insert(GitRegistry::cache_name(url, Some(commit)));
+                }
+            }
+        }
+    }
+
+    Ok(list_dir(cache_root)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|n| !used.contains(n.to_string_lossy().as_ref()))
+        })
+        .map(|path| entry(GarbageKind::GitCache, path))
+        .collect())
+}
+
+fn entry(kind: GarbageKind, path: PathBuf) -> GarbageEntry {
+    let bytes = disk_usage(&path);
+    GarbageEntry { kind, path, bytes }
+}
+
+/// Entries of a directory (empty when it does not exist)
+fn list_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+/// Size of a file or directory tree, not following symlinks
+fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.path().symlink_metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn remove_entry(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_symlink() {
+        remove_link(path)
+    } else if metadata.is_dir() {
+        Ok(fs::remove_dir_all(path)?)
+    } else {
+        Ok(fs::remove_file(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Skill, SkillVersion};
+    use crate::store::StoreConfig;
+    use std::collections::HashMap;
+
+    fn skill_id(id: &str) -> SkillId {
+        SkillId::new(id).unwrap()
+    }
+
+    async fn import(store: &mut LocalStore, root: &Path, id: &str, body: &str) {
+        let source = root.join("source").join(id).join(body);
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), body).unwrap();
+
+        let skill = Skill {
+            id: skill_id(id),
+            name: id.to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        store.import_skill(&skill, &source).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_gc_collects_only_unreferenced_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        import(&mut store, dir.path(), "kept", "one").await;
+        import(&mut store, dir.path(), "gone", "one").await;
+
+        // Record whose files were deleted by hand
+        remove_link(&store.skill_path(&skill_id("gone"))).unwrap();
+        fs::remove_dir_all(store.versions_dir(&skill_id("gone"))).unwrap();
+        // Files without a record
+        fs::create_dir_all(store.skills_dir().join("stray")).unwrap();
+        fs::write(store.skills_dir().join("stray").join("SKILL.md"), "x").unwrap();
+        // A version nothing refers to
+        let unused = store
+            .versions_dir(&skill_id("kept"))
+            .join("0123456789abcdef");
+        fs::create_dir_all(&unused).unwrap();
+        // An object no manifest mentions
+        let loose = dir.path().join("loose");
+        fs::create_dir_all(&loose).unwrap();
+        fs::write(loose.join("notes.md"), "loose").unwrap();
+        store.objects().ingest(&loose).unwrap();
+
+        let garbage = store_garbage(&store).unwrap();
+        let kinds: Vec<GarbageKind> = garbage.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                GarbageKind::OrphanedRecord,
+                GarbageKind::OrphanedRecord,
+                GarbageKind::UnusedVersion,
+                GarbageKind::OrphanedSkill,
+                GarbageKind::UnusedObject,
+            ]
+        );
+
+        // A dry run deletes nothing
+        let report = sweep(garbage.clone(), true);
+        assert!(report.reclaimable_bytes() > 0);
+        assert!(unused.exists());
+
+        let report = sweep(garbage, false);
+        assert!(report.failed.is_empty());
+        store.reload().unwrap();
+        assert!(!store.is_installed(&skill_id("gone")));
+        assert!(store.is_installed(&skill_id("kept")));
+        assert!(store
+            .skill_path(&skill_id("kept"))
+            .join("SKILL.md")
+            .exists());
+        assert!(!unused.exists());
+        // The object shared by both skills is still referenced by the kept one
+        assert_eq!(store.objects().list().len(), 1);
+        assert!(store_garbage(&store).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_git_caches_of_configured_registries_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://github.com/anthropics/skills";
+        let used = dir.path().join(GitRegistry::cache_name(url, None));
+        let stale = dir
+            .path()
+            .join(GitRegistry::cache_name("https://example.com/old", None));
+        fs::create_dir_all(&used).unwrap();
+        fs::create_dir_all(&stale).unwrap();
+
+        let registries = vec![RegistryConfig {
+            name: "anthropic".to_string(),
+            url: url.to_string(),
+            branch: None,
+            description: None,
+            enabled: true,
+            registry_type: RegistryType::Git,
+            tags: Vec::new(),
+        }];
+
+        let garbage = git_cache_garbage(dir.path(), &registries, &[]).unwrap();
+        let paths: Vec<&Path> = garbage.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(paths, vec![stale.as_path()]);
+    }
+}
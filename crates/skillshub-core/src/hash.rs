@@ -0,0 +1,323 @@
+//! Content hashing - the one way SkillsHub fingerprints a skill directory
+//!
+//! A directory is described by a [`DirManifest`]: every file's relative path,
+//! normalised mode, size and SHA-256, plus a root digest over all of them.
+//! Renaming, moving or `chmod +x`-ing a file therefore changes the digest,
+//! not just editing it. Per-file hashes are cached by path, size and
+//! modification time, so re-hashing an unchanged directory only stats it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::error::{Error, Result};
+
+/// Version of the digest format recorded in install records
+///
+/// Version 0 hashed file contents only; version 1 is the manifest digest.
+pub const DIGEST_VERSION: u32 = 1;
+
+/// Files modified this recently are not cached: a write within the same
+/// timestamp tick would otherwise go unnoticed
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Cached entries beyond this are dropped wholesale rather than pruned
+const MAX_CACHE_ENTRIES: usize = 100_000;
+
+/// Fingerprint of a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDigest {
+    /// SHA-256 of the content
+    pub hash: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Normalised mode: `0o755` for executables, `0o644` otherwise
+    pub mode: u32,
+}
+
+impl FileDigest {
+    /// Fingerprint content read from a file with the given metadata
+    pub fn of(content: &[u8], metadata: &fs::Metadata) -> Self {
+        Self {
+            hash: hash_bytes(content),
+            size: content.len() as u64,
+            mode: normalized_mode(metadata),
+        }
+    }
+
+    /// Whether the file is executable
+    pub fn is_executable(&self) -> bool {
+        self.mode & 0o111 != 0
+    }
+}
+
+/// Fingerprint of a directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirManifest {
+    /// Root digest over every file's path, mode, size and hash
+    pub digest: String,
+    /// Files keyed by `/`-separated relative path
+    pub files: BTreeMap<String, FileDigest>,
+}
+
+impl DirManifest {
+    /// Build a manifest and its root digest from file fingerprints
+    pub fn from_files(files: BTreeMap<String, FileDigest>) -> Self {
+        let mut hasher = Sha256::new();
+        for (path, file) in &files {
+            hasher.update(format!("{:o} {} {} ", file.mode, file.size, file.hash));
+            hasher.update(path.as_bytes());
+            hasher.update([0]);
+        }
+        Self {
+            digest: hex::encode(hasher.finalize()),
+            files,
+        }
+    }
+
+    /// Relative paths that were added, removed or changed in `other`
+    pub fn changed_files(&self, other: &DirManifest) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, file)| other.files.get(*path) != Some(*file))
+            .map(|(path, _)| PathBuf::from(path))
+            .collect();
+        changed.extend(
+            other
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .map(PathBuf::from),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// Fingerprint a directory (or a single file)
+pub fn manifest(path: &Path) -> Result<DirManifest> {
+    let mut files = BTreeMap::new();
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+
+    for (relative, file_path, metadata) in walk_files(path)? {
+        let hash = cache.file_hash(&file_path, &metadata)?;
+        files.insert(
+            relative,
+            FileDigest {
+                hash,
+                size: metadata.len(),
+                mode: normalized_mode(&metadata),
+            },
+        );
+    }
+
+    cache.persist();
+    Ok(DirManifest::from_files(files))
+}
+
+/// Root digest of a directory (see [`manifest`])
+pub fn digest(path: &Path) -> Result<String> {
+    manifest(path).map(|m| m.digest)
+}
+
+/// Digest used before [`DIGEST_VERSION`] 1: file contents only, in name order
+///
+/// Only needed to check content pinned by older lockfiles.
+pub fn legacy_digest(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry.map_err(|e| Error::Io(e.into()))?;
+        if entry.file_type().is_file() {
+            hasher.update(fs::read(entry.path())?);
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// SHA-256 of a byte string as hex
+pub fn hash_bytes(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Every regular file below `path` as `(relative key, path, metadata)`
+///
+/// Symlinked files are followed (a copy of the directory would contain
+/// their content); symlinked directories are not descended into.
+pub(crate) fn walk_files(path: &Path) -> Result<Vec<(String, PathBuf, fs::Metadata)>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry.map_err(|e| Error::Io(e.into()))?;
+        let metadata = match fs::metadata(entry.path()) {
+            Ok(metadata) if metadata.is_file() => metadata,
+            _ => continue,
+        };
+        let relative = if entry.depth() == 0 {
+            entry.file_name().to_string_lossy().to_string()
+        } else {
+            relative_key(path, entry.path())
+        };
+        files.push((relative, entry.path().to_path_buf(), metadata));
+    }
+    Ok(files)
+}
+
+/// `/`-separated path of `path` relative to `root`
+pub(crate) fn relative_key(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Modification time in nanoseconds since the epoch
+pub(crate) fn mtime_ns(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(unix)]
+fn normalized_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    if metadata.permissions().mode() & 0o111 != 0 {
+        0o755
+    } else {
+        0o644
+    }
+}
+
+#[cfg(not(unix))]
+fn normalized_mode(_metadata: &fs::Metadata) -> u32 {
+    0o644
+}
+
+/// Per-file hashes keyed by absolute path, valid while size and mtime match
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HashCache {
+    entries: HashMap<PathBuf, CachedHash>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedHash {
+    size: u64,
+    mtime_ns: u64,
+    hash: String,
+}
+
+impl HashCache {
+    fn file_hash(&mut self, path: &Path, metadata: &fs::Metadata) -> Result<String> {
+        let size = metadata.len();
+        let mtime_ns = mtime_ns(metadata);
+        if let Some(cached) = self.entries.get(path) {
+            if cached.size == size && cached.mtime_ns == mtime_ns {
+                return Ok(cached.hash.clone());
+            }
+        }
+
+        let hash = hash_bytes(&fs::read(path)?);
+        let settled = metadata
+            .modified()
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok())
+            .is_some_and(|age| age >= RACY_WINDOW);
+        if settled {
+            self.entries.insert(
+                path.to_path_buf(),
+                CachedHash {
+                    size,
+                    mtime_ns,
+                    hash: hash.clone(),
+                },
+            );
+            self.dirty = true;
+        }
+        Ok(hash)
+    }
+
+    fn persist(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        if self.entries.len() > MAX_CACHE_ENTRIES {
+            self.entries.clear();
+        }
+        if let Some(path) = cache_path() {
+            if let Ok(content) = serde_json::to_vec(self) {
+                let _ = crate::store::write_atomic(&path, &content);
+            }
+        }
+    }
+}
+
+fn cache() -> &'static Mutex<HashCache> {
+    static CACHE: OnceLock<Mutex<HashCache>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let cache = cache_path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+        Mutex::new(cache)
+    })
+}
+
+/// Where the hash cache is kept between runs (not persisted by unit tests)
+fn cache_path() -> Option<PathBuf> {
+    if cfg!(test) {
+        return None;
+    }
+    dirs::data_local_dir().map(|dir| dir.join("skillshub").join("cache").join("file_hashes.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_covers_paths_and_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("skill");
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(root.join("SKILL.md"), "# Demo").unwrap();
+        fs::write(root.join("scripts").join("run.sh"), "echo hi").unwrap();
+
+        let before = manifest(&root).unwrap();
+        assert_eq!(before.files.len(), 2);
+        assert!(before.files.contains_key("scripts/run.sh"));
+        // Unchanged content hashes the same, and old-style digests differ
+        assert_eq!(digest(&root).unwrap(), before.digest);
+        assert_ne!(legacy_digest(&root).unwrap(), before.digest);
+
+        // Moving a file changes the digest even though the content is the same
+        fs::rename(root.join("scripts/run.sh"), root.join("run.sh")).unwrap();
+        let moved = manifest(&root).unwrap();
+        assert_ne!(moved.digest, before.digest);
+        assert_eq!(
+            before.changed_files(&moved),
+            vec![PathBuf::from("run.sh"), PathBuf::from("scripts/run.sh")]
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+            let executable = manifest(&root).unwrap();
+            assert_ne!(executable.digest, moved.digest);
+            assert!(executable.files["run.sh"].is_executable());
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Index files for tools that only discover skills listed in one
+//!
+//! A tool's [`SkillStructure`](crate::adapters::SkillStructure) can ask for an
+//! index next to its skills. A Markdown index (e.g. `AGENTS.md`) gets a
+//! section between [`BEGIN_MARKER`] and [`END_MARKER`]; everything outside it
+//! is left as written. Any other file is a JSON manifest owned entirely by
+//! SkillsHub, and one without the `generated_by` marker is never overwritten.
+//! Entries are sorted and files are only written when their content changes,
+//! so regenerating an unchanged index touches nothing.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::registry::parse_skill_md;
+
+/// Start of the generated section in a Markdown index
+pub const BEGIN_MARKER: &str =
+    "<!-- skillshub:begin (generated from synced skills; edits here are overwritten) -->";
+
+/// End of the generated section in a Markdown index
+pub const END_MARKER: &str = "<!-- skillshub:end -->";
+
+/// `generated_by` value of JSON manifests written by SkillsHub
+const GENERATOR: &str = "SkillsHub";
+
+/// One skill listed in an index
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Name the skill is projected under
+    pub name: String,
+    /// Description from its SKILL.md
+    pub description: String,
+    /// Path to the skill's SKILL.md (or rendered file), relative to the index
+    pub path: PathBuf,
+}
+
+/// Contents of a JSON manifest
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    generated_by: String,
+    skills: Vec<IndexEntry>,
+}
+
+/// Bring the index at `index_path` in line with `entries`
+///
+/// An index without entries is removed (the section, or the whole file when
+/// nothing else is left). Returns whether the file changed.
+pub fn update(index_path: &Path, entries: &[IndexEntry]) -> Result<bool> {
+    let mut entries = entries.to_vec();
+    entries.sort();
+
+    let current = match fs::read_to_string(index_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let updated = if is_markdown(index_path) {
+        markdown(current.as_deref().unwrap_or_default(), &entries)
+    } else {
+        match json(current.as_deref(), &entries)? {
+            Some(updated) => updated,
+            None => {
+                tracing::warn!(
+                    "Leaving {} in place: it was not generated by SkillsHub",
+                    index_path.display()
+                );
+                return Ok(false);
+            }
+        }
+    };
+
+    if current.as_deref().unwrap_or_default() == updated {
+        return Ok(false);
+    }
+    if updated.is_empty() {
+        fs::remove_file(index_path)?;
+    } else {
+        if let Some(parent) = index_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(index_path, updated)?;
+    }
+    Ok(true)
+}
+
+/// Entry for a skill projected at `target`, listed in `index_path`
+///
+/// Folders are listed by their `SKILL.md`, rendered files by themselves; the
+/// description is read from the listed file's frontmatter.
+pub fn entry(index_path: &Path, target: &Path) -> IndexEntry {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file = if target.is_dir() {
+        target.join("SKILL.md")
+    } else {
+        target.to_path_buf()
+    };
+    let description = parse_skill_md(&file)
+        .ok()
+        .and_then(|metadata| metadata.description)
+        .unwrap_or_default();
+    let base = index_path.parent().map(normalize).unwrap_or_default();
+    let file = normalize(&file);
+    let path = file
+        .strip_prefix(&base)
+        .map(Path::to_path_buf)
+        .unwrap_or(file);
+    IndexEntry {
+        name,
+        description,
+        path,
+    }
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+}
+
+/// `content` with its generated section replaced, appended or dropped
+fn markdown(content: &str, entries: &[IndexEntry]) -> String {
+    let section = (!entries.is_empty()).then(|| {
+        let mut section = format!("{}\n## Skills\n\n", BEGIN_MARKER);
+        for entry in entries {
+            section.push_str(&format!(
+                "- [{}]({})",
+                entry.name,
+                entry.path.to_string_lossy().replace('\\', "/")
+            ));
+            if !entry.description.is_empty() {
+                section.push_str(&format!(": {}", entry.description));
+            }
+            section.push('\n');
+        }
+        section.push_str(END_MARKER);
+        section
+    });
+
+    let existing = content.find(BEGIN_MARKER).and_then(|start| {
+        content[start..]
+            .find(END_MARKER)
+            .map(|end| (start, start + end + END_MARKER.len()))
+    });
+    let (before, after) = match existing {
+        Some((start, end)) => (&content[..start], &content[end..]),
+        None if section.is_none() => return content.to_string(),
+        None => (content, ""),
+    };
+
+    let before = before.trim_end();
+    let after = after.trim_start_matches(['\r', '\n']);
+    let mut updated = before.to_string();
+    if let Some(section) = section {
+        if !updated.is_empty() {
+            updated.push_str("\n\n");
+        }
+        updated.push_str(&section);
+        updated.push('\n');
+    } else if !updated.is_empty() {
+        updated.push('\n');
+    }
+    if !after.is_empty() {
+        updated.push('\n');
+        updated.push_str(after);
+    }
+    updated
+}
+
+/// Manifest for `entries`, or `None` if `current` is not one of ours
+fn json(current: Option<&str>, entries: &[IndexEntry]) -> Result<Option<String>> {
+    if let Some(current) = current {
+        let ours = serde_json::from_str::<Manifest>(current)
+            .is_ok_and(|manifest| manifest.generated_by == GENERATOR);
+        if !ours {
+            return Ok(None);
+        }
+    }
+    if entries.is_empty() {
+        return Ok(Some(String::new()));
+    }
+    let manifest = Manifest {
+        generated_by: GENERATOR.to_string(),
+        skills: entries.to_vec(),
+    };
+    Ok(Some(serde_json::to_string_pretty(&manifest)? + "\n"))
+}
+
+/// `path` with `.` and `..` resolved lexically, so an index at
+/// `skills/../AGENTS.md` lists `skills/<name>/SKILL.md`
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint() -> IndexEntry {
+        IndexEntry {
+            name: "lint".to_string(),
+            description: "Fix lint errors".to_string(),
+            path: PathBuf::from("skills/lint/SKILL.md"),
+        }
+    }
+
+    #[test]
+    fn test_markdown_section_keeps_handwritten_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = dir.path().join("AGENTS.md");
+        fs::write(&index, "# Agents\n\nBe nice.\n").unwrap();
+
+        assert!(update(&index, &[lint()]).unwrap());
+        let written = fs::read_to_string(&index).unwrap();
+        assert!(written.starts_with("# Agents\n\nBe nice.\n\n<!-- skillshub:begin"));
+        assert!(written.contains("- [lint](skills/lint/SKILL.md): Fix lint errors\n"));
+        assert!(!update(&index, &[lint()]).unwrap());
+
+        // Content after the section survives regeneration
+        fs::write(&index, format!("{}\n## Notes\n", written)).unwrap();
+        let review = IndexEntry {
+            name: "review".to_string(),
+            description: String::new(),
+            path: PathBuf::from("skills/review/SKILL.md"),
+        };
+        assert!(update(&index, &[review, lint()]).unwrap());
+        let written = fs::read_to_string(&index).unwrap();
+        assert!(written.contains("Fix lint errors\n- [review](skills/review/SKILL.md)\n"));
+        assert!(written.ends_with(&format!("{}\n\n## Notes\n", END_MARKER)));
+
+        assert!(update(&index, &[]).unwrap());
+        assert_eq!(
+            fs::read_to_string(&index).unwrap(),
+            "# Agents\n\nBe nice.\n\n## Notes\n"
+        );
+    }
+
+    #[test]
+    fn test_json_manifest_is_only_replaced_when_generated() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = dir.path().join("skills.json");
+
+        assert!(update(&index, &[lint()]).unwrap());
+        assert!(fs::read_to_string(&index)
+            .unwrap()
+            .contains("\"generated_by\": \"SkillsHub\""));
+        assert!(update(&index, &[]).unwrap());
+        assert!(!index.exists());
+
+        fs::write(&index, "{\"skills\": []}").unwrap();
+        assert!(!update(&index, &[lint()]).unwrap());
+        assert_eq!(fs::read_to_string(&index).unwrap(), "{\"skills\": []}");
+    }
+
+    #[test]
+    fn test_entry_paths_are_relative_to_the_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let skills = dir.path().join("skills");
+        fs::create_dir_all(skills.join("lint")).unwrap();
+        fs::write(
+            skills.join("lint").join("SKILL.md"),
+            "---\ndescription: Fix lint errors\n---\n",
+        )
+        .unwrap();
+
+        let entry = entry(&skills.join("..").join("AGENTS.md"), &skills.join("lint"));
+        assert_eq!(entry.name, "lint");
+        assert_eq!(entry.description, "Fix lint errors");
+        assert_eq!(entry.path, PathBuf::from("skills/lint/SKILL.md"));
+    }
+}
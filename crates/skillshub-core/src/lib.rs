@@ -4,16 +4,27 @@
 //! Provides skill management, multi-tool sync, security scanning, and registry access.
 
 pub mod adapters;
+pub mod backup;
 pub mod cloud_sync;
 pub mod config;
 pub mod error;
+pub mod gc;
+pub mod hash;
+pub mod index;
+pub mod lock;
+pub mod lockfile;
+pub mod manifest;
 pub mod models;
+pub mod objects;
 pub mod plugins;
 pub mod registry;
+pub mod render;
 pub mod scanner;
+pub mod schema;
 pub mod store;
 pub mod sync;
 pub mod update;
+pub mod watch;
 
 pub use config::AppConfig;
 pub use error::{Error, Result};
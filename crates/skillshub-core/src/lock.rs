@@ -0,0 +1,186 @@
+//! Store locking - advisory file locks coordinating processes that share a store
+//!
+//! The CLI, the desktop app and background jobs may open the same store at
+//! once. Readers take a shared lock on `<store>/.lock`, writers an exclusive
+//! one; acquisition polls until the configured timeout and then fails with
+//! [`Error::StoreBusy`].
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// Name of the lock file inside the store root
+pub const LOCK_FILE: &str = ".lock";
+
+/// How often a busy lock is retried
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lock mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LockMode {
+    /// Any number of readers
+    Shared,
+    /// A single writer
+    Exclusive,
+}
+
+/// Advisory lock on a store root
+///
+/// Clones share one file handle, and acquisitions through the same handle
+/// nest: an operation holding the exclusive lock can call helpers that ask
+/// for it again. A shared hold is upgraded when a nested caller needs
+/// exclusive access and stays exclusive until the outermost guard is dropped.
+#[derive(Clone)]
+pub struct StoreLock {
+    inner: Arc<LockInner>,
+}
+
+struct LockInner {
+    file: File,
+    path: PathBuf,
+    timeout: Duration,
+    held: Mutex<Held>,
+}
+
+#[derive(Default)]
+struct Held {
+    mode: Option<LockMode>,
+    count: usize,
+}
+
+/// Releases its hold on the store lock when dropped
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct StoreLockGuard {
+    lock: StoreLock,
+}
+
+impl StoreLock {
+    /// Open (creating if needed) the lock file of a store root
+    pub fn open(root: &Path, timeout: Duration) -> Result<Self> {
+        let path = root.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        Ok(Self {
+            inner: Arc::new(LockInner {
+                file,
+                path,
+                timeout,
+                held: Mutex::new(Held::default()),
+            }),
+        })
+    }
+
+    /// Acquire a shared (read) lock
+    pub fn shared(&self) -> Result<StoreLockGuard> {
+        self.acquire(LockMode::Shared)
+    }
+
+    /// Acquire an exclusive (write) lock
+    pub fn exclusive(&self) -> Result<StoreLockGuard> {
+        self.acquire(LockMode::Exclusive)
+    }
+
+    fn acquire(&self, mode: LockMode) -> Result<StoreLockGuard> {
+        let mut held = self
+            .inner
+            .held
+            .lock()
+            .map_err(|_| Error::System("Store lock state poisoned".to_string()))?;
+
+        if held.mode.is_none_or(|current| current < mode) {
+            self.lock_file(mode)?;
+            held.mode = Some(mode);
+        }
+        held.count += 1;
+
+        Ok(StoreLockGuard { lock: self.clone() })
+    }
+
+    fn lock_file(&self, mode: LockMode) -> Result<()> {
+        let deadline = Instant::now() + self.inner.timeout;
+        loop {
+            let attempt = match mode {
+                LockMode::Shared => self.inner.file.try_lock_shared(),
+                LockMode::Exclusive => self.inner.file.try_lock(),
+            };
+
+            match attempt {
+                Ok(()) => return Ok(()),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(Error::StoreBusy(format!(
+                        "{} is locked by another SkillsHub process (waited {}s)",
+                        self.inner.path.display(),
+                        self.inner.timeout.as_secs()
+                    )));
+                }
+                Err(TryLockError::Error(e)) => return Err(Error::Io(e)),
+            }
+        }
+    }
+}
+
+impl Drop for StoreLockGuard {
+    fn drop(&mut self) {
+        let Ok(mut held) = self.lock.inner.held.lock() else {
+            return;
+        };
+        held.count = held.count.saturating_sub(1);
+        if held.count == 0 {
+            held.mode = None;
+            let _ = self.lock.inner.file.unlock();
+        }
+    }
+}
+
+/// Try to take an exclusive lock on a standalone lock file without waiting
+///
+/// Returns `None` when another handle holds it. Used for per-transaction
+/// locks whose holder may be a different process.
+pub(crate) fn try_lock_file(path: &Path) -> Result<Option<File>> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    match file.try_lock() {
+        Ok(()) => Ok(Some(file)),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(Error::Io(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusive_lock_excludes_other_handles() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = StoreLock::open(dir.path(), Duration::from_millis(100)).unwrap();
+        let second = StoreLock::open(dir.path(), Duration::from_millis(100)).unwrap();
+
+        let guard = first.exclusive().unwrap();
+        // Nested acquisition through the same handle does not deadlock
+        let nested = first.shared().unwrap();
+        assert!(matches!(second.shared(), Err(Error::StoreBusy(_))));
+
+        drop(nested);
+        drop(guard);
+        let _shared = second.shared().unwrap();
+        let _also_shared = first.shared().unwrap();
+        assert!(matches!(second.exclusive(), Err(Error::StoreBusy(_))));
+    }
+}
@@ -0,0 +1,597 @@
+//! Lockfile - exact resolved versions of installed skills
+//!
+//! `skillshub.lock` pins every skill to the source it was resolved from (URL,
+//! ref, commit and subpath) together with the content hash of its files, so
+//! the same store can be reproduced on another machine:
+//!
+//! ```toml
+//! version = 2
+//!
+//! [skills.pdf]
+//! version = "1.2.0"
+//! kind = "git"
+//! url = "https://github.com/anthropics/skills"
+//! commit = "9f2c1e4…"
+//! subpath = "pdf"
+//! content_hash = "3b4c…"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::gc::TEMP_INSTALL_PREFIX;
+use crate::hash;
+use crate::manifest::ManifestSkillStatus;
+use crate::models::{InstallRecord, Skill, SkillId, SkillSource, SkillVersion};
+use crate::registry::git::validate_commit;
+use crate::registry::{parse_skill_md, AggregatedRegistry, GitRegistry, RegistryProvider};
+use crate::store::{write_atomic, LocalStore};
+
+/// File name of the lockfile
+pub const LOCK_FILE: &str = "skillshub.lock";
+
+/// Current lockfile format version
+///
+/// Version 1 lockfiles pin content hashes in the older contents-only format
+/// (see [`hash::legacy_digest`]); they are still verified that way.
+pub const LOCKFILE_VERSION: u32 = 2;
+
+/// Resolved versions of a set of skills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Lockfile format version
+    pub version: u32,
+    /// Locked skills keyed by skill ID
+    #[serde(default)]
+    pub skills: BTreeMap<SkillId, LockedSkill>,
+}
+
+/// Kind of source a skill was resolved from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockedSourceKind {
+    Git,
+    Registry,
+    Http,
+    Local,
+}
+
+/// A single pinned skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Resolved version string
+    pub version: String,
+    /// Source kind
+    pub kind: LockedSourceKind,
+    /// Repository URL, registry URL, download URL or local path
+    pub url: String,
+    /// Branch or tag the skill was resolved from
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Exact git commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Path of the skill inside the source (git subpath or registry skill ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpath: Option<String>,
+    /// Content hash of the skill files (see [`hash::digest`])
+    pub content_hash: String,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Load a lockfile
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let lockfile: Self = toml::from_str(&content).map_err(|e| {
+            Error::InvalidSkillFormat(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+
+        if lockfile.version > LOCKFILE_VERSION {
+            return Err(Error::InvalidSkillFormat(format!(
+                "{} uses lockfile version {}, this build supports up to {}",
+                path.display(),
+                lockfile.version,
+                LOCKFILE_VERSION
+            )));
+        }
+        for (skill_id, locked) in &lockfile.skills {
+            if let Some(commit) = &locked.commit {
+                validate_commit(commit).map_err(|e| {
+                    Error::InvalidSkillFormat(format!("{}: {}: {}", path.display(), skill_id, e))
+                })?;
+            }
+        }
+        Ok(lockfile)
+    }
+
+    /// Write the lockfile atomically
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| Error::System(format!("Failed to serialize lockfile: {}", e)))?;
+        write_atomic(path, content.as_bytes())
+    }
+
+    /// Lock the given installed skills (all installed skills when `skill_ids` is `None`)
+    ///
+    /// Skills whose record has no content hash are hashed from the store.
+    pub fn from_store(store: &LocalStore, skill_ids: Option<&[SkillId]>) -> Result<Self> {
+        let mut lockfile = Self::default();
+
+        for record in store.list_installed() {
+            if skill_ids.is_some_and(|ids| !ids.contains(&record.skill_id)) {
+                continue;
+            }
+            let content_hash = if record.version.content_hash.is_empty() {
+                store.calculate_hash(&record.skill_id)?
+            } else {
+                record.version.content_hash.clone()
+            };
+            lockfile.skills.insert(
+                record.skill_id.clone(),
+                LockedSkill::from_record(record, content_hash),
+            );
+        }
+
+        Ok(lockfile)
+    }
+
+    /// Hash a skill directory the way this lockfile's content hashes were computed
+    fn content_hash(&self, path: &Path) -> Result<String> {
+        if self.version < 2 {
+            hash::legacy_digest(path)
+        } else {
+            hash::digest(path)
+        }
+    }
+
+    /// Content hash of an installed skill, comparable with this lockfile's hashes
+    fn installed_hash(&self, store: &LocalStore, skill_id: &SkillId) -> Option<String> {
+        if !store.is_installed(skill_id) {
+            return None;
+        }
+        if self.version < 2 {
+            hash::legacy_digest(&store.skill_path(skill_id)).ok()
+        } else {
+            store.calculate_hash(skill_id).ok()
+        }
+    }
+
+    /// Rewrite local paths inside `root` relative to it, so a lockfile
+    /// committed to a repository works from any checkout
+    pub fn relative_to(mut self, root: &Path) -> Self {
+        for locked in self.skills.values_mut() {
+            if locked.kind != LockedSourceKind::Local {
+                continue;
+            }
+            if let Ok(relative) = Path::new(&locked.url).strip_prefix(root) {
+                locked.url = relative.display().to_string();
+            }
+        }
+        self
+    }
+}
+
+impl LockedSkill {
+    fn from_record(record: &InstallRecord, content_hash: String) -> Self {
+        let (kind, url, reference, subpath) = match &record.source {
+            SkillSource::Git { url, branch, path } => (
+                LockedSourceKind::Git,
+                url.clone(),
+                branch.clone(),
+                path.clone(),
+            ),
+            SkillSource::Registry {
+                registry_url,
+                skill_id,
+            } => (
+                LockedSourceKind::Registry,
+                registry_url.clone(),
+                None,
+                Some(skill_id.clone()),
+            ),
+            SkillSource::Http { url } => (LockedSourceKind::Http, url.clone(), None, None),
+            SkillSource::Local { path } => (
+                LockedSourceKind::Local,
+                path.display().to_string(),
+                None,
+                None,
+            ),
+        };
+
+        Self {
+            version: record.version.version.clone(),
+            kind,
+            url,
+            reference,
+            commit: record.version.commit.clone(),
+            subpath,
+            content_hash,
+        }
+    }
+
+    /// Rebuild the source this skill was resolved from
+    fn source(&self, skill_id: &SkillId) -> SkillSource {
+        match self.kind {
+            LockedSourceKind::Git => SkillSource::Git {
+                url: self.url.clone(),
+                branch: self.reference.clone(),
+                path: self.subpath.clone(),
+            },
+            LockedSourceKind::Registry => SkillSource::Registry {
+                registry_url: self.url.clone(),
+                skill_id: self.subpath.clone().unwrap_or_else(|| skill_id.to_string()),
+            },
+            LockedSourceKind::Http => SkillSource::Http {
+                url: self.url.clone(),
+            },
+            LockedSourceKind::Local => SkillSource::Local {
+                path: PathBuf::from(&self.url),
+            },
+        }
+    }
+}
+
+/// Install every locked skill whose stored content differs from the lock
+///
+/// Each skill is fetched from its pinned source and imported only if the
+/// fetched files hash to the locked `content_hash`. `lock_dir` is used to
+/// resolve relative local paths; `registries` serves registry-kind sources.
+pub async fn install_locked(
+    lockfile: &Lockfile,
+    lock_dir: &Path,
+    store: &mut LocalStore,
+    registries: &AggregatedRegistry,
+) -> Vec<(SkillId, ManifestSkillStatus)> {
+    let mut results = Vec::new();
+
+    for (skill_id, locked) in &lockfile.skills {
+        if lockfile.installed_hash(store, skill_id).as_deref() == Some(&locked.content_hash) {
+            results.push((
+                skill_id.clone(),
+                ManifestSkillStatus::Satisfied {
+                    version: locked.version.clone(),
+                },
+            ));
+            continue;
+        }
+
+        let status =
+            match install_one(lockfile, skill_id, locked, lock_dir, store, registries).await {
+                Ok(registry) => ManifestSkillStatus::Installed {
+                    version: locked.version.clone(),
+                    registry,
+                },
+                Err(e) => ManifestSkillStatus::Failed {
+                    error: e.to_string(),
+                },
+            };
+        results.push((skill_id.clone(), status));
+    }
+
+    results
+}
+
+async fn install_one(
+    lockfile: &Lockfile,
+    skill_id: &SkillId,
+    locked: &LockedSkill,
+    lock_dir: &Path,
+    store: &mut LocalStore,
+    registries: &AggregatedRegistry,
+) -> Result<String> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("{}{}", TEMP_INSTALL_PREFIX, skill_id.file_name()));
+    let result = match fetch_locked(skill_id, locked, lock_dir, registries, &temp_dir).await {
+        Ok((path, registry)) => {
+            verify_and_import(lockfile, skill_id, locked, &path, store).map(|_| registry)
+        }
+        Err(e) => Err(e),
+    };
+
+    // A fetch that failed part way may have left files behind as well
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Fetch a locked skill, returning its path and the source it came from
+///
+/// Remote sources are fetched into `temp_dir`; local ones are used in place.
+async fn fetch_locked(
+    skill_id: &SkillId,
+    locked: &LockedSkill,
+    lock_dir: &Path,
+    registries: &AggregatedRegistry,
+    temp_dir: &Path,
+) -> Result<(PathBuf, String)> {
+    let subpath = locked.subpath.as_deref().map(SkillId::new).transpose()?;
+
+    match locked.kind {
+        LockedSourceKind::Git => {
+            let provider = match &locked.commit {
+                Some(commit) => {
+                    GitRegistry::pinned(&locked.url, &locked.url, locked.reference.clone(), commit)?
+                }
+                None => GitRegistry::new(&locked.url, &locked.url, locked.reference.clone()),
+            };
+            let subpath = subpath.unwrap_or_else(|| skill_id.unqualified());
+            let path = provider.fetch(&subpath, temp_dir).await?;
+            Ok((path, locked.url.clone()))
+        }
+        LockedSourceKind::Registry => {
+            let subpath = subpath.unwrap_or_else(|| skill_id.clone());
+            let (_, path, registry) = registries.fetch_skill(&subpath, temp_dir).await?;
+            Ok((path, registry))
+        }
+        LockedSourceKind::Local => {
+            let path = lock_dir.join(&locked.url);
+            if !path.is_dir() {
+                return Err(Error::SkillNotFound(format!(
+                    "{} (local path {} does not exist)",
+                    skill_id,
+                    path.display()
+                )));
+            }
+            Ok((path, "local".to_string()))
+        }
+        LockedSourceKind::Http => Err(Error::RegistryError(format!(
+            "Cannot reproduce {}: HTTP sources are not supported in locked installs",
+            skill_id
+        ))),
+    }
+}
+
+/// Check fetched content against the lock, then stage, scan and commit it
+///
+/// A matching hash only shows the content is what was locked, not that it
+/// is safe, so it is scanned like any other install.
+fn verify_and_import(
+    lockfile: &Lockfile,
+    skill_id: &SkillId,
+    locked: &LockedSkill,
+    path: &Path,
+    store: &mut LocalStore,
+) -> Result<()> {
+    let actual = lockfile.content_hash(path)?;
+    if actual != locked.content_hash {
+        return Err(Error::IntegrityError(format!(
+            "{}: fetched content hash {} does not match locked {}",
+            skill_id, actual, locked.content_hash
+        )));
+    }
+
+    let mut version = SkillVersion::new(&locked.version, &locked.content_hash);
+    version.commit = locked.commit.clone();
+
+    let skill_md_path = path.join("SKILL.md");
+    let metadata = parse_skill_md(&skill_md_path).unwrap_or_default();
+    let skill = Skill {
+        id: skill_id.clone(),
+        name: metadata.name.unwrap_or_else(|| skill_id.to_string()),
+        description: metadata.description.unwrap_or_default(),
+        author: metadata.author,
+        tags: metadata.tags,
+        compatible_tools: metadata.compatible_tools,
+        version,
+        source: locked.source(skill_id),
+        skill_md_path,
+        resources: Vec::new(),
+        metadata: std::collections::HashMap::new(),
+    };
+
+    store.import_scanned(&skill, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::StoreConfig;
+    use tempfile::TempDir;
+
+    fn skill_id(id: &str) -> SkillId {
+        SkillId::new(id).unwrap()
+    }
+
+    fn local_skill(dir: &Path, content: &str) -> PathBuf {
+        let skill_dir = dir.join("hello");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+        skill_dir
+    }
+
+    fn temp_store(dir: &Path) -> LocalStore {
+        LocalStore::new(StoreConfig {
+            root_dir: dir.join("store"),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn locked_local(path: &Path, content_hash: String) -> Lockfile {
+        let mut lockfile = Lockfile::default();
+        lockfile.skills.insert(
+            SkillId::new("hello").unwrap(),
+            LockedSkill {
+                version: "1.0.0".to_string(),
+                kind: LockedSourceKind::Local,
+                url: path.display().to_string(),
+                reference: None,
+                commit: None,
+                subpath: None,
+                content_hash,
+            },
+        );
+        lockfile
+    }
+
+    #[test]
+    fn test_lockfile_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+
+        let mut lockfile = locked_local(Path::new("skills/hello"), "abc".to_string());
+        lockfile.skills.insert(
+            SkillId::new("pdf").unwrap(),
+            LockedSkill {
+                version: "1.2.0".to_string(),
+                kind: LockedSourceKind::Git,
+                url: "https://github.com/anthropics/skills".to_string(),
+                reference: Some("main".to_string()),
+                commit: Some("0123456789abcdef".to_string()),
+                subpath: Some("pdf".to_string()),
+                content_hash: "def".to_string(),
+            },
+        );
+        lockfile.save(&path).unwrap();
+
+        let loaded = Lockfile::load(&path).unwrap();
+        assert_eq!(loaded.skills, lockfile.skills);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("ref = \"main\""));
+    }
+
+    #[test]
+    fn test_unsafe_commits_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let url = "https://github.com/anthropics/skills";
+
+        for commit in [
+            "x/../../../..",
+            "--upload-pack=touch pwned",
+            "0123abc/..",
+            "abc",
+        ] {
+            let mut lockfile = Lockfile::default();
+            lockfile.skills.insert(
+                SkillId::new("pdf").unwrap(),
+                LockedSkill {
+                    version: "1.2.0".to_string(),
+                    kind: LockedSourceKind::Git,
+                    url: url.to_string(),
+                    reference: None,
+                    commit: Some(commit.to_string()),
+                    subpath: None,
+                    content_hash: "def".to_string(),
+                },
+            );
+            lockfile.save(&path).unwrap();
+            assert!(
+                matches!(Lockfile::load(&path), Err(Error::InvalidSkillFormat(_))),
+                "{} was accepted",
+                commit
+            );
+            assert!(GitRegistry::pinned(url, url, None, commit).is_err());
+        }
+        assert!(GitRegistry::pinned(url, url, None, "0123456789abcdef").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_locked_install_verifies_hash() {
+        let dir = TempDir::new().unwrap();
+        let source = local_skill(dir.path(), "---\nname: hello\n---\n");
+        let content_hash = hash::digest(&source).unwrap();
+        let registries = AggregatedRegistry::new();
+
+        let mut store = temp_store(dir.path());
+        let results = install_locked(
+            &locked_local(&source, content_hash.clone()),
+            dir.path(),
+            &mut store,
+            &registries,
+        )
+        .await;
+        assert!(matches!(
+            results[0].1,
+            ManifestSkillStatus::Installed { .. }
+        ));
+        assert_eq!(
+            store.calculate_hash(&skill_id("hello")).unwrap(),
+            content_hash
+        );
+
+        // A second run is a no-op
+        let results = install_locked(
+            &locked_local(&source, content_hash),
+            dir.path(),
+            &mut store,
+            &registries,
+        )
+        .await;
+        assert!(matches!(
+            results[0].1,
+            ManifestSkillStatus::Satisfied { .. }
+        ));
+
+        // Content that no longer matches the lock is rejected
+        let mut other = temp_store(&dir.path().join("other"));
+        let results = install_locked(
+            &locked_local(&source, "stale".to_string()),
+            dir.path(),
+            &mut other,
+            &registries,
+        )
+        .await;
+        assert!(matches!(results[0].1, ManifestSkillStatus::Failed { .. }));
+        assert!(!other.is_installed(&skill_id("hello")));
+
+        // So is matching content the security scan blocks
+        fs::write(source.join("helper.exe"), "MZ").unwrap();
+        let results = install_locked(
+            &locked_local(&source, hash::digest(&source).unwrap()),
+            dir.path(),
+            &mut other,
+            &registries,
+        )
+        .await;
+        assert!(matches!(results[0].1, ManifestSkillStatus::Failed { .. }));
+        assert!(!other.is_installed(&skill_id("hello")));
+    }
+
+    #[tokio::test]
+    async fn test_version_1_lockfile_uses_legacy_hashes() {
+        let dir = TempDir::new().unwrap();
+        let source = local_skill(dir.path(), "---\nname: hello\n---\n");
+        let registries = AggregatedRegistry::new();
+
+        let mut lockfile = locked_local(&source, hash::legacy_digest(&source).unwrap());
+        lockfile.version = 1;
+        let path = dir.path().join(LOCK_FILE);
+        lockfile.save(&path).unwrap();
+        let lockfile = Lockfile::load(&path).unwrap();
+
+        let mut store = temp_store(dir.path());
+        let results = install_locked(&lockfile, dir.path(), &mut store, &registries).await;
+        assert!(matches!(
+            results[0].1,
+            ManifestSkillStatus::Installed { .. }
+        ));
+        let results = install_locked(&lockfile, dir.path(), &mut store, &registries).await;
+        assert!(matches!(
+            results[0].1,
+            ManifestSkillStatus::Satisfied { .. }
+        ));
+
+        // Re-locking records the current digest format
+        let relocked = Lockfile::from_store(&store, None).unwrap();
+        assert_eq!(relocked.version, LOCKFILE_VERSION);
+        assert_eq!(
+            relocked.skills["hello"].content_hash,
+            hash::digest(&source).unwrap()
+        );
+    }
+}
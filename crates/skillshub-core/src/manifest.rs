@@ -0,0 +1,381 @@
+//! Project manifest - per-repository skill requirements
+//!
+//! A `skillshub.toml` committed to a repository declares which skills the
+//! project needs, where they come from, which versions are acceptable and
+//! which tools they are projected into:
+//!
+//! ```toml
+//! tools = ["claude", "cursor"]
+//!
+//! [skills.pdf]
+//! source = "anthropics"
+//! version = "^1.0"
+//!
+//! [skills.code-review]
+//! source = "https://github.com/org/skills"
+//! ```
+//!
+//! `source` may be a configured registry name, a git URL or a path relative to
+//! the manifest. Without a source, all enabled registries are searched.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::gc::TEMP_INSTALL_PREFIX;
+use crate::models::{Skill, SkillId, SyncStrategy, ToolType};
+use crate::registry::{GitRegistry, LocalRegistry, RegistryManager, RegistryProvider};
+use crate::store::LocalStore;
+use crate::sync::SyncEngine;
+
+/// File name of the project manifest
+pub const MANIFEST_FILE: &str = "skillshub.toml";
+
+/// Skill requirements declared by a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    /// Tools to project skills into (lowercase keys, e.g. "claude")
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Required skills keyed by skill ID
+    #[serde(default)]
+    pub skills: BTreeMap<SkillId, ManifestSkill>,
+}
+
+/// A single skill requirement
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestSkill {
+    /// Registry name, git URL or local path (relative to the manifest)
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Version constraint: a semver requirement such as "^1.2" or "=1.2.0";
+    /// non-semver versions (tags, branches) must match exactly
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl ManifestSkill {
+    /// Check whether an installed or fetched version satisfies the constraint
+    pub fn accepts(&self, version: &str) -> bool {
+        let Some(constraint) = self.version.as_deref().map(str::trim) else {
+            return true;
+        };
+        if constraint.is_empty() || constraint == "*" {
+            return true;
+        }
+
+        match (VersionReq::parse(constraint), Version::parse(version)) {
+            (Ok(req), Ok(version)) => req.matches(&version),
+            // Non-semver versions (tags, commits) must match exactly
+            _ => constraint == version,
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Load a manifest file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            Error::InvalidSkillFormat(format!("Failed to parse {}: {}", path.display(), e))
+        })
+    }
+
+    /// Find the nearest manifest in `start` or one of its ancestors
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Resolve the declared tools
+    pub fn tool_types(&self) -> Result<Vec<ToolType>> {
+        self.tools
+            .iter()
+            .map(|key| ToolType::from_key(key).ok_or_else(|| Error::ToolNotFound(key.clone())))
+            .collect()
+    }
+}
+
+/// Outcome of resolving one manifest skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum ManifestSkillStatus {
+    /// Already in the store with an acceptable version
+    Satisfied { version: String },
+    /// Fetched and imported into the store
+    Installed { version: String, registry: String },
+    /// Could not be resolved
+    Failed { error: String },
+}
+
+/// Result of projecting a manifest into a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestReport {
+    /// Projections that were written
+    pub projected: Vec<(SkillId, ToolType)>,
+    /// Declared projections that could not be written
+    pub missing: Vec<(SkillId, ToolType, String)>,
+    /// Skills found in the project's tool directories that the manifest does not declare
+    pub extra: Vec<(SkillId, ToolType)>,
+}
+
+/// Import every declared skill that is missing from the store (or whose
+/// installed version does not satisfy the constraint)
+///
+/// `manifest_dir` is used to resolve relative local sources.
+pub async fn install_missing(
+    manifest: &ProjectManifest,
+    manifest_dir: &Path,
+    store: &mut LocalStore,
+    registries: &RegistryManager,
+) -> Vec<(SkillId, ManifestSkillStatus)> {
+    let mut results = Vec::new();
+
+    for (skill_id, spec) in &manifest.skills {
+        if let Some(record) = store.get_record(skill_id) {
+            if spec.accepts(&record.version.version) {
+                results.push((
+                    skill_id.clone(),
+                    ManifestSkillStatus::Satisfied {
+                        version: record.version.version.clone(),
+                    },
+                ));
+                continue;
+            }
+        }
+
+        let status = match install_one(skill_id, spec, manifest_dir, store, registries).await {
+            Ok((skill, registry)) => ManifestSkillStatus::Installed {
+                version: skill.version.version,
+                registry,
+            },
+            Err(e) => ManifestSkillStatus::Failed {
+                error: e.to_string(),
+            },
+        };
+        results.push((skill_id.clone(), status));
+    }
+
+    results
+}
+
+async fn install_one(
+    skill_id: &SkillId,
+    spec: &ManifestSkill,
+    manifest_dir: &Path,
+    store: &mut LocalStore,
+    registries: &RegistryManager,
+) -> Result<(Skill, String)> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("{}{}", TEMP_INSTALL_PREFIX, skill_id.file_name()));
+    let result = match fetch_declared(skill_id, spec, manifest_dir, registries, &temp_dir).await {
+        Ok((skill, path, registry)) => {
+            import_declared(skill_id, spec, store, skill, &path).map(|skill| (skill, registry))
+        }
+        Err(e) => Err(e),
+    };
+
+    // A fetch that failed part way may have left files behind as well
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Fetch a declared skill from its source or the configured registries
+async fn fetch_declared(
+    skill_id: &SkillId,
+    spec: &ManifestSkill,
+    manifest_dir: &Path,
+    registries: &RegistryManager,
+    temp_dir: &Path,
+) -> Result<(Skill, PathBuf, String)> {
+    match spec.source.as_deref() {
+        None => {
+            registries
+                .aggregated()
+                .fetch_skill(skill_id, temp_dir)
+                .await
+        }
+        Some(source) => {
+            let provider = source_provider(source, manifest_dir, registries)?;
+            let id = if skill_id.namespace() == Some(provider.name()) {
+                skill_id.unqualified()
+            } else {
+                skill_id.clone()
+            };
+            let skill = provider.get_skill(&id).await?;
+            let path = provider.fetch(&id, temp_dir).await?;
+            Ok((skill, path, provider.name().to_string()))
+        }
+    }
+}
+
+/// Check a fetched skill against its version constraint and import it
+fn import_declared(
+    skill_id: &SkillId,
+    spec: &ManifestSkill,
+    store: &mut LocalStore,
+    skill: Skill,
+    path: &Path,
+) -> Result<Skill> {
+    // The store keeps the skill under the ID the manifest declares
+    let skill = Skill {
+        id: skill_id.clone(),
+        ..skill
+    };
+
+    if !spec.accepts(&skill.version.version) {
+        return Err(Error::RegistryError(format!(
+            "{} {} does not satisfy version constraint {}",
+            skill_id,
+            skill.version.version,
+            spec.version.as_deref().unwrap_or("*")
+        )));
+    }
+
+    // Declared skills are scanned like any other install before they are committed
+    store.import_scanned(&skill, path)?;
+    Ok(skill)
+}
+
+/// Build a provider for an explicit manifest source
+fn source_provider(
+    source: &str,
+    manifest_dir: &Path,
+    registries: &RegistryManager,
+) -> Result<Box<dyn RegistryProvider>> {
+    if let Some(provider) = registries.get_provider(source) {
+        return Ok(provider);
+    }
+
+    if source.starts_with("http://")
+        || source.starts_with("https://")
+        || source.starts_with("git@")
+        || source.ends_with(".git")
+    {
+        return Ok(Box::new(GitRegistry::new(source, source, None)));
+    }
+
+    let path = manifest_dir.join(source);
+    if path.is_dir() {
+        return Ok(Box::new(LocalRegistry::new(source, path)));
+    }
+
+    Err(Error::RegistryError(format!(
+        "Unknown skill source '{}': not a configured registry, git URL or directory",
+        source
+    )))
+}
+
+/// Project every declared skill that is in the store into the declared tools,
+/// then report undeclared skills found in the project's tool directories
+///
+/// `engine` should be project-scoped (see [`SyncEngine::for_project`]).
+pub fn project_skills<F>(
+    manifest: &ProjectManifest,
+    tools: &[ToolType],
+    engine: &mut SyncEngine,
+    strategy_resolver: F,
+) -> ManifestReport
+where
+    F: Fn(ToolType) -> SyncStrategy,
+{
+    let mut report = ManifestReport::default();
+
+    for skill_id in manifest.skills.keys() {
+        for tool in tools {
+            if !engine.store().is_installed(skill_id) {
+                report.missing.push((
+                    skill_id.clone(),
+                    *tool,
+                    "not installed in the store".to_string(),
+                ));
+                continue;
+            }
+
+            match engine.sync_skill(skill_id, *tool, strategy_resolver(*tool)) {
+                Ok(()) => report.projected.push((skill_id.clone(), *tool)),
+                Err(e) => report
+                    .missing
+                    .push((skill_id.clone(), *tool, e.to_string())),
+            }
+        }
+    }
+
+    let declared: HashSet<&SkillId> = manifest.skills.keys().collect();
+    let targeted: HashSet<&ToolType> = tools.iter().collect();
+    for scanned in engine.scan_all_tools() {
+        if targeted.contains(&scanned.tool) && !declared.contains(&scanned.id) {
+            report.extra.push((scanned.id, scanned.tool));
+        }
+    }
+    report.extra.sort_by(|a, b| a.0.cmp(&b.0));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest: ProjectManifest = toml::from_str(
+            r#"
+tools = ["claude", "Cursor"]
+
+[skills.pdf]
+source = "anthropics"
+version = "^1.2"
+
+[skills.notes]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.tool_types().unwrap(),
+            vec![ToolType::Claude, ToolType::Cursor]
+        );
+        assert_eq!(manifest.skills.len(), 2);
+        assert_eq!(manifest.skills["pdf"].source.as_deref(), Some("anthropics"));
+        assert!(manifest.skills["notes"].source.is_none());
+
+        // Skill IDs are validated when the manifest is parsed
+        assert!(toml::from_str::<ProjectManifest>("[skills.\"../x\"]").is_err());
+    }
+
+    #[test]
+    fn test_version_constraints() {
+        let spec = ManifestSkill {
+            source: None,
+            version: Some("^1.2".to_string()),
+        };
+        assert!(spec.accepts("1.4.0"));
+        assert!(!spec.accepts("2.0.0"));
+        assert!(!spec.accepts("1.1.9"));
+
+        let exact_tag = ManifestSkill {
+            source: None,
+            version: Some("main".to_string()),
+        };
+        assert!(exact_tag.accepts("main"));
+        assert!(!exact_tag.accepts("dev"));
+
+        assert!(ManifestSkill::default().accepts("anything"));
+    }
+
+    #[test]
+    fn test_unknown_tool_is_rejected() {
+        let manifest = ProjectManifest {
+            tools: vec!["notatool".to_string()],
+            skills: BTreeMap::new(),
+        };
+        assert!(manifest.tool_types().is_err());
+    }
+}
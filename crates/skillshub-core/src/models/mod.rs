@@ -2,10 +2,14 @@
 
 mod scan_report;
 mod skill;
+mod skill_id;
 mod sync_state;
 mod tool;
+mod verify_report;
 
 pub use scan_report::*;
 pub use skill::*;
+pub use skill_id::*;
 pub use sync_state::*;
 pub use tool::*;
+pub use verify_report::*;
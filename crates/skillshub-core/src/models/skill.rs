@@ -4,11 +4,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::SkillId;
+
 /// Represents a skill package (SKILL.md + resources)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skill {
     /// Unique identifier for the skill
-    pub id: String,
+    pub id: SkillId,
     /// Display name
     pub name: String,
     /// Description of the skill
@@ -111,7 +113,7 @@ pub struct SkillMetadata {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstallRecord {
     /// Skill ID
-    pub skill_id: String,
+    pub skill_id: SkillId,
     /// Installed version
     pub version: SkillVersion,
     /// Installation timestamp
@@ -122,4 +124,16 @@ pub struct InstallRecord {
     pub projected_tools: Vec<String>,
     /// Security scan result at install time
     pub scan_passed: bool,
+    /// Previous versions retained in the store for rollback, newest first
+    #[serde(default)]
+    pub history: Vec<SkillVersion>,
+    /// Format of the content hashes above (see [`crate::hash::DIGEST_VERSION`])
+    #[serde(default)]
+    pub digest_version: u32,
+    /// Name of the skill's directory in tools, instead of the last segment of its ID
+    #[serde(default)]
+    pub alias: Option<SkillId>,
+    /// Kept in the store but not projected into any tool
+    #[serde(default)]
+    pub disabled: bool,
 }
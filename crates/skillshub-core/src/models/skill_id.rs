@@ -0,0 +1,293 @@
+//! Skill identifiers
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Longest on-disk name of a skill ID, leaving room for suffixes such as
+/// `.json` within common 255-byte file name limits
+const MAX_FILE_NAME_LEN: usize = 200;
+
+/// Device names Windows reserves regardless of extension
+const WINDOWS_RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Validated skill identifier
+///
+/// IDs come from registries, URLs, directory names and Claude plugins
+/// (`plugin@marketplace:skill`), so they are checked before they reach a
+/// path. An ID is one or more `/`-separated segments: skills installed from
+/// a registry are namespaced by it (`registry/skill` or
+/// `registry/owner/skill`), so the same name from two sources does not
+/// collide. Empty segments, `.`/`..`, backslashes, control characters and
+/// surrounding whitespace are rejected. Everything else is allowed and
+/// stored on disk under [`SkillId::file_name`], a reversible encoding that
+/// is safe on every platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SkillId(String);
+
+impl SkillId {
+    /// Validate an ID
+    pub fn new(id: impl Into<String>) -> Result<Self> {
+        let id = id.into();
+        let invalid = |reason: &str| Err(Error::InvalidSkillId(format!("{:?}: {}", id, reason)));
+
+        if id.is_empty() {
+            return invalid("must not be empty");
+        }
+        if id.contains('\\') {
+            return invalid("must not contain backslashes");
+        }
+        if id.chars().any(char::is_control) {
+            return invalid("must not contain control characters");
+        }
+        for segment in id.split('/') {
+            if segment.is_empty() {
+                return invalid("must not contain empty segments");
+            }
+            if segment == "." || segment == ".." {
+                return invalid("must not be a relative path");
+            }
+            if segment.trim() != segment {
+                return invalid("must not start or end with whitespace");
+            }
+        }
+
+        if Self::encode(&id).len() > MAX_FILE_NAME_LEN {
+            return invalid("is too long");
+        }
+        Ok(Self(id))
+    }
+
+    /// Decode an on-disk name produced by [`Self::file_name`]
+    pub fn from_file_name(name: &str) -> Result<Self> {
+        let invalid = || Error::InvalidSkillId(format!("{:?}: not a skill file name", name));
+
+        let mut bytes = Vec::with_capacity(name.len());
+        let mut rest = name.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'%' {
+                let hex = tail.get(..2).ok_or_else(invalid)?;
+                let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+                rest = &tail[2..];
+            } else {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+
+        let id = Self::new(String::from_utf8(bytes).map_err(|_| invalid())?)?;
+        // Only the canonical encoding is accepted, so names and IDs map one to one
+        if id.file_name() != name {
+            return Err(invalid());
+        }
+        Ok(id)
+    }
+
+    /// Namespace an ID with the registry it comes from
+    pub fn qualified(registry: &str, id: &SkillId) -> Result<Self> {
+        Self::new(format!("{}/{}", registry, id))
+    }
+
+    /// The ID as given
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the ID is namespaced
+    pub fn is_qualified(&self) -> bool {
+        self.0.contains('/')
+    }
+
+    /// First segment of a namespaced ID, normally the registry
+    pub fn namespace(&self) -> Option<&str> {
+        self.0.split_once('/').map(|(namespace, _)| namespace)
+    }
+
+    /// The ID without its namespace, as the registry knows it
+    pub fn unqualified(&self) -> SkillId {
+        match self.0.split_once('/') {
+            Some((_, rest)) => Self(rest.to_string()),
+            None => self.clone(),
+        }
+    }
+
+    /// Last segment, the default name in tool directories
+    pub fn short_name(&self) -> SkillId {
+        match self.0.rsplit_once('/') {
+            Some((_, name)) => Self(name.to_string()),
+            None => self.clone(),
+        }
+    }
+
+    /// Name used for the skill's files and directories
+    ///
+    /// ASCII letters, digits and `-_.@+` are kept; every other byte is
+    /// written as `%XX`, as are a leading or trailing `.` and the first
+    /// character of names Windows reserves (`con`, `nul`, ...). Plain IDs
+    /// like `pdf` or `frontend-design` are stored unchanged.
+    pub fn file_name(&self) -> String {
+        Self::encode(&self.0)
+    }
+
+    fn encode(id: &str) -> String {
+        let stem = id.split('.').next().unwrap_or_default();
+        let id = id.as_bytes();
+        let reserved = WINDOWS_RESERVED.contains(&stem.to_ascii_lowercase().as_str());
+
+        let mut name = String::with_capacity(id.len());
+        for (i, &byte) in id.iter().enumerate() {
+            let safe = byte.is_ascii_alphanumeric() || b"-_.@+".contains(&byte);
+            let edge_dot = byte == b'.' && (i == 0 || i == id.len() - 1);
+            if !safe || edge_dot || (reserved && i == 0) {
+                name.push_str(&format!("%{:02X}", byte));
+            } else {
+                name.push(byte as char);
+            }
+        }
+        name
+    }
+}
+
+impl fmt::Display for SkillId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for SkillId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for SkillId {
+    type Error = Error;
+
+    fn try_from(id: String) -> Result<Self> {
+        Self::new(id)
+    }
+}
+
+impl TryFrom<&str> for SkillId {
+    type Error = Error;
+
+    fn try_from(id: &str) -> Result<Self> {
+        Self::new(id)
+    }
+}
+
+impl From<SkillId> for String {
+    fn from(id: SkillId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for SkillId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SkillId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for SkillId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SkillId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SkillId {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsafe_ids_are_rejected() {
+        for id in [
+            "",
+            ".",
+            "..",
+            "../../.ssh",
+            "a//b",
+            "/a",
+            "a/",
+            "a/ b",
+            "a\\b",
+            "a\0b",
+            " pdf",
+            "pdf\n",
+        ] {
+            assert!(SkillId::new(id).is_err(), "{:?} should be rejected", id);
+        }
+        assert!(serde_json::from_str::<SkillId>("\"../etc\"").is_err());
+        assert!(SkillId::new("x".repeat(MAX_FILE_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_file_names_round_trip() {
+        let cases = [
+            ("pdf", "pdf"),
+            ("frontend-design", "frontend-design"),
+            (
+                "superpowers@market:brainstorm",
+                "superpowers@market%3Abrainstorm",
+            ),
+            (".hidden", "%2Ehidden"),
+            ("con", "%63on"),
+            ("Nul.txt", "%4Eul.txt"),
+            ("100%", "100%25"),
+            ("résumé", "r%C3%A9sum%C3%A9"),
+            ("anthropics/pdf", "anthropics%2Fpdf"),
+        ];
+        for (id, file_name) in cases {
+            let id = SkillId::new(id).unwrap();
+            assert_eq!(id.file_name(), file_name);
+            assert_eq!(SkillId::from_file_name(file_name).unwrap(), id);
+        }
+
+        // Non-canonical or undecodable names do not map to an ID
+        for name in ["%70df", "a:b", "%2", "%2F", "a%2F..", "%FF"] {
+            assert!(SkillId::from_file_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_namespaced_ids() {
+        let id = SkillId::new("anthropics/owner/pdf").unwrap();
+        assert!(id.is_qualified());
+        assert_eq!(id.namespace(), Some("anthropics"));
+        assert_eq!(id.unqualified(), "owner/pdf");
+        assert_eq!(id.short_name(), "pdf");
+
+        let bare = SkillId::new("pdf").unwrap();
+        assert!(!bare.is_qualified());
+        assert_eq!(bare.namespace(), None);
+        assert_eq!(bare.short_name(), bare);
+        assert_eq!(SkillId::qualified("obra", &bare).unwrap(), "obra/pdf");
+    }
+}
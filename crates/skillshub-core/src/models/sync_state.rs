@@ -4,24 +4,71 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use super::{SkillVersion, SyncStrategy, ToolType};
+use super::{RenderFormat, SkillId, SkillVersion, SyncStrategy, ToolType};
+
+/// Current schema version of the persisted sync state
+pub const SYNC_STATE_SCHEMA_VERSION: u32 = 1;
+
+/// Where projections are written
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum SyncScope {
+    /// Each tool's global skills directory (e.g. `~/.claude/skills/`)
+    #[default]
+    Global,
+    /// Each tool's project-level directory inside a repository (e.g. `<root>/.claude/skills/`)
+    Project { root: PathBuf },
+}
+
+impl std::fmt::Display for SyncScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncScope::Global => write!(f, "global"),
+            SyncScope::Project { root } => write!(f, "project:{}", root.display()),
+        }
+    }
+}
 
 /// Sync state for all tools
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncState {
+    /// Schema version of the persisted file
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Scope the projections in this state belong to
+    #[serde(default)]
+    pub scope: SyncScope,
     /// Per-tool sync status
     pub tools: HashMap<String, ToolSyncState>,
     /// Last sync timestamp
     pub last_sync: Option<String>,
 }
 
+impl Default for SyncState {
+    fn default() -> Self {
+        Self::new(SyncScope::Global)
+    }
+}
+
+impl SyncState {
+    /// Create an empty state for a scope
+    pub fn new(scope: SyncScope) -> Self {
+        Self {
+            schema_version: SYNC_STATE_SCHEMA_VERSION,
+            scope,
+            tools: HashMap::new(),
+            last_sync: None,
+        }
+    }
+}
+
 /// Sync state for a single tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSyncState {
     /// Tool type
     pub tool: ToolType,
     /// Skills synced to this tool
-    pub skills: HashMap<String, SkillSyncStatus>,
+    pub skills: HashMap<SkillId, SkillSyncStatus>,
     /// Last sync time for this tool
     pub last_sync: Option<String>,
 }
@@ -30,7 +77,7 @@ pub struct ToolSyncState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillSyncStatus {
     /// Skill ID
-    pub skill_id: String,
+    pub skill_id: SkillId,
     /// Version synced to this tool
     pub version: SkillVersion,
     /// Strategy used for sync
@@ -39,6 +86,12 @@ pub struct SkillSyncStatus {
     pub target_path: PathBuf,
     /// Whether there's drift detected
     pub drift: Option<DriftInfo>,
+    /// Layout the skill was projected in
+    #[serde(default)]
+    pub format: RenderFormat,
+    /// Hash of the file written for rendered formats, to detect edits to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rendered_hash: Option<String>,
 }
 
 /// Information about drift
@@ -50,6 +103,9 @@ pub struct DriftInfo {
     pub description: String,
     /// Detected at timestamp
     pub detected_at: String,
+    /// Files (relative to the skill root) that differ from the store
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
 }
 
 /// Types of drift that can occur
@@ -81,8 +137,17 @@ impl std::fmt::Display for DriftType {
 }
 
 /// Plan for a sync operation
+///
+/// Also serves as the dry-run output of a sync: nothing is touched until a
+/// plan is executed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncPlan {
+    /// Skills to collect from tools into the hub
+    #[serde(default)]
+    pub to_collect: Vec<CollectAction>,
+    /// Skills not collected because tools hold differing copies
+    #[serde(default)]
+    pub conflicts: Vec<CollectConflict>,
     /// Skills to add
     pub to_add: Vec<SyncAction>,
     /// Skills to update
@@ -96,6 +161,8 @@ pub struct SyncPlan {
 impl SyncPlan {
     pub fn new() -> Self {
         Self {
+            to_collect: Vec::new(),
+            conflicts: Vec::new(),
             to_add: Vec::new(),
             to_update: Vec::new(),
             to_remove: Vec::new(),
@@ -103,8 +170,39 @@ impl SyncPlan {
         }
     }
 
+    /// Append another plan's entries
+    pub fn merge(&mut self, other: SyncPlan) {
+        self.to_collect.extend(other.to_collect);
+        self.conflicts.extend(other.conflicts);
+        self.to_add.extend(other.to_add);
+        self.to_update.extend(other.to_update);
+        self.to_remove.extend(other.to_remove);
+        self.to_repair.extend(other.to_repair);
+    }
+
+    /// Tool actions in execution order: adds, updates, repairs, removals
+    pub fn actions(&self) -> impl Iterator<Item = &SyncAction> {
+        self.to_add
+            .iter()
+            .chain(&self.to_update)
+            .chain(&self.to_repair)
+            .chain(&self.to_remove)
+    }
+
+    /// Add an action to the list for its type
+    pub fn push(&mut self, action: SyncAction) {
+        match action.action {
+            SyncActionType::Add => self.to_add.push(action),
+            SyncActionType::Update => self.to_update.push(action),
+            SyncActionType::Repair => self.to_repair.push(action),
+            SyncActionType::Remove => self.to_remove.push(action),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.to_add.is_empty()
+        self.to_collect.is_empty()
+            && self.conflicts.is_empty()
+            && self.to_add.is_empty()
             && self.to_update.is_empty()
             && self.to_remove.is_empty()
             && self.to_repair.is_empty()
@@ -121,13 +219,31 @@ impl Default for SyncPlan {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncAction {
     /// Skill ID
-    pub skill_id: String,
+    pub skill_id: SkillId,
     /// Target tool
     pub tool: ToolType,
     /// Action type
     pub action: SyncActionType,
     /// Strategy to use
     pub strategy: SyncStrategy,
+    /// Projection path in the tool's directory
+    #[serde(default)]
+    pub target_path: PathBuf,
+    /// Whether an entry SkillsHub did not create sits at the target path and
+    /// would be moved to a backup
+    #[serde(default)]
+    pub overwrites: bool,
+}
+
+/// A skill that would be collected from tools into the hub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectAction {
+    /// Skill the copies would be collected as
+    pub skill_id: SkillId,
+    /// Tools holding (identical) copies
+    pub tools: Vec<ToolType>,
+    /// Copy that would be imported
+    pub source_path: PathBuf,
 }
 
 /// Type of sync action
@@ -143,8 +259,9 @@ pub enum SyncActionType {
 /// A skill scanned from a tool's skills directory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScannedSkill {
-    /// Skill ID (directory name)
-    pub id: String,
+    /// Hub skill projected under this directory name, or the name decoded
+    /// as an ID if no hub skill is
+    pub id: SkillId,
     /// Full path to the skill
     pub path: PathBuf,
     /// Which tool this skill was found in
@@ -159,11 +276,79 @@ pub struct ScannedSkill {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HubSyncStatus {
     /// Skill ID
-    pub skill_id: String,
+    pub skill_id: SkillId,
     /// Path in SkillsHub repository
     pub hub_path: PathBuf,
     /// Tools that have this skill
     pub synced_to: Vec<ToolType>,
     /// Tools missing this skill
     pub missing_in: Vec<ToolType>,
+    /// Whether the skill is disabled (and so not expected in any tool)
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// Copies of a skill found in several tools whose contents differ
+///
+/// Nothing is collected into the hub for the skill until the conflict is
+/// resolved with a [`ConflictResolution`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectConflict {
+    /// Skill the copies would be collected as
+    pub skill_id: SkillId,
+    /// Distinct contents, the one found in the most tools first
+    pub variants: Vec<CollectVariant>,
+}
+
+/// One distinct content among the copies of a conflicting skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectVariant {
+    /// Content hash shared by the copies
+    pub content_hash: String,
+    /// Tools holding this content
+    pub tools: Vec<ToolType>,
+    /// Where each of those tools holds it, in the same order
+    pub paths: Vec<PathBuf>,
+    /// Differences from the first variant (empty for the first one)
+    pub diffs: Vec<FileDiff>,
+}
+
+/// How a file differs between two copies of a skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Path relative to the skill directory
+    pub path: PathBuf,
+    pub change: FileChange,
+    /// Unified diff of the lines, if both sides are text
+    pub diff: Option<String>,
+}
+
+/// Kind of change to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl std::fmt::Display for FileChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileChange::Added => write!(f, "added"),
+            FileChange::Removed => write!(f, "removed"),
+            FileChange::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// How to settle a [`CollectConflict`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ConflictResolution {
+    /// Collect the copy found in this tool; the other tools get it too
+    UseTool { tool: ToolType },
+    /// Collect every variant, the first under the skill's ID and the others
+    /// as `<id>-<tool>`
+    KeepBoth,
 }
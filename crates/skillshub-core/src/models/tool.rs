@@ -57,6 +57,42 @@ impl ToolType {
         ]
     }
 
+    /// Look up a built-in tool by its lowercase key (e.g. "claude", "kilocode")
+    pub fn from_key(key: &str) -> Option<ToolType> {
+        Self::all_builtin()
+            .into_iter()
+            .find(|t| t.key().eq_ignore_ascii_case(key.trim()))
+    }
+
+    /// Lowercase key naming the tool in config files and on the command line
+    ///
+    /// The inverse of [`Self::from_key`], and the same as its serde name.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ToolType::Amp => "amp",
+            ToolType::Antigravity => "antigravity",
+            ToolType::Claude => "claude",
+            ToolType::Cline => "cline",
+            ToolType::Codex => "codex",
+            ToolType::Cursor => "cursor",
+            ToolType::CodeBuddy => "codebuddy",
+            ToolType::Factory => "factory",
+            ToolType::Gemini => "gemini",
+            ToolType::Copilot => "copilot",
+            ToolType::Goose => "goose",
+            ToolType::KiloCode => "kilocode",
+            ToolType::Kimi => "kimi",
+            ToolType::Kiro => "kiro",
+            ToolType::OpenCode => "opencode",
+            ToolType::OpenClaw => "openclaw",
+            ToolType::Qwen => "qwen",
+            ToolType::RooCode => "roocode",
+            ToolType::Trae => "trae",
+            ToolType::Windsurf => "windsurf",
+            ToolType::Custom => "custom",
+        }
+    }
+
     /// Get the default global skills directory for this tool
     pub fn default_skills_dir(&self) -> Option<PathBuf> {
         let home = dirs::home_dir()?;
@@ -159,6 +195,9 @@ pub struct ToolProfile {
     pub custom_project_path: Option<String>,
     /// Sync strategy for this tool
     pub sync_strategy: SyncStrategy,
+    /// Layout skills are projected in
+    #[serde(default)]
+    pub render_format: RenderFormat,
     /// Whether the tool was detected on the system
     pub detected: bool,
     /// Custom tool name (only for Custom type)
@@ -174,6 +213,7 @@ impl ToolProfile {
             custom_global_path: None,
             custom_project_path: None,
             sync_strategy: SyncStrategy::Auto,
+            render_format: RenderFormat::SkillFolder,
             detected: false,
             custom_name: None,
         }
@@ -191,6 +231,7 @@ impl ToolProfile {
             custom_global_path: global_path,
             custom_project_path: project_path,
             sync_strategy: SyncStrategy::Auto,
+            render_format: RenderFormat::SkillFolder,
             detected: true,
             custom_name: Some(name),
         }
@@ -227,20 +268,105 @@ pub enum SyncStrategy {
     Auto,
     /// Always use symbolic links
     Link,
+    /// Symbolic links with a path relative to the tool directory, which keep
+    /// working when the home directory is moved or mounted elsewhere
+    #[serde(rename = "relative-link")]
+    RelativeLink,
+    /// Mirror the directory tree with hard-linked files, for tools that
+    /// ignore symlinked directories (copies across file systems)
+    Hardlink,
     /// Always copy files
     Copy,
 }
 
+impl SyncStrategy {
+    /// Whether projections made with this strategy are real directories
+    /// holding their own files, which only change when rewritten
+    pub fn is_mirror(self) -> bool {
+        matches!(self, SyncStrategy::Hardlink | SyncStrategy::Copy)
+    }
+}
+
 impl std::fmt::Display for SyncStrategy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SyncStrategy::Auto => write!(f, "auto"),
             SyncStrategy::Link => write!(f, "link"),
+            SyncStrategy::RelativeLink => write!(f, "relative-link"),
+            SyncStrategy::Hardlink => write!(f, "hardlink"),
             SyncStrategy::Copy => write!(f, "copy"),
         }
     }
 }
 
+/// Layout a skill is projected into a tool's directory in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RenderFormat {
+    /// The skill folder with its `SKILL.md`, as stored in the hub
+    #[default]
+    SkillFolder,
+    /// Cursor rule (`.cursor/rules/<name>.mdc`)
+    CursorRule,
+    /// GitHub Copilot instructions (`.github/instructions/<name>.instructions.md`)
+    CopilotInstructions,
+    /// Windsurf rule (`.windsurf/rules/<name>.md`)
+    WindsurfRule,
+    /// Kiro steering file (`.kiro/steering/<name>.md`)
+    KiroSteering,
+}
+
+impl RenderFormat {
+    /// Rule format a tool reads natively besides skill folders
+    pub fn native_for(tool: ToolType) -> Option<Self> {
+        match tool {
+            ToolType::Cursor => Some(RenderFormat::CursorRule),
+            ToolType::Copilot => Some(RenderFormat::CopilotInstructions),
+            ToolType::Windsurf => Some(RenderFormat::WindsurfRule),
+            ToolType::Kiro => Some(RenderFormat::KiroSteering),
+            _ => None,
+        }
+    }
+
+    /// Whether `tool` can read skills in this format
+    pub fn supported_by(self, tool: ToolType) -> bool {
+        self == RenderFormat::SkillFolder || Self::native_for(tool) == Some(self)
+    }
+
+    /// Directory beside the tool's skills directory that rendered files go
+    /// to, e.g. `.cursor/rules` for `.cursor/skills`; `None` for skill folders
+    pub fn dir_name(self) -> Option<&'static str> {
+        match self {
+            RenderFormat::SkillFolder => None,
+            RenderFormat::CursorRule | RenderFormat::WindsurfRule => Some("rules"),
+            RenderFormat::CopilotInstructions => Some("instructions"),
+            RenderFormat::KiroSteering => Some("steering"),
+        }
+    }
+
+    /// File or folder name of a skill projected under `name`
+    pub fn file_name(self, name: &str) -> String {
+        match self {
+            RenderFormat::SkillFolder => name.to_string(),
+            RenderFormat::CursorRule => format!("{}.mdc", name),
+            RenderFormat::CopilotInstructions => format!("{}.instructions.md", name),
+            RenderFormat::WindsurfRule | RenderFormat::KiroSteering => format!("{}.md", name),
+        }
+    }
+}
+
+impl std::fmt::Display for RenderFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderFormat::SkillFolder => write!(f, "skill-folder"),
+            RenderFormat::CursorRule => write!(f, "cursor-rule"),
+            RenderFormat::CopilotInstructions => write!(f, "copilot-instructions"),
+            RenderFormat::WindsurfRule => write!(f, "windsurf-rule"),
+            RenderFormat::KiroSteering => write!(f, "kiro-steering"),
+        }
+    }
+}
+
 /// Result of a projection operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectionResult {
@@ -255,3 +381,21 @@ pub struct ProjectionResult {
     /// Error message if failed
     pub error: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_keys_round_trip() {
+        for tool in ToolType::all_builtin() {
+            assert_eq!(ToolType::from_key(tool.key()), Some(tool));
+            assert_eq!(
+                serde_json::to_string(&tool).unwrap(),
+                format!("\"{}\"", tool.key())
+            );
+        }
+        assert_eq!(ToolType::from_key(" KiloCode "), Some(ToolType::KiloCode));
+        assert_eq!(ToolType::from_key("custom"), None);
+    }
+}
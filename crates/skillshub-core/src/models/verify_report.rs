@@ -0,0 +1,73 @@
+//! Store integrity report models
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::SkillId;
+
+/// Kind of integrity problem found in the store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueType {
+    /// Files no longer hash to the recorded content hash
+    Modified,
+    /// The record exists but its files are gone
+    MissingFiles,
+    /// Files exist in the store without a record
+    Orphaned,
+    /// The record exists but cannot be parsed (e.g. written by a newer version)
+    UnreadableRecord,
+}
+
+impl std::fmt::Display for IntegrityIssueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityIssueType::Modified => write!(f, "modified"),
+            IntegrityIssueType::MissingFiles => write!(f, "missing files"),
+            IntegrityIssueType::Orphaned => write!(f, "orphaned"),
+            IntegrityIssueType::UnreadableRecord => write!(f, "unreadable record"),
+        }
+    }
+}
+
+/// A single integrity problem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    /// Skill ID (decoded from the directory name for orphans)
+    pub skill_id: SkillId,
+    /// Issue type
+    pub issue_type: IntegrityIssueType,
+    /// Store path the issue was found at
+    pub path: PathBuf,
+    /// Content hash from the install record
+    pub expected_hash: Option<String>,
+    /// Content hash of the files on disk
+    pub actual_hash: Option<String>,
+    /// Whether a repair fixed the issue
+    #[serde(default)]
+    pub repaired: bool,
+    /// Why a repair attempt failed
+    #[serde(default)]
+    pub repair_error: Option<String>,
+}
+
+/// Result of verifying the local store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyReport {
+    /// Number of install records checked
+    pub checked: usize,
+    /// Problems found
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl VerifyReport {
+    /// Whether the store is consistent
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues that are still present (not repaired)
+    pub fn unresolved(&self) -> impl Iterator<Item = &IntegrityIssue> {
+        self.issues.iter().filter(|issue| !issue.repaired)
+    }
+}
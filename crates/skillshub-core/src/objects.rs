@@ -0,0 +1,285 @@
+//! Object store - content-addressed files shared by every stored skill
+//!
+//! Each distinct file is kept once under `objects/<aa>/<rest>`, keyed by the
+//! SHA-256 of its content. A stored skill version is described by a
+//! [`SkillTree`] mapping relative paths to objects and is materialised by
+//! hardlinking the objects into its directory, falling back to copies where
+//! hardlinks are not available. On Unix objects are read-only, so editing a
+//! stored file through a link fails instead of changing every skill that
+//! shares it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::error::Result;
+use crate::hash::{self, hash_bytes, mtime_ns, relative_key, DirManifest, FileDigest};
+use crate::store::write_atomic;
+
+/// A file of a skill tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeEntry {
+    /// Content fingerprint; its hash is the object key
+    #[serde(flatten)]
+    pub file: FileDigest,
+    /// Modification time of the materialised file, in nanoseconds since the epoch
+    #[serde(default)]
+    pub mtime_ns: u64,
+}
+
+/// The files of one stored skill version
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillTree {
+    /// Root digest of the whole skill (see [`crate::hash::manifest`])
+    pub content_hash: String,
+    /// Files keyed by `/`-separated relative path
+    pub files: BTreeMap<String, TreeEntry>,
+}
+
+impl SkillTree {
+    /// Load a tree manifest
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write a tree manifest atomically
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(path, content.as_bytes())
+    }
+
+    /// Record the modification times of the files materialised in `dir`
+    pub fn stamp(&mut self, dir: &Path) {
+        for (relative, entry) in &mut self.files {
+            if let Ok(metadata) = fs::metadata(dir.join(relative)) {
+                entry.mtime_ns = mtime_ns(&metadata);
+            }
+        }
+    }
+
+    /// Whether `dir` still holds exactly these files, judged by size and
+    /// modification time without reading any content
+    pub fn is_unchanged(&self, dir: &Path) -> bool {
+        let mut seen = 0;
+        for entry in WalkDir::new(dir) {
+            let Ok(entry) = entry else {
+                return false;
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                return false;
+            };
+            let Some(expected) = self.files.get(&relative_key(dir, entry.path())) else {
+                return false;
+            };
+            if expected.file.size != metadata.len() || expected.mtime_ns != mtime_ns(&metadata) {
+                return false;
+            }
+            seen += 1;
+        }
+        seen == self.files.len()
+    }
+
+    /// The tree as a directory manifest
+    pub fn manifest(&self) -> DirManifest {
+        DirManifest::from_files(
+            self.files
+                .iter()
+                .map(|(path, entry)| (path.clone(), entry.file.clone()))
+                .collect(),
+        )
+    }
+}
+
+/// Content-addressed object directory
+pub struct ObjectStore {
+    root: PathBuf,
+}
+
+impl ObjectStore {
+    /// Open an object directory (created on first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Path of the object with the given content hash
+    pub fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[..2]).join(&hash[2..])
+    }
+
+    /// Store every file below `src` (or `src` itself when it is a file)
+    ///
+    /// The returned tree's `content_hash` is the digest of `src`, which the
+    /// materialised directory reproduces.
+    pub fn ingest(&self, src: &Path) -> Result<SkillTree> {
+        let mut tree = SkillTree::default();
+
+        // Symlinked files are stored as regular files, like a copy would
+        for (relative, path, metadata) in hash::walk_files(src)? {
+            let content = fs::read(&path)?;
+            let file = FileDigest::of(&content, &metadata);
+            self.store_object(&content, &file)?;
+            tree.files.insert(relative, TreeEntry { file, mtime_ns: 0 });
+        }
+
+        tree.content_hash = tree.manifest().digest;
+        Ok(tree)
+    }
+
+    /// Materialise a tree into `dest` and record the resulting modification times
+    pub fn materialize(&self, tree: &mut SkillTree, dest: &Path) -> Result<()> {
+        fs::create_dir_all(dest)?;
+        for (relative, entry) in &tree.files {
+            let target = dest.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let executable = entry.file.is_executable();
+            let object = self.object_path(&entry.file.hash);
+            let same_mode = fs::metadata(&object)
+                .map(|m| is_executable(&m) == executable)
+                .unwrap_or(false);
+            if !(same_mode && fs::hard_link(&object, &target).is_ok()) {
+                fs::copy(&object, &target)?;
+                set_mode(&target, executable)?;
+            }
+        }
+        tree.stamp(dest);
+        Ok(())
+    }
+
+    /// Whether every object of a tree is present with the expected content
+    pub fn is_intact(&self, tree: &SkillTree) -> bool {
+        tree.files.values().all(|entry| {
+            fs::read(self.object_path(&entry.file.hash))
+                .map(|content| hash_bytes(&content) == entry.file.hash)
+                .unwrap_or(false)
+        })
+    }
+
+    /// All stored objects as `(hash, path)`
+    pub fn list(&self) -> Vec<(String, PathBuf)> {
+        let mut objects = Vec::new();
+        let Ok(prefixes) = fs::read_dir(&self.root) else {
+            return objects;
+        };
+        for prefix in prefixes.flatten() {
+            let prefix_name = prefix.file_name().to_string_lossy().to_string();
+            let Ok(files) = fs::read_dir(prefix.path()) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let name = file.file_name().to_string_lossy().to_string();
+                if !name.starts_with('.') {
+                    objects.push((format!("{}{}", prefix_name, name), file.path()));
+                }
+            }
+        }
+        objects.sort();
+        objects
+    }
+
+    /// Store one file's content under its hash
+    ///
+    /// An existing object is reused when its content still matches; a damaged
+    /// one is replaced by a fresh file (links to the old file keep it).
+    fn store_object(&self, content: &[u8], file: &FileDigest) -> Result<()> {
+        let hash = &file.hash;
+        let path = self.object_path(hash);
+
+        if let Ok(existing) = fs::read(&path) {
+            if &hash_bytes(&existing) == hash {
+                return Ok(());
+            }
+        }
+
+        // Written under a unique name and renamed, so readers never see a partial object
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let tmp = self.root.join(format!(
+            ".{}-{}-{}.tmp",
+            &hash[..16],
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&self.root)?;
+        fs::write(&tmp, content)?;
+        set_mode(&tmp, file.is_executable())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// Make a stored file read-only, keeping it executable if needed
+#[cfg(unix)]
+fn set_mode(path: &Path, executable: bool) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = if executable { 0o555 } else { 0o444 };
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+/// Read-only files cannot be deleted on Windows, so permissions are left alone
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _executable: bool) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_files_share_one_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let objects = ObjectStore::new(dir.path().join("objects"));
+        for skill in ["one", "two"] {
+            let src = dir.path().join("src").join(skill);
+            fs::create_dir_all(src.join("scripts")).unwrap();
+            fs::write(src.join("SKILL.md"), skill).unwrap();
+            fs::write(src.join("scripts").join("helper.sh"), "echo shared").unwrap();
+        }
+
+        let mut one = objects.ingest(&dir.path().join("src/one")).unwrap();
+        let two = objects.ingest(&dir.path().join("src/two")).unwrap();
+        assert_eq!(
+            one.files["scripts/helper.sh"].file.hash,
+            two.files["scripts/helper.sh"].file.hash
+        );
+        assert_eq!(objects.list().len(), 3);
+
+        let dest = dir.path().join("materialized");
+        objects.materialize(&mut one, &dest).unwrap();
+        assert_eq!(
+            fs::read_to_string(dest.join("scripts/helper.sh")).unwrap(),
+            "echo shared"
+        );
+        assert!(one.is_unchanged(&dest));
+        assert_eq!(hash::digest(&dest).unwrap(), one.content_hash);
+        assert!(objects.is_intact(&one));
+
+        fs::write(dest.join("extra.md"), "new").unwrap();
+        assert!(!one.is_unchanged(&dest));
+    }
+}
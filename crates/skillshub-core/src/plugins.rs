@@ -13,8 +13,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
-use crate::models::{Skill, SkillSource, SkillVersion};
-use crate::registry::calculate_dir_hash;
+use crate::hash;
+use crate::models::{Skill, SkillId, SkillSource, SkillVersion};
 
 /// Source of a marketplace (GitHub or Git URL)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -133,12 +133,14 @@ pub struct PluginSkill {
 }
 
 impl PluginSkill {
-    /// Get a unique identifier for this plugin skill
-    pub fn id(&self) -> String {
-        format!(
+    /// Get a unique identifier for this plugin skill (`plugin@marketplace:skill`)
+    ///
+    /// Fails when the plugin metadata yields an unsafe ID (e.g. a name with `/`).
+    pub fn id(&self) -> Result<SkillId> {
+        SkillId::new(format!(
             "{}@{}:{}",
             self.plugin_name, self.marketplace, self.skill_name
-        )
+        ))
     }
 
     /// Convert to a Skill model
@@ -155,10 +157,10 @@ impl PluginSkill {
         let content = fs::read_to_string(&skill_md_path)?;
         let (name, description) = parse_skill_md_header(&content);
 
-        let content_hash = calculate_dir_hash(&self.skill_path).unwrap_or_default();
+        let content_hash = hash::digest(&self.skill_path).unwrap_or_default();
 
         Ok(Skill {
-            id: self.id(),
+            id: self.id()?,
             name: name.unwrap_or_else(|| self.skill_name.clone()),
             description: description.unwrap_or_default(),
             author: None,
@@ -461,6 +463,9 @@ This is a skill.
             commit_sha: None,
             installed_at: "2026-01-01T00:00:00Z".to_string(),
         };
-        assert_eq!(skill.id(), "document-skills@anthropic-agent-skills:pdf");
+        assert_eq!(
+            skill.id().unwrap(),
+            "document-skills@anthropic-agent-skills:pdf"
+        );
     }
 }
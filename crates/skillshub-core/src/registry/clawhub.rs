@@ -5,8 +5,9 @@ use std::path::PathBuf;
 use tokio::fs;
 
 use crate::error::{Error, Result};
-use crate::models::{Skill, SkillSource, SkillVersion};
-use crate::registry::{calculate_dir_hash, RegistryProvider, SkillListing, SkillQuery};
+use crate::hash;
+use crate::models::{Skill, SkillId, SkillSource, SkillVersion};
+use crate::registry::{RegistryProvider, SkillListing, SkillQuery};
 
 /// ClawHub registry (API-based)
 pub struct ClawHubRegistry {
@@ -111,9 +112,11 @@ impl RegistryProvider for ClawHubRegistry {
         let listings = data
             .items
             .into_iter()
-            .map(|item| {
+            // Slugs that are not valid skill IDs cannot be installed
+            .filter_map(|item| Some((SkillId::new(item.slug.clone()).ok()?, item)))
+            .map(|(id, item)| {
                 SkillListing {
-                    id: item.slug.clone(),
+                    id,
                     name: item.display_name,
                     description: item.summary,
                     author: None, // ClawHub API doesn't seem to expose author directly in the list view easily, or it's part of slug
@@ -132,13 +135,13 @@ impl RegistryProvider for ClawHubRegistry {
         Ok(listings)
     }
 
-    async fn get_skill(&self, skill_id: &str) -> Result<Skill> {
+    async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill> {
         let cache_dir = dirs::data_local_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("skillshub")
             .join("cache")
             .join("clawhub")
-            .join(skill_id);
+            .join(skill_id.file_name());
 
         let skill_md_path = cache_dir.join("SKILL.md");
 
@@ -151,10 +154,10 @@ impl RegistryProvider for ClawHubRegistry {
         }
 
         let metadata = crate::registry::parse_skill_md(&skill_md_path)?;
-        let content_hash = calculate_dir_hash(&cache_dir)?;
+        let content_hash = hash::digest(&cache_dir)?;
 
         Ok(Skill {
-            id: skill_id.to_string(),
+            id: skill_id.clone(),
             name: metadata.name.unwrap_or_else(|| skill_id.to_string()),
             description: metadata.description.unwrap_or_default(),
             author: metadata.author,
@@ -174,14 +177,16 @@ impl RegistryProvider for ClawHubRegistry {
         })
     }
 
-    async fn fetch(&self, skill_id: &str, dest: &std::path::Path) -> Result<PathBuf> {
+    async fn fetch(&self, skill_id: &SkillId, dest: &std::path::Path) -> Result<PathBuf> {
         if dest.exists() {
             fs::remove_dir_all(dest).await.map_err(Error::Io)?;
         }
         fs::create_dir_all(dest).await.map_err(Error::Io)?;
 
-        let download_url = format!("{}/download?slug={}", self.api_url, skill_id);
-        let zip_path = dest.join(format!("{}.zip", skill_id));
+        let slug: String =
+            url::form_urlencoded::byte_serialize(skill_id.as_str().as_bytes()).collect();
+        let download_url = format!("{}/download?slug={}", self.api_url, slug);
+        let zip_path = dest.join(format!("{}.zip", skill_id.file_name()));
 
         self.download_file(&download_url, &zip_path).await?;
 
@@ -205,7 +210,7 @@ impl RegistryProvider for ClawHubRegistry {
         Ok(dest.to_path_buf())
     }
 
-    async fn versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>> {
+    async fn versions(&self, skill_id: &SkillId) -> Result<Vec<SkillVersion>> {
         let skill = self.get_skill(skill_id).await?;
         Ok(vec![skill.version])
     }
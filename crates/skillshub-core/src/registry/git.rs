@@ -1,49 +1,141 @@
 use async_trait::async_trait;
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
 use tokio::fs;
 use walkdir::WalkDir;
 
 use crate::error::{Error, Result};
-use crate::models::{Skill, SkillSource, SkillVersion};
-use crate::registry::{
-    calculate_dir_hash, parse_skill_md, RegistryProvider, SkillListing, SkillQuery,
-};
+use crate::hash;
+use crate::models::{Skill, SkillId, SkillSource, SkillVersion};
+use crate::registry::{parse_skill_md, RegistryProvider, SkillListing, SkillQuery};
 
 /// Git-based registry (scans a remote repository)
 pub struct GitRegistry {
     name: String,
     url: String,
     branch: Option<String>,
+    /// Commit to check out instead of the branch head
+    commit: Option<String>,
     cache_dir: PathBuf,
 }
 
 impl GitRegistry {
     pub fn new(name: impl Into<String>, url: impl Into<String>, branch: Option<String>) -> Self {
-        // Calculate a stable cache path for this repo
         let url_str = url.into();
-        let mut hasher = Sha256::new();
-        hasher.update(url_str.as_bytes());
-        let hash = hex::encode(hasher.finalize());
-
-        let cache_dir = dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("skillshub")
-            .join("cache")
-            .join("git")
-            .join(&hash[0..12]);
+        let cache_dir = Self::cache_root().join(Self::cache_name(&url_str, None));
 
         Self {
             name: name.into(),
             url: url_str,
             branch,
+            commit: None,
             cache_dir,
         }
     }
 
+    /// Create a registry pinned to an exact commit
+    ///
+    /// Pinned checkouts live in their own cache directory and are never
+    /// pulled, so the content stays reproducible. The commit must be a hex
+    /// object ID (see [`validate_commit`]).
+    pub fn pinned(
+        name: impl Into<String>,
+        url: impl Into<String>,
+        branch: Option<String>,
+        commit: impl Into<String>,
+    ) -> Result<Self> {
+        let mut registry = Self::new(name, url, branch);
+        let commit = commit.into();
+        validate_commit(&commit)?;
+        registry.cache_dir =
+            Self::cache_root().join(Self::cache_name(&registry.url, Some(&commit)));
+        registry.commit = Some(commit);
+        Ok(registry)
+    }
+
+    /// Directory holding the clones of every git registry
+    pub fn cache_root() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("skillshub")
+            .join("cache")
+            .join("git")
+    }
+
+    /// Stable cache directory name for a repository (and pinned commit)
+    pub fn cache_name(url: &str, commit: Option<&str>) -> String {
+        let hash = hex::encode(Sha256::digest(url.as_bytes()));
+        match commit {
+            Some(commit) => format!("{}@{}", &hash[0..12], commit),
+            None => hash[0..12].to_string(),
+        }
+    }
+
+    /// Commit currently checked out in the cache
+    pub async fn head_commit(&self) -> Result<String> {
+        let output = git(&self.cache_dir, &["rev-parse", "HEAD"]).await?;
+        if !output.status.success() {
+            return Err(Error::System(format!(
+                "Git rev-parse failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Check out the pinned commit into a fresh cache directory
+    async fn checkout_commit(&self, commit: &str) -> Result<()> {
+        if self.cache_dir.join(".git").exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.cache_dir)
+            .await
+            .map_err(Error::Io)?;
+
+        let result = async {
+            run_git(&self.cache_dir, &["init", "-q"]).await?;
+            run_git(&self.cache_dir, &["remote", "add", "origin", &self.url]).await?;
+            // Shallow fetch of a single commit; servers that refuse unadvertised
+            // objects need the full history instead
+            if run_git(
+                &self.cache_dir,
+                &[
+                    "fetch",
+                    "--depth",
+                    "1",
+                    "--end-of-options",
+                    "origin",
+                    commit,
+                ],
+            )
+            .await
+            .is_err()
+            {
+                run_git(&self.cache_dir, &["fetch", "origin"]).await?;
+            }
+            // `switch` rather than `checkout`, which rejects `--end-of-options`
+            run_git(
+                &self.cache_dir,
+                &["switch", "-q", "--detach", "--end-of-options", commit],
+            )
+            .await
+        }
+        .await;
+
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&self.cache_dir).await;
+        }
+        result
+    }
+
     /// Ensure the repository is cloned and up to date
     async fn sync_repo(&self) -> Result<()> {
+        if let Some(commit) = &self.commit {
+            return self.checkout_commit(commit).await;
+        }
+
         if !self.cache_dir.exists() {
             fs::create_dir_all(&self.cache_dir)
                 .await
@@ -115,10 +207,12 @@ impl RegistryProvider for GitRegistry {
 
                 if entry.file_name() == "SKILL.md" {
                     let skill_path = entry.path().parent().unwrap();
-                    let id = skill_path
+                    let Some(id) = skill_path
                         .file_name()
-                        .map(|s| s.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "unknown".to_string());
+                        .and_then(|s| SkillId::new(s.to_string_lossy()).ok())
+                    else {
+                        continue;
+                    };
 
                     // Parse metadata
                     let metadata = match parse_skill_md(&entry.path().to_path_buf()) {
@@ -138,7 +232,7 @@ impl RegistryProvider for GitRegistry {
                         source: SkillSource::Git {
                             url: repo_url.clone(),
                             branch: repo_branch.clone(),
-                            path: Some(id),
+                            path: Some(id.to_string()),
                         },
                     });
                 }
@@ -153,7 +247,7 @@ impl RegistryProvider for GitRegistry {
             let matches = query
                 .query
                 .as_ref()
-                .is_none_or(|q| listing.id.contains(q) || listing.name.contains(q));
+                .is_none_or(|q| listing.id.as_str().contains(q) || listing.name.contains(q));
 
             if matches {
                 results.push(listing);
@@ -163,11 +257,12 @@ impl RegistryProvider for GitRegistry {
         Ok(results)
     }
 
-    async fn get_skill(&self, skill_id: &str) -> Result<Skill> {
+    async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill> {
         // Determine path within cache
         // We need to find where this skill_id is located.
-        // For simplicity, we assume skill_id == directory name
-        let skill_path = self.cache_dir.join(skill_id);
+        // For simplicity, we assume skill_id == directory name; validated IDs
+        // are a single path component, so this stays inside the checkout
+        let skill_path = self.cache_dir.join(skill_id.as_str());
         let skill_md_path = skill_path.join("SKILL.md");
 
         if !skill_md_path.exists() {
@@ -179,19 +274,21 @@ impl RegistryProvider for GitRegistry {
         }
 
         let metadata = parse_skill_md(&skill_md_path)?;
-        let content_hash = calculate_dir_hash(&skill_path)?;
+        let content_hash = hash::digest(&skill_path)?;
+        let mut version = SkillVersion::new(
+            metadata.version.unwrap_or_else(|| "0.0.0".to_string()),
+            content_hash,
+        );
+        version.commit = self.head_commit().await.ok();
 
         Ok(Skill {
-            id: skill_id.to_string(),
+            id: skill_id.clone(),
             name: metadata.name.unwrap_or_else(|| skill_id.to_string()),
             description: metadata.description.unwrap_or_default(),
             author: metadata.author,
             tags: metadata.tags,
             compatible_tools: metadata.compatible_tools,
-            version: SkillVersion::new(
-                metadata.version.unwrap_or_else(|| "0.0.0".to_string()),
-                content_hash,
-            ),
+            version,
             source: SkillSource::Git {
                 url: self.url.clone(),
                 branch: self.branch.clone(),
@@ -203,8 +300,11 @@ impl RegistryProvider for GitRegistry {
         })
     }
 
-    async fn fetch(&self, skill_id: &str, dest: &std::path::Path) -> Result<PathBuf> {
-        let skill_path = self.cache_dir.join(skill_id);
+    async fn fetch(&self, skill_id: &SkillId, dest: &std::path::Path) -> Result<PathBuf> {
+        let skill_path = self.cache_dir.join(skill_id.as_str());
+        if !skill_path.exists() && self.commit.is_some() {
+            self.sync_repo().await?;
+        }
         if !skill_path.exists() {
             return Err(Error::SkillNotFound(skill_id.to_string()));
         }
@@ -238,8 +338,45 @@ impl RegistryProvider for GitRegistry {
         Ok(dest.to_path_buf())
     }
 
-    async fn versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>> {
+    async fn versions(&self, skill_id: &SkillId) -> Result<Vec<SkillVersion>> {
         let skill = self.get_skill(skill_id).await?;
         Ok(vec![skill.version])
     }
 }
+
+/// Check that `commit` is a 7 to 64 character hex object ID
+///
+/// Commits come from lockfiles, which may be untrusted; anything else could
+/// escape the cache directory or be read by git as an option.
+pub fn validate_commit(commit: &str) -> Result<()> {
+    if (7..=64).contains(&commit.len()) && commit.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Ok(());
+    }
+    Err(Error::RegistryError(format!(
+        "{:?} is not a git commit id",
+        commit
+    )))
+}
+
+/// Run a git command in `dir` and capture its output
+async fn git(dir: &Path, args: &[&str]) -> Result<Output> {
+    let dir = dir.to_path_buf();
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    tokio::task::spawn_blocking(move || Command::new("git").current_dir(dir).args(args).output())
+        .await
+        .map_err(|e| Error::System(format!("Failed to join git task: {}", e)))?
+        .map_err(|e| Error::System(format!("Failed to execute git: {}", e)))
+}
+
+/// Run a git command in `dir`, failing on a non-zero exit status
+async fn run_git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = git(dir, args).await?;
+    if !output.status.success() {
+        return Err(Error::System(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(())
+}
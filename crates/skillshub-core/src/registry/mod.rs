@@ -8,7 +8,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
-use crate::models::{Skill, SkillMetadata, SkillSource, SkillVersion};
+use crate::models::{Skill, SkillId, SkillMetadata, SkillSource, SkillVersion};
+use crate::schema::{self, Versioned};
 
 pub mod clawhub;
 pub mod git;
@@ -53,7 +54,7 @@ pub enum RegistryType {
 /// A skill listing from the registry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillListing {
-    pub id: String,
+    pub id: SkillId,
     pub name: String,
     pub description: String,
     pub author: Option<String>,
@@ -85,13 +86,28 @@ pub trait RegistryProvider: Send + Sync {
     async fn search(&self, query: &SkillQuery) -> Result<Vec<SkillListing>>;
 
     /// Get skill details
-    async fn get_skill(&self, skill_id: &str) -> Result<Skill>;
+    async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill>;
 
     /// Fetch skill to a local path
-    async fn fetch(&self, skill_id: &str, dest: &std::path::Path) -> Result<PathBuf>;
+    async fn fetch(&self, skill_id: &SkillId, dest: &std::path::Path) -> Result<PathBuf>;
 
     /// Get available versions
-    async fn versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>>;
+    async fn versions(&self, skill_id: &SkillId) -> Result<Vec<SkillVersion>>;
+}
+
+/// Contents of `registries.json`
+#[derive(Serialize, Deserialize)]
+struct RegistriesFile {
+    registries: Vec<RegistryConfig>,
+}
+
+/// v0 was a bare list of registries
+impl Versioned for RegistriesFile {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(_from: u32, document: serde_json::Value) -> Result<serde_json::Value> {
+        schema::wrap_list(document, "registries")
+    }
 }
 
 /// Manager for multiple registries
@@ -159,17 +175,30 @@ impl RegistryManager {
         }
     }
 
+    /// Build an aggregated registry from all enabled registries
+    pub fn aggregated(&self) -> AggregatedRegistry {
+        let mut aggregated = AggregatedRegistry::new();
+        for config in self.configs.iter().filter(|c| c.enabled) {
+            if let Some(provider) = self.get_provider(&config.name) {
+                aggregated.add_registry(provider);
+            }
+        }
+        aggregated
+    }
+
+    /// Read the registry list; an unreadable file is an error, not a reset to the defaults
     fn load(&mut self) -> Result<()> {
-        let content = fs::read_to_string(&self.config_path)?;
-        self.configs =
-            serde_json::from_str(&content).unwrap_or_else(|_| Self::default_registries());
+        if let Some(file) = schema::load::<RegistriesFile>(&self.config_path)? {
+            self.configs = file.registries;
+        }
         Ok(())
     }
 
     fn save(&self) -> Result<()> {
-        let content = serde_json::to_string_pretty(&self.configs)?;
-        fs::write(&self.config_path, content)?;
-        Ok(())
+        let file = RegistriesFile {
+            registries: self.configs.clone(),
+        };
+        schema::save(&self.config_path, &file)
     }
 
     fn default_registries() -> Vec<RegistryConfig> {
@@ -241,13 +270,26 @@ impl AggregatedRegistry {
     }
 
     /// Search all registries concurrently and merge results
+    ///
+    /// Listing IDs are namespaced by the registry they come from.
     pub async fn search(&self, query: &SkillQuery) -> Result<Vec<SkillListing>> {
         let mut set = tokio::task::JoinSet::new();
 
         for registry in &self.registries {
             let registry = Arc::clone(registry);
             let query = query.clone();
-            set.spawn(async move { registry.search(&query).await });
+            set.spawn(async move {
+                let listings = registry.search(&query).await?;
+                Ok::<_, crate::error::Error>(
+                    listings
+                        .into_iter()
+                        .filter_map(|mut listing| {
+                            listing.id = SkillId::qualified(registry.name(), &listing.id).ok()?;
+                            Some(listing)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            });
         }
 
         let mut results = Vec::new();
@@ -259,23 +301,72 @@ impl AggregatedRegistry {
         Ok(results)
     }
 
-    pub async fn get_skill(&self, skill_id: &str) -> Result<Skill> {
-        for registry in &self.registries {
-            if let Ok(skill) = registry.get_skill(skill_id).await {
-                return Ok(skill);
-            }
-        }
-        Err(crate::error::Error::SkillNotFound(skill_id.to_string()))
+    /// Get a skill; the returned skill's ID is namespaced by its registry
+    ///
+    /// See [`Self::get_skill_with_source`] for how the ID is resolved.
+    pub async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill> {
+        self.get_skill_with_source(skill_id)
+            .await
+            .map(|(skill, _)| skill)
+    }
+
+    /// Fetch a skill, returning the skill, the fetched path and the name of
+    /// the registry it came from
+    pub async fn fetch_skill(
+        &self,
+        skill_id: &SkillId,
+        dest: &std::path::Path,
+    ) -> Result<(Skill, PathBuf, String)> {
+        let (registry, skill) = self.resolve(skill_id).await?;
+        let path = registry.fetch(&skill.id.unqualified(), dest).await?;
+        Ok((skill, path, registry.name().to_string()))
     }
 
     /// Get a skill along with the name of the registry it was found in
-    pub async fn get_skill_with_source(&self, skill_id: &str) -> Result<(Skill, String)> {
-        for registry in &self.registries {
-            if let Ok(skill) = registry.get_skill(skill_id).await {
-                return Ok((skill, registry.name().to_string()));
+    ///
+    /// An ID namespaced by a configured registry (`anthropics/pdf`) is only
+    /// looked up there. Any other ID is looked up in every registry and must
+    /// be offered by exactly one of them.
+    pub async fn get_skill_with_source(&self, skill_id: &SkillId) -> Result<(Skill, String)> {
+        let (registry, skill) = self.resolve(skill_id).await?;
+        Ok((skill, registry.name().to_string()))
+    }
+
+    async fn resolve(&self, skill_id: &SkillId) -> Result<(Arc<dyn RegistryProvider>, Skill)> {
+        let namespaced = self
+            .registries
+            .iter()
+            .find(|r| skill_id.namespace() == Some(r.name()));
+        let candidates: Vec<(&Arc<dyn RegistryProvider>, SkillId)> = match namespaced {
+            Some(registry) => vec![(registry, skill_id.unqualified())],
+            None => self
+                .registries
+                .iter()
+                .map(|r| (r, skill_id.clone()))
+                .collect(),
+        };
+
+        let mut found = Vec::new();
+        for (registry, id) in candidates {
+            if let Ok(mut skill) = registry.get_skill(&id).await {
+                skill.id = SkillId::qualified(registry.name(), &id)?;
+                found.push((Arc::clone(registry), skill));
             }
         }
-        Err(crate::error::Error::SkillNotFound(skill_id.to_string()))
+
+        match found.len() {
+            0 => Err(crate::error::Error::SkillNotFound(skill_id.to_string())),
+            1 => Ok(found.remove(0)),
+            _ => Err(crate::error::Error::AmbiguousSkill(format!(
+                "{}: offered by {}",
+                skill_id,
+                found
+                    .iter()
+                    .map(|(_, skill)| skill.id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
     }
 }
 
@@ -320,18 +411,21 @@ impl RegistryProvider for LocalRegistry {
             if path.is_dir() {
                 let skill_md = path.join("SKILL.md");
                 if skill_md.exists() {
-                    let id = entry.file_name().to_string_lossy().to_string();
+                    let Ok(id) = SkillId::new(entry.file_name().to_string_lossy()) else {
+                        continue;
+                    };
                     let metadata = parse_skill_md(&skill_md)?;
 
                     // Apply query filter
                     let matches = query.query.as_ref().is_none_or(|q| {
-                        id.contains(q) || metadata.name.as_ref().is_some_and(|n| n.contains(q))
+                        id.as_str().contains(q)
+                            || metadata.name.as_ref().is_some_and(|n| n.contains(q))
                     });
 
                     if matches {
                         results.push(SkillListing {
-                            id: id.clone(),
-                            name: metadata.name.unwrap_or(id),
+                            name: metadata.name.unwrap_or_else(|| id.to_string()),
+                            id,
                             description: metadata.description.unwrap_or_default(),
                             author: metadata.author,
                             tags: metadata.tags,
@@ -348,8 +442,8 @@ impl RegistryProvider for LocalRegistry {
         Ok(results)
     }
 
-    async fn get_skill(&self, skill_id: &str) -> Result<Skill> {
-        let skill_path = self.path.join(skill_id);
+    async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill> {
+        let skill_path = self.path.join(skill_id.as_str());
         let skill_md_path = skill_path.join("SKILL.md");
 
         if !skill_md_path.exists() {
@@ -357,10 +451,10 @@ impl RegistryProvider for LocalRegistry {
         }
 
         let metadata = parse_skill_md(&skill_md_path)?;
-        let content_hash = calculate_dir_hash(&skill_path)?;
+        let content_hash = crate::hash::digest(&skill_path)?;
 
         Ok(Skill {
-            id: skill_id.to_string(),
+            id: skill_id.clone(),
             name: metadata.name.unwrap_or_else(|| skill_id.to_string()),
             description: metadata.description.unwrap_or_default(),
             author: metadata.author,
@@ -379,8 +473,8 @@ impl RegistryProvider for LocalRegistry {
         })
     }
 
-    async fn fetch(&self, skill_id: &str, _dest: &std::path::Path) -> Result<PathBuf> {
-        let skill_path = self.path.join(skill_id);
+    async fn fetch(&self, skill_id: &SkillId, _dest: &std::path::Path) -> Result<PathBuf> {
+        let skill_path = self.path.join(skill_id.as_str());
         if !skill_path.exists() {
             return Err(crate::error::Error::SkillNotFound(skill_id.to_string()));
         }
@@ -389,7 +483,7 @@ impl RegistryProvider for LocalRegistry {
         Ok(skill_path)
     }
 
-    async fn versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>> {
+    async fn versions(&self, skill_id: &SkillId) -> Result<Vec<SkillVersion>> {
         let skill = self.get_skill(skill_id).await?;
         Ok(vec![skill.version])
     }
@@ -425,7 +519,7 @@ impl RegistryProvider for HttpRegistry {
             reqwest::get(self.listings_endpoint()).await?.json().await?;
 
         if let Some(q) = &query.query {
-            results.retain(|item| item.id.contains(q) || item.name.contains(q));
+            results.retain(|item| item.id.as_str().contains(q) || item.name.contains(q));
         }
 
         if !query.tags.is_empty() {
@@ -444,22 +538,20 @@ impl RegistryProvider for HttpRegistry {
         Ok(results)
     }
 
-    async fn get_skill(&self, skill_id: &str) -> Result<Skill> {
-        let url = format!(
-            "{}/skills/{}",
-            self.base_url.trim_end_matches('/'),
-            skill_id
-        );
+    async fn get_skill(&self, skill_id: &SkillId) -> Result<Skill> {
+        let id: String =
+            url::form_urlencoded::byte_serialize(skill_id.as_str().as_bytes()).collect();
+        let url = format!("{}/skills/{}", self.base_url.trim_end_matches('/'), id);
         Ok(reqwest::get(url).await?.json().await?)
     }
 
-    async fn fetch(&self, _skill_id: &str, _dest: &std::path::Path) -> Result<PathBuf> {
+    async fn fetch(&self, _skill_id: &SkillId, _dest: &std::path::Path) -> Result<PathBuf> {
         Err(crate::error::Error::System(
             "HTTP registry fetch is not supported yet".to_string(),
         ))
     }
 
-    async fn versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>> {
+    async fn versions(&self, skill_id: &SkillId) -> Result<Vec<SkillVersion>> {
         let skill = self.get_skill(skill_id).await?;
         Ok(vec![skill.version])
     }
@@ -531,19 +623,81 @@ pub fn parse_skill_md(path: &PathBuf) -> Result<SkillMetadata> {
     Ok(metadata)
 }
 
-pub fn calculate_dir_hash(path: &PathBuf) -> Result<String> {
-    use sha2::{Digest, Sha256};
-    use walkdir::WalkDir;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let mut hasher = Sha256::new();
-
-    for entry in WalkDir::new(path).sort_by_file_name() {
-        let entry = entry.map_err(|e| crate::error::Error::Io(e.into()))?;
-        if entry.file_type().is_file() {
-            let content = std::fs::read(entry.path())?;
-            hasher.update(&content);
+    fn local_registry(root: &std::path::Path, name: &str, skills: &[&str]) -> LocalRegistry {
+        for skill in skills {
+            let dir = root.join(name).join(skill);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), format!("# {} from {}", skill, name)).unwrap();
         }
+        LocalRegistry::new(name, root.join(name))
+    }
+
+    #[tokio::test]
+    async fn test_registry_namespaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut aggregated = AggregatedRegistry::new();
+        aggregated.add_registry(Box::new(local_registry(
+            dir.path(),
+            "one",
+            &["pdf", "docx"],
+        )));
+        aggregated.add_registry(Box::new(local_registry(dir.path(), "two", &["pdf"])));
+
+        let mut ids: Vec<String> = aggregated
+            .search(&SkillQuery::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|listing| listing.id.to_string())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["one/docx", "one/pdf", "two/pdf"]);
+
+        // Namespaced IDs go to their registry; bare ones must be unique
+        let pdf = SkillId::new("pdf").unwrap();
+        let (skill, registry) = aggregated
+            .get_skill_with_source(&SkillId::new("two/pdf").unwrap())
+            .await
+            .unwrap();
+        assert_eq!((skill.id.as_str(), registry.as_str()), ("two/pdf", "two"));
+        assert!(matches!(
+            aggregated.get_skill(&pdf).await,
+            Err(crate::error::Error::AmbiguousSkill(_))
+        ));
+        let docx = aggregated
+            .get_skill(&SkillId::new("docx").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(docx.id, "one/docx");
     }
 
-    Ok(hex::encode(hasher.finalize()))
+    #[test]
+    fn test_legacy_registries_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = RegistryManager {
+            configs: Vec::new(),
+            config_path: dir.path().join("registries.json"),
+        };
+        let legacy = RegistryManager::default_registries();
+        fs::write(
+            &manager.config_path,
+            serde_json::to_string(&legacy).unwrap(),
+        )
+        .unwrap();
+
+        manager.load().unwrap();
+        assert_eq!(manager.configs.len(), legacy.len());
+        assert!(dir.path().join("registries.json.v0.bak").exists());
+
+        // A damaged file is reported instead of being replaced with the defaults
+        fs::write(&manager.config_path, "[{\"name\": ").unwrap();
+        assert!(matches!(
+            manager.load(),
+            Err(crate::error::Error::SchemaError(_))
+        ));
+    }
 }
@@ -0,0 +1,196 @@
+//! Rendering skills into tool-native rule formats
+//!
+//! Some tools read single rule files with their own frontmatter rather than
+//! skill folders (see [`RenderFormat`]). [`render`] turns a stored skill's
+//! `SKILL.md` into such a file: `description` carries over, `globs` become
+//! the format's file patterns and `always_apply: true` makes the rule apply
+//! everywhere. Without either, the rule is left for the agent to pick by its
+//! description where the format allows that. The body is kept as it is.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::models::{RenderFormat, SkillId};
+
+/// Render the skill stored at `skill_dir` in `format`
+pub fn render(format: RenderFormat, skill_id: &SkillId, skill_dir: &Path) -> Result<String> {
+    let content = fs::read_to_string(skill_dir.join("SKILL.md"))?;
+    let (fields, body) = split_frontmatter(&content);
+
+    let description = fields.get("description").cloned().unwrap_or_default();
+    let globs = fields
+        .get("globs")
+        .map(|value| parse_list(value))
+        .unwrap_or_default();
+    let always = fields
+        .get("always_apply")
+        .or_else(|| fields.get("alwaysApply"))
+        .is_some_and(|value| value == "true");
+
+    let mut header = Vec::new();
+    match format {
+        RenderFormat::SkillFolder => {
+            return Err(Error::SyncError(format!(
+                "{} is projected as a folder, not rendered",
+                skill_id
+            )));
+        }
+        RenderFormat::CursorRule => {
+            header.push(format!("description: {}", quote(&description)));
+            header.push(format!("globs: {}", globs.join(",")));
+            header.push(format!("alwaysApply: {}", always));
+        }
+        RenderFormat::CopilotInstructions => {
+            header.push(format!("description: {}", quote(&description)));
+            if always {
+                header.push("applyTo: \"**\"".to_string());
+            } else if !globs.is_empty() {
+                header.push(format!("applyTo: {}", quote(&globs.join(","))));
+            }
+        }
+        RenderFormat::WindsurfRule => {
+            let trigger = if always {
+                "always_on"
+            } else if !globs.is_empty() {
+                "glob"
+            } else {
+                "model_decision"
+            };
+            header.push(format!("trigger: {}", trigger));
+            header.push(format!("description: {}", quote(&description)));
+            if trigger == "glob" {
+                header.push(format!("globs: {}", globs.join(",")));
+            }
+        }
+        RenderFormat::KiroSteering => {
+            if always {
+                header.push("inclusion: always".to_string());
+            } else if let [glob] = globs.as_slice() {
+                header.push("inclusion: fileMatch".to_string());
+                header.push(format!("fileMatchPattern: {}", quote(glob)));
+            } else if !globs.is_empty() {
+                let patterns: Vec<String> = globs.iter().map(|g| quote(g)).collect();
+                header.push("inclusion: fileMatch".to_string());
+                header.push(format!("fileMatchPattern: [{}]", patterns.join(", ")));
+            } else {
+                header.push("inclusion: manual".to_string());
+            }
+        }
+    }
+
+    let mut rendered = format!(
+        "---\n{}\n---\n\n<!-- Generated by SkillsHub from {}; edit the skill in the hub instead -->\n\n{}",
+        header.join("\n"),
+        skill_id,
+        body.trim_start_matches(['\r', '\n'])
+    );
+    if !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    // Scripts and references cannot go into a single file; point at them instead
+    let has_resources = fs::read_dir(skill_dir)?
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.file_name() != "SKILL.md");
+    if has_resources {
+        rendered.push_str(&format!(
+            "\nSupporting files for this skill are in `{}`.\n",
+            skill_dir.display()
+        ));
+    }
+    Ok(rendered)
+}
+
+/// Split `SKILL.md` into its frontmatter fields and body
+///
+/// Like [`crate::registry::parse_skill_md`], only flat `key: value` pairs are
+/// read, plus `- item` lines continuing a key without a value.
+fn split_frontmatter(content: &str) -> (HashMap<String, String>, &str) {
+    let mut fields = HashMap::new();
+    let Some(rest) = content.strip_prefix("---") else {
+        return (fields, content);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (fields, content);
+    };
+    let body = rest[end + 4..]
+        .split_once('\n')
+        .map(|(_, body)| body)
+        .unwrap_or_default();
+
+    let mut last_key: Option<String> = None;
+    for line in rest[..end].lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let (Some(item), Some(key)) = (trimmed.strip_prefix("- "), &last_key) {
+            let value: &mut String = fields.entry(key.clone()).or_default();
+            if !value.is_empty() {
+                value.push(',');
+            }
+            value.push_str(item.trim());
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let key = key.trim().to_string();
+            let value = value.trim().trim_matches('"').trim_matches('\'');
+            fields.insert(key.clone(), value.to_string());
+            last_key = Some(key);
+        }
+    }
+    (fields, body)
+}
+
+/// Items of a `[a, b]` or `a, b` list
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .trim_matches('[')
+        .trim_matches(']')
+        .split(',')
+        .map(|s| s.trim().trim_matches('"').trim_matches('\''))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// A YAML double-quoted string
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: lint\ndescription: Fix \"lint\" errors\nglobs:\n  - src/**/*.rs\n  - tests/**/*.rs\n---\n\n# Lint\n\nRun clippy.\n",
+        )
+        .unwrap();
+        let id = SkillId::new("lint").unwrap();
+
+        let cursor = render(RenderFormat::CursorRule, &id, dir.path()).unwrap();
+        assert!(cursor.starts_with(
+            "---\ndescription: \"Fix \\\"lint\\\" errors\"\nglobs: src/**/*.rs,tests/**/*.rs\nalwaysApply: false\n---\n"
+        ));
+        assert!(cursor.ends_with("# Lint\n\nRun clippy.\n"));
+
+        let kiro = render(RenderFormat::KiroSteering, &id, dir.path()).unwrap();
+        assert!(kiro.contains(
+            "inclusion: fileMatch\nfileMatchPattern: [\"src/**/*.rs\", \"tests/**/*.rs\"]\n"
+        ));
+
+        // Supporting files are pointed at rather than lost
+        fs::write(dir.path().join("check.sh"), "cargo clippy").unwrap();
+        let windsurf = render(RenderFormat::WindsurfRule, &id, dir.path()).unwrap();
+        assert!(windsurf.starts_with("---\ntrigger: glob\n"));
+        assert!(windsurf.contains("Supporting files for this skill are in"));
+
+        assert!(render(RenderFormat::SkillFolder, &id, dir.path()).is_err());
+    }
+}
@@ -84,7 +84,7 @@ impl SecurityScanner {
         let mut report = ScanReport::clean(skill_id, "");
 
         // Update hash
-        report.version_hash = calculate_hash(skill_path)?;
+        report.version_hash = crate::hash::digest(skill_path)?;
 
         // Scan all relevant files
         for entry in WalkDir::new(skill_path) {
@@ -293,19 +293,3 @@ fn truncate_line(line: &str, max_len: usize) -> String {
         line.to_string()
     }
 }
-
-fn calculate_hash(path: &Path) -> Result<String> {
-    use sha2::{Digest, Sha256};
-
-    let mut hasher = Sha256::new();
-
-    for entry in WalkDir::new(path).sort_by_file_name() {
-        let entry = entry.map_err(|e| crate::error::Error::Io(e.into()))?;
-        if entry.file_type().is_file() {
-            let content = fs::read(entry.path())?;
-            hasher.update(&content);
-        }
-    }
-
-    Ok(hex::encode(hasher.finalize()))
-}
@@ -0,0 +1,208 @@
+//! Versioned persisted files
+//!
+//! Every JSON file SkillsHub keeps (install records, `config.json`,
+//! `registries.json`, …) carries a top-level `schema_version`. Files are read
+//! through [`load`], which upgrades older documents one version at a time with
+//! the type's [`Versioned::migrate`] steps, keeps the original next to the file
+//! as `<name>.v<N>.bak` and rewrites it in the current version. A file that
+//! cannot be parsed or was written by a newer SkillsHub is an error; it is never
+//! replaced with defaults.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::store::write_atomic;
+
+/// Name of the version field in every persisted document
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// A document persisted as a versioned JSON file
+///
+/// Files written before versioning have no `schema_version` and count as
+/// version 0.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version written by this build
+    const SCHEMA_VERSION: u32;
+
+    /// Upgrade a raw document from version `from` to `from + 1`
+    fn migrate(from: u32, document: Value) -> Result<Value>;
+}
+
+/// Read a versioned file, upgrading it in place if it is older
+///
+/// Returns `None` if the file does not exist.
+pub fn load<T: Versioned>(path: &Path) -> Result<Option<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut document: Value = serde_json::from_str(&content)
+        .map_err(|e| schema_error(path, format!("not valid JSON ({})", e)))?;
+
+    let found = version_of(&document).map_err(|e| schema_error(path, e))?;
+    if found > T::SCHEMA_VERSION {
+        return Err(schema_error(
+            path,
+            format!(
+                "written by a newer SkillsHub (schema version {}, this build reads up to {})",
+                found,
+                T::SCHEMA_VERSION
+            ),
+        ));
+    }
+
+    for from in found..T::SCHEMA_VERSION {
+        document = T::migrate(from, document)
+            .map_err(|e| schema_error(path, format!("migration from v{} failed: {}", from, e)))?;
+    }
+    let value = serde_json::from_value(document)
+        .map_err(|e| schema_error(path, format!("unreadable: {}", e)))?;
+
+    if found < T::SCHEMA_VERSION {
+        let backup = backup_path(path, found);
+        fs::write(&backup, &content)?;
+        tracing::info!(
+            "Upgraded {} from schema v{} to v{} (original kept at {})",
+            path.display(),
+            found,
+            T::SCHEMA_VERSION,
+            backup.display()
+        );
+        save(path, &value)?;
+    }
+    Ok(Some(value))
+}
+
+/// Write a versioned file atomically, stamped with the current version
+pub fn save<T: Versioned>(path: &Path, value: &T) -> Result<()> {
+    let mut document = serde_json::to_value(value)?;
+    match document.as_object_mut() {
+        Some(object) => {
+            object.insert(
+                SCHEMA_VERSION_FIELD.to_string(),
+                Value::from(T::SCHEMA_VERSION),
+            );
+        }
+        None => {
+            return Err(schema_error(
+                path,
+                "versioned documents must be objects".into(),
+            ));
+        }
+    }
+    let content = serde_json::to_string_pretty(&document)?;
+    write_atomic(path, content.as_bytes())
+}
+
+/// Migration step for files that used to be a bare JSON array: wrap it as
+/// `{ "<key>": [...] }`
+pub fn wrap_list(document: Value, key: &str) -> Result<Value> {
+    match document {
+        Value::Array(items) => Ok(serde_json::json!({ key: items })),
+        Value::Object(_) => Ok(document),
+        _ => Err(Error::SchemaError(format!("expected a list of {}", key))),
+    }
+}
+
+fn version_of(document: &Value) -> std::result::Result<u32, String> {
+    match document.get(SCHEMA_VERSION_FIELD) {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("invalid {}: {}", SCHEMA_VERSION_FIELD, version)),
+    }
+}
+
+/// `<name>.v<N>.bak`, without overwriting an earlier backup
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = path.with_file_name(format!("{}.v{}.bak", file_name, version));
+    if !backup.exists() {
+        return backup;
+    }
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    path.with_file_name(format!("{}.v{}.{}.bak", file_name, version, stamp))
+}
+
+fn schema_error(path: &Path, message: String) -> Error {
+    Error::SchemaError(format!("{}: {}", path.display(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Names {
+        names: Vec<String>,
+        #[serde(default)]
+        sorted: bool,
+    }
+
+    impl Versioned for Names {
+        const SCHEMA_VERSION: u32 = 2;
+
+        fn migrate(from: u32, document: Value) -> Result<Value> {
+            match from {
+                0 => wrap_list(document, "names"),
+                _ => {
+                    let mut document = document;
+                    document["sorted"] = Value::Bool(false);
+                    Ok(document)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_upgrade_in_place() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("names.json");
+        fs::write(&path, r#"["a", "b"]"#).unwrap();
+
+        let loaded: Names = load(&path).unwrap().unwrap();
+        assert_eq!(loaded.names, vec!["a", "b"]);
+
+        let backup = temp.path().join("names.json.v0.bak");
+        assert_eq!(fs::read_to_string(backup).unwrap(), r#"["a", "b"]"#);
+        let rewritten: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(rewritten[SCHEMA_VERSION_FIELD], 2);
+
+        // Current files are read as they are
+        assert_eq!(load::<Names>(&path).unwrap().unwrap(), loaded);
+        assert!(!temp.path().join("names.json.v2.bak").exists());
+    }
+
+    #[test]
+    fn test_unreadable_files_are_errors() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("names.json");
+        assert!(load::<Names>(&path).unwrap().is_none());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(load::<Names>(&path), Err(Error::SchemaError(_))));
+
+        fs::write(&path, r#"{"schema_version": 3, "names": []}"#).unwrap();
+        assert!(matches!(load::<Names>(&path), Err(Error::SchemaError(_))));
+
+        fs::write(&path, r#"{"schema_version": 2, "names": 5}"#).unwrap();
+        assert!(matches!(load::<Names>(&path), Err(Error::SchemaError(_))));
+        // Nothing was rewritten
+        assert!(fs::read_to_string(&path).unwrap().contains("\"names\": 5"));
+    }
+}
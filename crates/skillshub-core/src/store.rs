@@ -3,19 +3,48 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use walkdir::WalkDir;
 
 use crate::error::{Error, Result};
-use crate::models::{InstallRecord, Skill};
+use crate::hash;
+use crate::lock::{try_lock_file, StoreLock, StoreLockGuard};
+use crate::models::{
+    InstallRecord, IntegrityIssue, IntegrityIssueType, Skill, SkillId, SkillVersion, SyncScope,
+    SyncState, VerifyReport, SYNC_STATE_SCHEMA_VERSION,
+};
+use crate::objects::{ObjectStore, SkillTree};
+use crate::registry::AggregatedRegistry;
+use crate::scanner::SecurityScanner;
+use crate::schema::{self, Versioned};
+
+/// Default number of previous versions kept per skill
+pub const DEFAULT_RETAINED_VERSIONS: usize = 3;
+
+/// Default time to wait for the store lock
+pub const DEFAULT_LOCK_TIMEOUT_SECS: u64 = 10;
 
 /// Local store configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoreConfig {
     /// Root directory for the store
     pub root_dir: PathBuf,
+    /// Number of previous versions kept per skill for rollback
+    #[serde(default = "default_retained_versions")]
+    pub retained_versions: usize,
+    /// Seconds to wait for another process holding the store lock
+    #[serde(default = "default_lock_timeout_secs")]
+    pub lock_timeout_secs: u64,
+}
+
+fn default_retained_versions() -> usize {
+    DEFAULT_RETAINED_VERSIONS
+}
+
+fn default_lock_timeout_secs() -> u64 {
+    DEFAULT_LOCK_TIMEOUT_SECS
 }
 
 impl Default for StoreConfig {
@@ -24,15 +53,76 @@ impl Default for StoreConfig {
             .unwrap_or_else(|| PathBuf::from("."))
             .join("skillshub")
             .join("store");
-        Self { root_dir }
+        Self {
+            root_dir,
+            retained_versions: DEFAULT_RETAINED_VERSIONS,
+            lock_timeout_secs: DEFAULT_LOCK_TIMEOUT_SECS,
+        }
+    }
+}
+
+/// Name of the staging journal inside `staging/`
+const JOURNAL_FILE: &str = "journal.json";
+
+/// A skill copied into the store's staging area, not yet visible
+///
+/// Commit it with [`LocalStore::commit_staged`] or discard it with
+/// [`LocalStore::abort_staged`].
+#[must_use = "staged installs must be committed or aborted"]
+#[derive(Debug)]
+pub struct StagedSkill {
+    txn_id: String,
+    skill: Skill,
+    path: PathBuf,
+    /// Files of the staged skill, already in the object store
+    tree: SkillTree,
+    /// Held for the lifetime of the transaction so recovery in other
+    /// processes leaves it alone
+    txn_lock: fs::File,
+}
+
+impl StagedSkill {
+    /// Skill being installed, with the content hash of the staged files
+    pub fn skill(&self) -> &Skill {
+        &self.skill
+    }
+
+    /// Directory holding the staged files
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 }
 
+/// In-flight installs, used to recover from crashes
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StagingJournal {
+    entries: Vec<StagingEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StagingEntry {
+    txn_id: String,
+    skill_id: SkillId,
+    started_at: String,
+    /// Final record, set once the install is being committed
+    #[serde(default)]
+    commit: Option<InstallRecord>,
+}
+
 /// Local skill store
+///
+/// Every mutation takes an exclusive lock on the store root and re-reads the
+/// records it changes, so several processes can share one store. File
+/// contents live once in the object store (`objects/`); each version
+/// directory is materialised from the tree recorded under `manifests/`.
 pub struct LocalStore {
     config: StoreConfig,
     /// Cached install records
-    records: HashMap<String, InstallRecord>,
+    records: HashMap<SkillId, InstallRecord>,
+    /// Records that could not be read, with the reason; reported by [`Self::verify`]
+    unreadable: HashMap<SkillId, String>,
+    lock: StoreLock,
+    objects: ObjectStore,
 }
 
 impl LocalStore {
@@ -40,19 +130,64 @@ impl LocalStore {
     pub fn new(config: StoreConfig) -> Result<Self> {
         fs::create_dir_all(&config.root_dir)?;
         fs::create_dir_all(config.root_dir.join("skills"))?;
+        fs::create_dir_all(config.root_dir.join("versions"))?;
         fs::create_dir_all(config.root_dir.join("metadata"))?;
 
+        let lock = StoreLock::open(
+            &config.root_dir,
+            Duration::from_secs(config.lock_timeout_secs),
+        )?;
+        let objects = ObjectStore::new(config.root_dir.join("objects"));
         let mut store = Self {
             config,
             records: HashMap::new(),
+            unreadable: HashMap::new(),
+            lock,
+            objects,
         };
-        store.load_records()?;
+        store.recover_staging()?;
+        store.reload()?;
+        store.migrate_skill_names()?;
+        store.migrate_digests()?;
         Ok(store)
     }
 
-    /// Create with default config
+    /// Take the store's exclusive lock for a multi-step operation
+    ///
+    /// Store methods lock on their own; holding this guard additionally keeps
+    /// other processes out between calls (e.g. while projecting into tools).
+    pub fn lock_exclusive(&self) -> Result<StoreLockGuard> {
+        self.lock.exclusive()
+    }
+
+    /// Re-read all install records from disk
+    pub fn reload(&mut self) -> Result<()> {
+        let _guard = self.lock.shared()?;
+        self.records.clear();
+        self.load_records()
+    }
+
+    /// Re-read one record from disk, so changes made by other processes are not lost
+    fn refresh_record(&mut self, skill_id: &SkillId) {
+        let record = match schema::load::<InstallRecord>(&self.metadata_path(skill_id)) {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                None
+            }
+        };
+        match record {
+            Some(record) => self.records.insert(skill_id.clone(), record),
+            None => self.records.remove(skill_id),
+        };
+    }
+
+    /// Create with default config, honouring the retention set in the app config
     pub fn default_store() -> Result<Self> {
-        Self::new(StoreConfig::default())
+        Self::new(StoreConfig {
+            retained_versions: crate::config::AppConfig::load_or_default().retained_versions,
+            ..Default::default()
+        })
     }
 
     /// Get the root directory
@@ -66,65 +201,850 @@ impl LocalStore {
     }
 
     /// Get the path for a specific skill
-    pub fn skill_path(&self, skill_id: &str) -> PathBuf {
-        self.skills_dir().join(skill_id)
+    ///
+    /// This is a stable pointer to the active version (see [`Self::version_path`]),
+    /// so projections linked to it follow rollbacks and updates.
+    pub fn skill_path(&self, skill_id: &SkillId) -> PathBuf {
+        self.skills_dir().join(skill_id.file_name())
+    }
+
+    /// Get the directory holding every retained version of a skill
+    pub fn versions_dir(&self, skill_id: &SkillId) -> PathBuf {
+        self.config
+            .root_dir
+            .join("versions")
+            .join(skill_id.file_name())
+    }
+
+    /// Get the path of one version of a skill, keyed by its content hash
+    pub fn version_path(&self, skill_id: &SkillId, content_hash: &str) -> PathBuf {
+        self.versions_dir(skill_id)
+            .join(version_dir_name(content_hash))
+    }
+
+    /// Get the file tree manifest of one version of a skill
+    pub fn manifest_path(&self, skill_id: &SkillId, content_hash: &str) -> PathBuf {
+        self.manifests_dir(skill_id)
+            .join(format!("{}.json", version_dir_name(content_hash)))
+    }
+
+    /// Get the directory holding the tree manifests of a skill's versions
+    pub fn manifests_dir(&self, skill_id: &SkillId) -> PathBuf {
+        self.config
+            .root_dir
+            .join("manifests")
+            .join(skill_id.file_name())
+    }
+
+    /// Get the content-addressed object store
+    pub fn objects(&self) -> &ObjectStore {
+        &self.objects
     }
 
     /// Check if a skill is installed
-    pub fn is_installed(&self, skill_id: &str) -> bool {
+    pub fn is_installed(&self, skill_id: &SkillId) -> bool {
         self.records.contains_key(skill_id)
     }
 
     /// Get install record for a skill
-    pub fn get_record(&self, skill_id: &str) -> Option<&InstallRecord> {
+    pub fn get_record(&self, skill_id: &SkillId) -> Option<&InstallRecord> {
         self.records.get(skill_id)
     }
 
+    /// Check if a skill was registered from Claude plugins rather than stored
+    pub fn is_plugin_record(&self, skill_id: &SkillId) -> bool {
+        self.records.get(skill_id).is_some_and(is_plugin_record)
+    }
+
     /// List all installed skills
     pub fn list_installed(&self) -> Vec<&InstallRecord> {
         self.records.values().collect()
     }
 
+    /// Name of a skill's directory in tools: its alias, or the last segment
+    /// of its ID (`anthropics/pdf` is projected as `pdf`)
+    pub fn projection_name(&self, skill_id: &SkillId) -> SkillId {
+        self.records
+            .get(skill_id)
+            .and_then(|r| r.alias.clone())
+            .unwrap_or_else(|| skill_id.short_name())
+    }
+
+    /// Resolve a name given by a user to an installed skill
+    ///
+    /// A full ID matches its skill. Otherwise `name` is matched against the
+    /// names skills are projected under (see [`Self::projection_name`]) and
+    /// must match exactly one of them.
+    pub fn resolve(&self, name: &str) -> Result<SkillId> {
+        let id = SkillId::new(name)?;
+        if self.records.contains_key(&id) {
+            return Ok(id);
+        }
+
+        let mut matches: Vec<&SkillId> = self
+            .records
+            .keys()
+            .filter(|skill_id| self.projection_name(skill_id) == id)
+            .collect();
+        matches.sort();
+        match matches.as_slice() {
+            [] => Err(Error::SkillNotFound(name.to_string())),
+            [skill_id] => Ok((*skill_id).clone()),
+            _ => Err(Error::AmbiguousSkill(format!(
+                "{}: matches {}",
+                name,
+                matches
+                    .iter()
+                    .map(|id| id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Set or clear the alias a skill is projected under
+    ///
+    /// Fails if another installed skill is already projected under that name.
+    /// Existing projections are not moved; see [`crate::sync::SyncEngine::set_alias`].
+    pub fn set_alias(
+        &mut self,
+        skill_id: &SkillId,
+        alias: Option<SkillId>,
+    ) -> Result<InstallRecord> {
+        if let Some(alias) = alias.as_ref().filter(|a| a.is_qualified()) {
+            return Err(Error::InvalidSkillId(format!(
+                "{:?}: an alias must not contain '/'",
+                alias.as_str()
+            )));
+        }
+
+        let _guard = self.lock.exclusive()?;
+        self.records.clear();
+        self.load_records()?;
+
+        let name = alias.clone().unwrap_or_else(|| skill_id.short_name());
+        let taken = self
+            .records
+            .keys()
+            .find(|id| *id != skill_id && self.projection_name(id) == name);
+        if let Some(other) = taken {
+            return Err(Error::StoreError(format!(
+                "{} is already projected as {}",
+                other, name
+            )));
+        }
+
+        let record = self
+            .records
+            .get_mut(skill_id)
+            .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
+        record.alias = alias;
+        let record = record.clone();
+        self.save_record(&record)?;
+        Ok(record)
+    }
+
+    /// Whether a skill is kept out of all tools
+    pub fn is_disabled(&self, skill_id: &SkillId) -> bool {
+        self.records.get(skill_id).is_some_and(|r| r.disabled)
+    }
+
+    /// Enable or disable a skill; projections are handled by the sync engine
+    pub fn set_disabled(&mut self, skill_id: &SkillId, disabled: bool) -> Result<InstallRecord> {
+        let _guard = self.lock.exclusive()?;
+        self.refresh_record(skill_id);
+        let record = self
+            .records
+            .get_mut(skill_id)
+            .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
+        record.disabled = disabled;
+        let record = record.clone();
+        self.save_record(&record)?;
+        Ok(record)
+    }
+
     /// Import a skill to the store
+    ///
+    /// The files are stored side by side with earlier versions under
+    /// `versions/<id>/<hash>/` and the new version is activated; the previous
+    /// one is kept for [`Self::rollback`] until it falls out of retention.
+    /// This is [`Self::stage_skill`] followed by [`Self::commit_staged`]; use
+    /// those directly to inspect (e.g. scan) the files before committing.
     pub async fn import_skill(
         &mut self,
         skill: &Skill,
         source_path: &Path,
     ) -> Result<InstallRecord> {
-        let skill_dir = self.skill_path(&skill.id);
+        let staged = self.stage_skill(skill, source_path)?;
+        self.commit_staged(staged)
+    }
 
-        // Copy skill files
-        if source_path.is_dir() {
-            copy_dir_all(source_path, &skill_dir)?;
-        } else {
-            fs::create_dir_all(&skill_dir)?;
-            let dest = skill_dir.join(source_path.file_name().unwrap_or_default());
-            fs::copy(source_path, dest)?;
+    /// Import a skill only if the staged files pass a security scan
+    ///
+    /// Like the CLI's single install, the files are staged, scanned and then
+    /// committed, or discarded with [`Error::PolicyViolation`] listing the
+    /// findings. Content fetched or found without a user vetting it (manifest
+    /// and lockfile installs, registry installs from the desktop app, store
+    /// repairs) goes through here.
+    pub fn import_scanned(&mut self, skill: &Skill, source_path: &Path) -> Result<InstallRecord> {
+        let staged = self.stage_skill(skill, source_path)?;
+        let report = match SecurityScanner::new().scan(skill.id.as_str(), staged.path()) {
+            Ok(report) => report,
+            Err(e) => {
+                self.abort_staged(staged)?;
+                return Err(e);
+            }
+        };
+        if !report.passed {
+            self.abort_staged(staged)?;
+            let findings: Vec<String> = report
+                .findings
+                .iter()
+                .map(|f| format!("[{}] {}", f.risk_level, f.description))
+                .collect();
+            return Err(Error::PolicyViolation(format!(
+                "{} blocked by the security scan: {}",
+                skill.id,
+                findings.join("; ")
+            )));
         }
+        self.commit_staged(staged)
+    }
 
-        // Create install record
-        let record = InstallRecord {
+    /// Get the staging directory for in-flight installs
+    pub fn staging_dir(&self) -> PathBuf {
+        self.config.root_dir.join("staging")
+    }
+
+    /// Copy a skill into a private staging directory inside the store
+    ///
+    /// Nothing is visible under `skills/` until [`Self::commit_staged`]; a
+    /// staged install that is neither committed nor aborted (e.g. after a
+    /// crash) is cleaned up the next time the store is opened. An ID that
+    /// differs only in case from an installed one is rejected: on
+    /// case-insensitive file systems both would share one directory.
+    pub fn stage_skill(&mut self, skill: &Skill, source_path: &Path) -> Result<StagedSkill> {
+        let file_name = skill.id.file_name();
+        if let Some(existing) = self
+            .records
+            .keys()
+            .find(|id| **id != skill.id && id.file_name().eq_ignore_ascii_case(&file_name))
+        {
+            return Err(Error::StoreError(format!(
+                "{} differs only in case from installed skill {}",
+                skill.id, existing
+            )));
+        }
+
+        let txn_id = next_txn_id(&skill.id);
+        let path = self.staging_dir().join(&txn_id);
+
+        fs::create_dir_all(self.staging_dir())?;
+        let txn_lock = try_lock_file(&self.txn_lock_path(&txn_id))?.ok_or_else(|| {
+            Error::StoreBusy(format!("staging transaction {} is already in use", txn_id))
+        })?;
+
+        self.update_journal(|journal| {
+            journal.entries.push(StagingEntry {
+                txn_id: txn_id.clone(),
+                skill_id: skill.id.clone(),
+                started_at: timestamp_now(),
+                commit: None,
+            })
+        })?;
+
+        let ingested = self.objects.ingest(source_path).and_then(|mut tree| {
+            self.objects.materialize(&mut tree, &path)?;
+            Ok(tree)
+        });
+
+        match ingested {
+            Ok(tree) => {
+                let mut skill = skill.clone();
+                skill.version.content_hash = tree.content_hash.clone();
+                Ok(StagedSkill {
+                    txn_id,
+                    skill,
+                    path,
+                    tree,
+                    txn_lock,
+                })
+            }
+            Err(e) => {
+                let _ = self.discard_txn(&txn_id, txn_lock);
+                Err(e)
+            }
+        }
+    }
+
+    /// Discard a staged install
+    pub fn abort_staged(&mut self, staged: StagedSkill) -> Result<()> {
+        self.discard_txn(&staged.txn_id, staged.txn_lock)
+    }
+
+    /// Move a staged install into the store and activate it
+    ///
+    /// The final record is journaled before any visible change, so a crash
+    /// part-way through is rolled forward on the next start.
+    pub fn commit_staged(&mut self, staged: StagedSkill) -> Result<InstallRecord> {
+        let StagedSkill {
+            txn_id,
+            skill,
+            path,
+            mut tree,
+            txn_lock,
+        } = staged;
+        let _guard = self.lock.exclusive()?;
+        self.refresh_record(&skill.id);
+        self.migrate_legacy_layout(&skill.id)?;
+
+        // The previously active version moves into the history
+        let previous = self.records.get(&skill.id).cloned();
+        let mut history = Vec::new();
+        if let Some(previous) = &previous {
+            if previous.version.content_hash != skill.version.content_hash {
+                history.push(previous.version.clone());
+            }
+            history.extend(previous.history.iter().cloned());
+        }
+        history.retain(|v| v.content_hash != skill.version.content_hash);
+
+        let mut record = InstallRecord {
             skill_id: skill.id.clone(),
             version: skill.version.clone(),
             installed_at: timestamp_now(),
             source: skill.source.clone(),
-            projected_tools: Vec::new(),
             scan_passed: true,
+            history,
+            digest_version: hash::DIGEST_VERSION,
+            alias: previous.as_ref().and_then(|p| p.alias.clone()),
+            disabled: previous.as_ref().is_some_and(|p| p.disabled),
+            projected_tools: previous.map(|p| p.projected_tools).unwrap_or_default(),
         };
+        let expired = self.expire_history(&mut record);
+
+        self.update_journal(|journal| {
+            if let Some(entry) = journal.entries.iter_mut().find(|e| e.txn_id == txn_id) {
+                entry.commit = Some(record.clone());
+            }
+        })?;
+
+        let version_dir = self.version_path(&skill.id, &skill.version.content_hash);
+        if version_dir.exists() && hash::digest(&version_dir)? == skill.version.content_hash {
+            // Identical content is already stored
+            fs::remove_dir_all(&path)?;
+        } else {
+            if version_dir.exists() {
+                // A damaged copy of this version is replaced
+                fs::remove_dir_all(&version_dir)?;
+            }
+            fs::create_dir_all(self.versions_dir(&skill.id))?;
+            fs::rename(&path, &version_dir)?;
+        }
+        tree.stamp(&version_dir);
+        tree.save(&self.manifest_path(&skill.id, &skill.version.content_hash))?;
+
+        self.finish_commit(&record)?;
+
+        for version in expired {
+            self.remove_version(&record.skill_id, &version.content_hash)?;
+        }
+
+        self.update_journal(|journal| journal.entries.retain(|e| e.txn_id != txn_id))?;
+        drop(txn_lock);
+        let _ = fs::remove_file(self.txn_lock_path(&txn_id));
+        Ok(record)
+    }
+
+    /// Activate a committed version and persist its record
+    fn finish_commit(&mut self, record: &InstallRecord) -> Result<()> {
+        self.activate_version(&record.skill_id, &record.version.content_hash)?;
+        self.save_record(record)?;
+        self.records.insert(record.skill_id.clone(), record.clone());
+        Ok(())
+    }
+
+    /// Remove a transaction's staging directory, journal entry and lock
+    fn discard_txn(&mut self, txn_id: &str, txn_lock: fs::File) -> Result<()> {
+        let path = self.staging_dir().join(txn_id);
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        self.update_journal(|journal| journal.entries.retain(|e| e.txn_id != txn_id))?;
+        drop(txn_lock);
+        let _ = fs::remove_file(self.txn_lock_path(txn_id));
+        Ok(())
+    }
+
+    fn txn_lock_path(&self, txn_id: &str) -> PathBuf {
+        self.staging_dir().join(format!("{}.lock", txn_id))
+    }
+
+    /// Finish or discard installs interrupted by a crash
+    ///
+    /// Journaled commits whose files reached `versions/` are rolled forward;
+    /// everything else left in `staging/` is removed. Transactions still
+    /// held by a running process are left alone.
+    fn recover_staging(&mut self) -> Result<()> {
+        let staging_dir = self.staging_dir();
+        let has_leftovers = fs::read_dir(&staging_dir)
+            .map(|entries| entries.flatten().any(|e| e.file_name() != JOURNAL_FILE))
+            .unwrap_or(false);
+        if !has_leftovers && self.load_journal().entries.is_empty() {
+            return Ok(());
+        }
+
+        let _guard = self.lock.exclusive()?;
+        let mut journal = self.load_journal();
+
+        // Transactions are named after their staging directory or lock file
+        let mut txn_ids: Vec<String> = fs::read_dir(&staging_dir)?
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name != JOURNAL_FILE)
+            .map(|name| name.trim_end_matches(".lock").to_string())
+            .chain(journal.entries.iter().map(|e| e.txn_id.clone()))
+            .collect();
+        txn_ids.sort();
+        txn_ids.dedup();
+
+        let mut dead = Vec::new();
+        for txn_id in txn_ids {
+            // Keep the lock until the leftovers are gone
+            if let Some(txn_lock) = try_lock_file(&self.txn_lock_path(&txn_id))? {
+                dead.push((txn_id, txn_lock));
+            }
+        }
+        if dead.is_empty() {
+            return Ok(());
+        }
+
+        for (txn_id, txn_lock) in dead {
+            match journal.entries.iter().find(|e| e.txn_id == txn_id) {
+                Some(StagingEntry {
+                    commit: Some(record),
+                    skill_id,
+                    ..
+                }) if self
+                    .version_path(&record.skill_id, &record.version.content_hash)
+                    .is_dir() =>
+                {
+                    tracing::warn!(
+                        "Completing interrupted install of {} (transaction {})",
+                        skill_id,
+                        txn_id
+                    );
+                    let record = record.clone();
+                    self.finish_commit(&record)?;
+                }
+                Some(entry) => tracing::warn!(
+                    "Discarding interrupted install of {} (transaction {})",
+                    entry.skill_id,
+                    txn_id
+                ),
+                None => tracing::warn!("Removing orphaned staging data {}", txn_id),
+            }
+
+            let path = staging_dir.join(&txn_id);
+            if path.exists() {
+                fs::remove_dir_all(&path)?;
+            }
+            journal.entries.retain(|e| e.txn_id != txn_id);
+            drop(txn_lock);
+            let _ = fs::remove_file(self.txn_lock_path(&txn_id));
+        }
+
+        self.save_journal(&journal)
+    }
+
+    /// Whether installs are currently staged (possibly by another process)
+    pub(crate) fn has_staged_installs(&self) -> bool {
+        !self.load_journal().entries.is_empty()
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.staging_dir().join(JOURNAL_FILE)
+    }
+
+    fn load_journal(&self) -> StagingJournal {
+        fs::read_to_string(self.journal_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_journal(&self, journal: &StagingJournal) -> Result<()> {
+        let content = serde_json::to_string_pretty(journal)?;
+        write_atomic(&self.journal_path(), content.as_bytes())
+    }
+
+    fn update_journal(&self, f: impl FnOnce(&mut StagingJournal)) -> Result<()> {
+        let _guard = self.lock.exclusive()?;
+        let mut journal = self.load_journal();
+        f(&mut journal);
+        self.save_journal(&journal)
+    }
+
+    /// Re-activate a previously installed version
+    ///
+    /// `version` matches a retained version string or content hash prefix;
+    /// without it the most recent previous version is restored. The version
+    /// that was active is kept in the history, so a rollback can be undone.
+    pub fn rollback(&mut self, skill_id: &SkillId, version: Option<&str>) -> Result<InstallRecord> {
+        let _guard = self.lock.exclusive()?;
+        self.refresh_record(skill_id);
+        self.migrate_legacy_layout(skill_id)?;
+
+        let mut record = self
+            .records
+            .get(skill_id)
+            .cloned()
+            .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
+
+        let index = match version {
+            None => (!record.history.is_empty()).then_some(0),
+            Some(wanted) => record
+                .history
+                .iter()
+                .position(|v| v.version == wanted || v.content_hash.starts_with(wanted)),
+        }
+        .ok_or_else(|| {
+            Error::StoreError(format!(
+                "No retained version {}of '{}' to roll back to",
+                version.map(|v| format!("{} ", v)).unwrap_or_default(),
+                skill_id
+            ))
+        })?;
+
+        let target = record.history.remove(index);
+        if !self.version_path(skill_id, &target.content_hash).is_dir()
+            && !self.rematerialize(skill_id, &target.content_hash)?
+        {
+            return Err(Error::StoreError(format!(
+                "Files for '{}' version {} are missing from the store",
+                skill_id, target.version
+            )));
+        }
+
+        self.activate_version(skill_id, &target.content_hash)?;
+
+        let previous = std::mem::replace(&mut record.version, target);
+        record.history.insert(0, previous);
 
         self.save_record(&record)?;
-        self.records.insert(skill.id.clone(), record.clone());
+        self.records.insert(skill_id.clone(), record.clone());
 
         Ok(record)
     }
 
+    /// Atomically point `skills/<id>` at a stored version
+    ///
+    /// A new symlink is created next to the old one and renamed over it, so
+    /// readers (and tool projections linking to `skills/<id>`) see either the
+    /// old or the new version, never a missing directory.
+    fn activate_version(&self, skill_id: &SkillId, content_hash: &str) -> Result<()> {
+        let link = self.skill_path(skill_id);
+
+        #[cfg(unix)]
+        {
+            let relative = Path::new("..")
+                .join("versions")
+                .join(skill_id.file_name())
+                .join(version_dir_name(content_hash));
+            let tmp = self
+                .skills_dir()
+                .join(format!(".{}.activate", skill_id.file_name()));
+            if tmp.is_symlink() {
+                fs::remove_file(&tmp)?;
+            }
+            std::os::unix::fs::symlink(&relative, &tmp)?;
+            fs::rename(&tmp, &link)?;
+        }
+        #[cfg(windows)]
+        {
+            // Directory symlinks need extra privileges on Windows; fall back to a copy
+            if link.is_symlink() {
+                fs::remove_dir(&link)?;
+            } else if link.exists() {
+                fs::remove_dir_all(&link)?;
+            }
+            let version_dir = self.version_path(skill_id, content_hash);
+            if std::os::windows::fs::symlink_dir(&version_dir, &link).is_err() {
+                copy_dir_all(&version_dir, &link)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move entries that older stores kept under the raw skill ID to the
+    /// encoded name (see [`SkillId::file_name`])
+    fn migrate_skill_names(&mut self) -> Result<()> {
+        let root = &self.config.root_dir;
+        let raw_paths = |id: &SkillId| {
+            [
+                root.join("metadata").join(format!("{}.json", id.as_str())),
+                root.join("skills").join(id.as_str()),
+                root.join("versions").join(id.as_str()),
+            ]
+        };
+        let outdated = self.records.keys().any(|id| {
+            id.as_str() != id.file_name()
+                && raw_paths(id).iter().any(|p| p.exists() || p.is_symlink())
+        });
+        if !outdated {
+            return Ok(());
+        }
+
+        let _guard = self.lock.exclusive()?;
+        for entry in fs::read_dir(self.metadata_dir())?.flatten() {
+            let path = entry.path();
+            let Some(record) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<InstallRecord>(&content).ok())
+            else {
+                continue;
+            };
+            let skill_id = &record.skill_id;
+            if skill_id.as_str() == skill_id.file_name() {
+                continue;
+            }
+
+            tracing::info!("Moving {} to {}", skill_id, skill_id.file_name());
+            for dir in ["skills", "versions", "manifests"] {
+                let dir = self.config.root_dir.join(dir);
+                let old = dir.join(skill_id.as_str());
+                let new = dir.join(skill_id.file_name());
+                if (old.exists() || old.is_symlink()) && !new.exists() && !new.is_symlink() {
+                    fs::rename(&old, &new)?;
+                }
+            }
+            // Version links are relative to the skill's directory name
+            let content_hash = &record.version.content_hash;
+            if !is_plugin_record(&record) && self.version_path(skill_id, content_hash).is_dir() {
+                self.activate_version(skill_id, content_hash)?;
+            }
+
+            let metadata_path = self.metadata_path(skill_id);
+            if path == metadata_path {
+                continue;
+            } else if metadata_path.exists() {
+                fs::remove_file(&path)?;
+            } else {
+                fs::rename(&path, &metadata_path)?;
+            }
+        }
+
+        self.records.clear();
+        self.load_records()
+    }
+
+    /// Move a skill stored in place under `skills/<id>` (older layout) into
+    /// `versions/<id>/<hash>/`
+    fn migrate_legacy_layout(&mut self, skill_id: &SkillId) -> Result<()> {
+        let skill_dir = self.skill_path(skill_id);
+        if skill_dir.is_symlink() || !skill_dir.is_dir() {
+            return Ok(());
+        }
+
+        let mut tree = self.objects.ingest(&skill_dir)?;
+        let content_hash = tree.content_hash.clone();
+        let version_dir = self.version_path(skill_id, &content_hash);
+        if !version_dir.exists() {
+            self.objects.materialize(&mut tree, &version_dir)?;
+            tree.save(&self.manifest_path(skill_id, &content_hash))?;
+        }
+        fs::remove_dir_all(&skill_dir)?;
+        self.activate_version(skill_id, &content_hash)?;
+
+        if let Some(record) = self.records.get_mut(skill_id) {
+            record.version.content_hash = content_hash;
+            let record = record.clone();
+            self.save_record(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Rehash skills recorded with an older digest format (see [`hash::DIGEST_VERSION`])
+    ///
+    /// Version directories and tree manifests move to their new hashes and the
+    /// hashes recorded for projections are remapped, so the format change does
+    /// not show up as modified skills or trigger a full re-sync.
+    fn migrate_digests(&mut self) -> Result<()> {
+        if self
+            .records
+            .values()
+            .all(|r| r.digest_version >= hash::DIGEST_VERSION)
+        {
+            return Ok(());
+        }
+
+        let _guard = self.lock.exclusive()?;
+        self.records.clear();
+        self.load_records()?;
+
+        let mut outdated: Vec<SkillId> = self
+            .records
+            .values()
+            .filter(|r| r.digest_version < hash::DIGEST_VERSION)
+            .map(|r| r.skill_id.clone())
+            .collect();
+        outdated.sort();
+
+        let mut renamed: HashMap<SkillId, HashMap<String, String>> = HashMap::new();
+        for skill_id in outdated {
+            tracing::info!("Rehashing stored versions of {}", skill_id);
+            self.migrate_legacy_layout(&skill_id)?;
+            let Some(mut record) = self.records.get(&skill_id).cloned() else {
+                continue;
+            };
+
+            if !is_plugin_record(&record) {
+                let mut map = HashMap::new();
+                for version in std::iter::once(&mut record.version).chain(&mut record.history) {
+                    if let Some(new_hash) = self.rehash_version(&skill_id, &version.content_hash)? {
+                        map.insert(version.content_hash.clone(), new_hash.clone());
+                        version.content_hash = new_hash;
+                    }
+                }
+                let content_hash = &record.version.content_hash;
+                if self.version_path(&skill_id, content_hash).is_dir() {
+                    self.activate_version(&skill_id, content_hash)?;
+                }
+                renamed.insert(skill_id.clone(), map);
+            }
+
+            record.digest_version = hash::DIGEST_VERSION;
+            self.save_record(&record)?;
+            self.records.insert(skill_id, record);
+        }
+
+        self.remap_sync_states(&renamed)
+    }
+
+    /// Move a stored version to its hash under the current digest format
+    ///
+    /// Returns the new hash, or `None` when the files are missing or no longer
+    /// match the old hash (left for [`Self::verify`] to report).
+    fn rehash_version(&self, skill_id: &SkillId, old_hash: &str) -> Result<Option<String>> {
+        let old_dir = self.version_path(skill_id, old_hash);
+        if !old_dir.is_dir() || hash::legacy_digest(&old_dir)? != old_hash {
+            return Ok(None);
+        }
+
+        let mut tree = self.objects.ingest(&old_dir)?;
+        let new_hash = tree.content_hash.clone();
+        let new_dir = self.version_path(skill_id, &new_hash);
+        if new_dir.exists() {
+            fs::remove_dir_all(&old_dir)?;
+        } else {
+            fs::rename(&old_dir, &new_dir)?;
+        }
+        let old_manifest = self.manifest_path(skill_id, old_hash);
+        if old_manifest.exists() {
+            fs::remove_file(&old_manifest)?;
+        }
+        tree.stamp(&new_dir);
+        tree.save(&self.manifest_path(skill_id, &new_hash))?;
+        Ok(Some(new_hash))
+    }
+
+    /// Rewrite the content hashes recorded in every sync state after a rehash
+    fn remap_sync_states(&self, renamed: &HashMap<SkillId, HashMap<String, String>>) -> Result<()> {
+        let mut paths = vec![self.config.root_dir.join("sync_state.json")];
+        if let Ok(projects) = fs::read_dir(self.config.root_dir.join("projects")) {
+            paths.extend(projects.flatten().map(|p| p.path().join("sync_state.json")));
+        }
+
+        for path in paths {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            // Unreadable states are moved aside when they are next loaded
+            let Ok(mut state) = serde_json::from_str::<SyncState>(&content) else {
+                continue;
+            };
+
+            let mut changed = false;
+            for tool_state in state.tools.values_mut() {
+                for (skill_id, status) in &mut tool_state.skills {
+                    let new_hash = renamed
+                        .get(skill_id)
+                        .and_then(|map| map.get(&status.version.content_hash));
+                    if let Some(new_hash) = new_hash {
+                        status.version.content_hash = new_hash.clone();
+                        changed = true;
+                    }
+                }
+            }
+            if changed {
+                let content = serde_json::to_string_pretty(&state)?;
+                write_atomic(&path, content.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop history entries beyond the retention limit, returning them so
+    /// their files can be deleted once the new record is in place
+    fn expire_history(&self, record: &mut InstallRecord) -> Vec<SkillVersion> {
+        if record.history.len() <= self.config.retained_versions {
+            return Vec::new();
+        }
+        record.history.split_off(self.config.retained_versions)
+    }
+
+    /// Delete the files and tree manifest of a stored version
+    fn remove_version(&self, skill_id: &SkillId, content_hash: &str) -> Result<()> {
+        let dir = self.version_path(skill_id, content_hash);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        let manifest = self.manifest_path(skill_id, content_hash);
+        if manifest.exists() {
+            fs::remove_file(&manifest)?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild a version directory from its tree manifest
+    ///
+    /// Returns `false` when there is no manifest or one of its objects is
+    /// missing or damaged.
+    fn rematerialize(&self, skill_id: &SkillId, content_hash: &str) -> Result<bool> {
+        let manifest = self.manifest_path(skill_id, content_hash);
+        let Ok(mut tree) = SkillTree::load(&manifest) else {
+            return Ok(false);
+        };
+        if tree.content_hash != content_hash || !self.objects.is_intact(&tree) {
+            return Ok(false);
+        }
+
+        let version_dir = self.version_path(skill_id, content_hash);
+        if version_dir.exists() {
+            fs::remove_dir_all(&version_dir)?;
+        }
+        self.objects.materialize(&mut tree, &version_dir)?;
+        tree.save(&manifest)?;
+        Ok(true)
+    }
+
     /// Remove a skill from the store
-    pub fn remove_skill(&mut self, skill_id: &str) -> Result<()> {
+    pub fn remove_skill(&mut self, skill_id: &SkillId) -> Result<()> {
+        let _guard = self.lock.exclusive()?;
         let skill_dir = self.skill_path(skill_id);
-        if skill_dir.exists() {
+        if skill_dir.is_symlink() {
+            remove_link(&skill_dir)?;
+        } else if skill_dir.exists() {
             fs::remove_dir_all(&skill_dir)?;
         }
 
+        for dir in [self.versions_dir(skill_id), self.manifests_dir(skill_id)] {
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+        }
+
         let metadata_path = self.metadata_path(skill_id);
         if metadata_path.exists() {
             fs::remove_file(&metadata_path)?;
@@ -138,14 +1058,15 @@ impl LocalStore {
     /// This records the skill in the store without copying files
     pub fn register_plugin_skill(
         &mut self,
-        skill_id: &str,
+        skill_id: &SkillId,
         source_path: &Path,
         tools: Vec<String>,
     ) -> Result<InstallRecord> {
         use crate::models::{SkillSource, SkillVersion};
 
+        let _guard = self.lock.exclusive()?;
         let record = InstallRecord {
-            skill_id: skill_id.to_string(),
+            skill_id: skill_id.clone(),
             version: SkillVersion::new("plugin", "from-claude-plugins"),
             installed_at: timestamp_now(),
             source: SkillSource::Local {
@@ -153,16 +1074,22 @@ impl LocalStore {
             },
             projected_tools: tools,
             scan_passed: true,
+            history: Vec::new(),
+            digest_version: hash::DIGEST_VERSION,
+            alias: self.records.get(skill_id).and_then(|r| r.alias.clone()),
+            disabled: self.records.get(skill_id).is_some_and(|r| r.disabled),
         };
 
         self.save_record(&record)?;
-        self.records.insert(skill_id.to_string(), record.clone());
+        self.records.insert(skill_id.clone(), record.clone());
 
         Ok(record)
     }
 
     /// Update projected tools for a skill
-    pub fn update_projected_tools(&mut self, skill_id: &str, tools: Vec<String>) -> Result<()> {
+    pub fn update_projected_tools(&mut self, skill_id: &SkillId, tools: Vec<String>) -> Result<()> {
+        let _guard = self.lock.exclusive()?;
+        self.refresh_record(skill_id);
         if let Some(record) = self.records.get_mut(skill_id) {
             record.projected_tools = tools;
             let record_clone = record.clone();
@@ -172,41 +1099,320 @@ impl LocalStore {
     }
 
     /// Calculate content hash for a skill directory
-    pub fn calculate_hash(&self, skill_id: &str) -> Result<String> {
+    ///
+    /// When the active version's files still match its tree manifest by size
+    /// and modification time, the recorded hash is returned without reading
+    /// any content.
+    pub fn calculate_hash(&self, skill_id: &SkillId) -> Result<String> {
         let skill_dir = self.skill_path(skill_id);
         if !skill_dir.exists() {
             return Err(Error::SkillNotFound(skill_id.to_string()));
         }
 
-        let mut hasher = Sha256::new();
+        if let Some(record) = self.records.get(skill_id) {
+            let content_hash = &record.version.content_hash;
+            let is_active = fs::canonicalize(&skill_dir).ok()
+                == fs::canonicalize(self.version_path(skill_id, content_hash)).ok();
+            if is_active {
+                if let Ok(tree) = SkillTree::load(&self.manifest_path(skill_id, content_hash)) {
+                    if &tree.content_hash == content_hash && tree.is_unchanged(&skill_dir) {
+                        return Ok(tree.content_hash);
+                    }
+                }
+            }
+        }
+
+        hash::digest(&skill_dir)
+    }
+
+    /// Check the store against its install records
+    ///
+    /// Every installed skill is re-hashed and compared with the recorded
+    /// `content_hash`; records without files and skill directories without a
+    /// record are reported too. Skills registered from Claude plugins live
+    /// outside the store and are not checked.
+    pub fn verify(&mut self) -> Result<VerifyReport> {
+        let _guard = self.lock.shared()?;
+        self.reload()?;
+
+        let mut report = VerifyReport::default();
+        let mut skill_ids: Vec<&SkillId> = self.records.keys().collect();
+        skill_ids.sort();
+
+        for skill_id in skill_ids {
+            let record = &self.records[skill_id];
+            if is_plugin_record(record) {
+                continue;
+            }
+            report.checked += 1;
+
+            let path = self.skill_path(skill_id);
+            let expected = record.version.content_hash.clone();
+            if !path.is_dir() {
+                report.issues.push(IntegrityIssue {
+                    skill_id: skill_id.clone(),
+                    issue_type: IntegrityIssueType::MissingFiles,
+                    path,
+                    expected_hash: Some(expected),
+                    actual_hash: None,
+                    repaired: false,
+                    repair_error: None,
+                });
+                continue;
+            }
+
+            let actual = hash::digest(&path)?;
+            if actual != expected {
+                report.issues.push(IntegrityIssue {
+                    skill_id: skill_id.clone(),
+                    issue_type: IntegrityIssueType::Modified,
+                    path,
+                    expected_hash: Some(expected),
+                    actual_hash: Some(actual),
+                    repaired: false,
+                    repair_error: None,
+                });
+            }
+        }
+
+        // Left for the user: rewriting them could lose data a newer version wrote
+        let mut unreadable: Vec<(&SkillId, &String)> = self.unreadable.iter().collect();
+        unreadable.sort();
+        for (skill_id, error) in unreadable {
+            report.issues.push(IntegrityIssue {
+                skill_id: skill_id.clone(),
+                issue_type: IntegrityIssueType::UnreadableRecord,
+                path: self.metadata_path(skill_id),
+                expected_hash: None,
+                actual_hash: None,
+                repaired: false,
+                repair_error: Some(error.clone()),
+            });
+        }
+
+        for skill_id in self.orphaned_skill_ids()? {
+            let path = self.skill_path(&skill_id);
+            let path = if path.exists() || path.is_symlink() {
+                path
+            } else {
+                self.versions_dir(&skill_id)
+            };
+            report.issues.push(IntegrityIssue {
+                skill_id,
+                issue_type: IntegrityIssueType::Orphaned,
+                path,
+                expected_hash: None,
+                actual_hash: None,
+                repaired: false,
+                repair_error: None,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Repair the issues of a [`Self::verify`] report, marking each as
+    /// repaired or recording why it could not be
+    ///
+    /// Missing files are restored from the stored version when it is intact,
+    /// otherwise missing and modified skills are re-fetched from their
+    /// recorded source and must hash to the recorded content hash. Orphaned
+    /// directories are registered as local skills. Re-fetched and orphaned
+    /// content is security-scanned first (see [`Self::import_scanned`]).
+    pub async fn repair(&mut self, report: &mut VerifyReport, registries: &AggregatedRegistry) {
+        for issue in &mut report.issues {
+            let result = match issue.issue_type {
+                IntegrityIssueType::MissingFiles | IntegrityIssueType::Modified => {
+                    self.restore_skill(&issue.skill_id, registries).await
+                }
+                IntegrityIssueType::Orphaned => self.register_orphan(&issue.skill_id),
+                IntegrityIssueType::UnreadableRecord => Err(Error::StoreError(format!(
+                    "{} cannot be read; fix or remove it by hand",
+                    issue.path.display()
+                ))),
+            };
+
+            match result {
+                Ok(()) => issue.repaired = true,
+                Err(e) => issue.repair_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    /// Reactivate an intact stored version or re-fetch a skill from its source
+    async fn restore_skill(
+        &mut self,
+        skill_id: &SkillId,
+        registries: &AggregatedRegistry,
+    ) -> Result<()> {
+        use crate::lockfile::{install_locked, Lockfile};
+        use crate::manifest::ManifestSkillStatus;
 
-        for entry in WalkDir::new(&skill_dir).sort_by_file_name() {
-            let entry = entry.map_err(|e| Error::Io(e.into()))?;
-            if entry.file_type().is_file() {
-                let content = fs::read(entry.path())?;
-                hasher.update(&content);
+        let record = self
+            .get_record(skill_id)
+            .cloned()
+            .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
+        let version_dir = self.version_path(skill_id, &record.version.content_hash);
+        if version_dir.is_dir() && hash::digest(&version_dir)? == record.version.content_hash {
+            let _guard = self.lock.exclusive()?;
+            return self.activate_version(skill_id, &record.version.content_hash);
+        }
+        {
+            let _guard = self.lock.exclusive()?;
+            if self.rematerialize(skill_id, &record.version.content_hash)? {
+                return self.activate_version(skill_id, &record.version.content_hash);
             }
         }
 
-        Ok(hex::encode(hasher.finalize()))
+        let lockfile = Lockfile::from_store(self, Some(std::slice::from_ref(skill_id)))?;
+        let root = self.root_dir().to_path_buf();
+        match install_locked(&lockfile, &root, self, registries)
+            .await
+            .pop()
+        {
+            Some((_, ManifestSkillStatus::Failed { error })) => Err(Error::IntegrityError(
+                format!("could not restore {} from its source: {}", skill_id, error),
+            )),
+            _ => Ok(()),
+        }
     }
 
-    fn metadata_path(&self, skill_id: &str) -> PathBuf {
-        self.config
-            .root_dir
-            .join("metadata")
-            .join(format!("{}.json", skill_id))
+    /// Record an orphaned skill directory as a local skill once it passes a
+    /// security scan
+    fn register_orphan(&mut self, skill_id: &SkillId) -> Result<()> {
+        let link = self.skill_path(skill_id);
+        let path = if link.is_dir() {
+            Some(link.clone())
+        } else {
+            newest_subdir(&self.versions_dir(skill_id))
+        };
+
+        match path {
+            Some(path) => {
+                let skill = crate::sync::collected_skill(skill_id, &path);
+                self.import_scanned(&skill, &path).map(|_| ())
+            }
+            None => {
+                // Nothing left to register: drop the dangling link
+                let _guard = self.lock.exclusive()?;
+                remove_link(&link)
+            }
+        }
+    }
+
+    /// Skill directories under `skills/` or `versions/` that have no record
+    ///
+    /// Names that do not decode to a skill ID are left to [`crate::gc`].
+    fn orphaned_skill_ids(&self) -> Result<Vec<SkillId>> {
+        let mut orphans = Vec::new();
+        for dir in [self.skills_dir(), self.config.root_dir.join("versions")] {
+            for entry in fs::read_dir(&dir)? {
+                let name = entry?.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') {
+                    continue;
+                }
+                if let Ok(skill_id) = SkillId::from_file_name(&name) {
+                    if !self.records.contains_key(&skill_id)
+                        && !self.unreadable.contains_key(&skill_id)
+                    {
+                        orphans.push(skill_id);
+                    }
+                }
+            }
+        }
+        orphans.sort();
+        orphans.dedup();
+        Ok(orphans)
+    }
+
+    /// Get the path of the persisted sync state for a scope
+    ///
+    /// Global projections live in `sync_state.json`; each project gets its own
+    /// `projects/<hash>/sync_state.json` keyed by the project root.
+    pub fn sync_state_path(&self, scope: &SyncScope) -> PathBuf {
+        match scope {
+            SyncScope::Global => self.config.root_dir.join("sync_state.json"),
+            SyncScope::Project { root } => {
+                let hash = hex::encode(Sha256::digest(root.to_string_lossy().as_bytes()));
+                self.config
+                    .root_dir
+                    .join("projects")
+                    .join(&hash[0..12])
+                    .join("sync_state.json")
+            }
+        }
+    }
+
+    /// Load the persisted sync state for a scope
+    ///
+    /// A missing file yields an empty state. A file that cannot be parsed, or
+    /// that was written by a newer schema, is moved aside to
+    /// `sync_state.json.corrupt-<timestamp>` and an empty state is returned.
+    pub fn load_sync_state(&self, scope: &SyncScope) -> SyncState {
+        let path = self.sync_state_path(scope);
+        if !path.exists() {
+            return SyncState::new(scope.clone());
+        }
+
+        let parsed = fs::read_to_string(&path)
+            .map_err(Error::from)
+            .and_then(|content| serde_json::from_str::<SyncState>(&content).map_err(Error::from))
+            .and_then(|state| {
+                if state.schema_version > SYNC_STATE_SCHEMA_VERSION {
+                    Err(Error::StoreError(format!(
+                        "unsupported sync state schema version {}",
+                        state.schema_version
+                    )))
+                } else {
+                    Ok(state)
+                }
+            });
+
+        match parsed {
+            Ok(mut state) => {
+                state.schema_version = SYNC_STATE_SCHEMA_VERSION;
+                state.scope = scope.clone();
+                state
+            }
+            Err(e) => {
+                let backup =
+                    path.with_file_name(format!("sync_state.json.corrupt-{}", timestamp_now()));
+                tracing::warn!(
+                    "Sync state at {} is unreadable ({}), moving it to {}",
+                    path.display(),
+                    e,
+                    backup.display()
+                );
+                let _ = fs::rename(&path, &backup);
+                SyncState::new(scope.clone())
+            }
+        }
+    }
+
+    /// Atomically persist a sync state under its scope
+    pub fn save_sync_state(&self, state: &SyncState) -> Result<()> {
+        let _guard = self.lock.exclusive()?;
+        let content = serde_json::to_string_pretty(state)?;
+        write_atomic(&self.sync_state_path(&state.scope), content.as_bytes())
+    }
+
+    /// Get the directory holding install records
+    pub fn metadata_dir(&self) -> PathBuf {
+        self.config.root_dir.join("metadata")
+    }
+
+    pub(crate) fn metadata_path(&self, skill_id: &SkillId) -> PathBuf {
+        self.metadata_dir()
+            .join(format!("{}.json", skill_id.file_name()))
     }
 
     fn save_record(&self, record: &InstallRecord) -> Result<()> {
-        let path = self.metadata_path(&record.skill_id);
-        let content = serde_json::to_string_pretty(record)?;
-        fs::write(path, content)?;
-        Ok(())
+        schema::save(&self.metadata_path(&record.skill_id), record)
     }
 
     fn load_records(&mut self) -> Result<()> {
-        let metadata_dir = self.config.root_dir.join("metadata");
+        self.unreadable.clear();
+        let metadata_dir = self.metadata_dir();
         if !metadata_dir.exists() {
             return Ok(());
         }
@@ -215,10 +1421,22 @@ impl LocalStore {
             let entry = entry?;
             let path = entry.path();
             if path.extension().is_some_and(|e| e == "json") {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok(record) = serde_json::from_str::<InstallRecord>(&content) {
+                // One bad record must not make the whole store unusable; it is
+                // remembered so `verify` reports it instead of an orphan
+                match schema::load::<InstallRecord>(&path) {
+                    Ok(Some(record)) => {
                         self.records.insert(record.skill_id.clone(), record);
                     }
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!("Skipping install record {}: {}", path.display(), e);
+                        let skill_id = path
+                            .file_stem()
+                            .and_then(|stem| SkillId::from_file_name(&stem.to_string_lossy()).ok());
+                        if let Some(skill_id) = skill_id {
+                            self.unreadable.insert(skill_id, e.to_string());
+                        }
+                    }
                 }
             }
         }
@@ -227,7 +1445,63 @@ impl LocalStore {
     }
 }
 
+/// Install records (`metadata/<id>.json`); v1 only adds the version stamp
+impl Versioned for InstallRecord {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(_from: u32, document: serde_json::Value) -> Result<serde_json::Value> {
+        Ok(document)
+    }
+}
+
+/// Whether a record points at a Claude plugin outside the store
+pub(crate) fn is_plugin_record(record: &InstallRecord) -> bool {
+    record.version.content_hash == "from-claude-plugins"
+}
+
+/// Most recently modified subdirectory of a directory
+fn newest_subdir(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+        .map(|e| e.path())
+}
+
+/// Unique ID for a staged install
+fn next_txn_id(skill_id: &SkillId) -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    format!(
+        "{}-{}-{}-{}",
+        skill_id.file_name(),
+        timestamp_now(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Directory name of a stored version
+pub(crate) fn version_dir_name(content_hash: &str) -> &str {
+    &content_hash[..content_hash.len().min(16)]
+}
+
+/// Remove a symlink (directory symlinks on Windows are removed as directories)
+pub(crate) fn remove_link(path: &Path) -> Result<()> {
+    #[cfg(windows)]
+    {
+        if fs::remove_dir(path).is_ok() {
+            return Ok(());
+        }
+    }
+    fs::remove_file(path)?;
+    Ok(())
+}
+
 /// Recursively copy a directory
+#[cfg(windows)]
 fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
@@ -245,6 +1519,29 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write a file atomically by writing a sibling temp file and renaming it over the target
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
 fn timestamp_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
@@ -252,3 +1549,593 @@ fn timestamp_now() -> String {
         .unwrap_or_default();
     format!("{}", duration.as_secs())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        SkillSource, SkillSyncStatus, SkillVersion, SyncStrategy, ToolSyncState, ToolType,
+    };
+
+    fn skill_id(id: &str) -> SkillId {
+        SkillId::new(id).unwrap()
+    }
+
+    fn temp_store() -> (tempfile::TempDir, LocalStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        (dir, store)
+    }
+
+    /// Write a demo skill with the given SKILL.md body and import it
+    async fn import_demo(store: &mut LocalStore, root: &Path, version: &str, body: &str) {
+        let source = root.join("source").join(version);
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), body).unwrap();
+
+        let skill = Skill {
+            id: skill_id("demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new(version, ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        store.import_skill(&skill, &source).await.unwrap();
+    }
+
+    fn active_body(store: &LocalStore) -> String {
+        fs::read_to_string(store.skill_path(&skill_id("demo")).join("SKILL.md")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_versions_are_kept_side_by_side() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+        import_demo(&mut store, dir.path(), "2.0.0", "two").await;
+
+        assert_eq!(active_body(&store), "two");
+        let record = store.get_record(&skill_id("demo")).unwrap();
+        assert!(!record.version.content_hash.is_empty());
+        assert_eq!(record.history.len(), 1);
+        assert!(store
+            .version_path(&skill_id("demo"), &record.history[0].content_hash)
+            .is_dir());
+
+        let record = store.rollback(&skill_id("demo"), None).unwrap();
+        assert_eq!(record.version.version, "1.0.0");
+        assert_eq!(active_body(&store), "one");
+
+        // The rolled-back version stays available
+        store.rollback(&skill_id("demo"), Some("2.0.0")).unwrap();
+        assert_eq!(active_body(&store), "two");
+        assert!(store.rollback(&skill_id("demo"), Some("9.9.9")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retention_prunes_old_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            retained_versions: 1,
+            ..Default::default()
+        })
+        .unwrap();
+
+        for (version, body) in [("1.0.0", "one"), ("2.0.0", "two"), ("3.0.0", "three")] {
+            import_demo(&mut store, dir.path(), version, body).await;
+        }
+
+        let record = store.get_record(&skill_id("demo")).unwrap();
+        assert_eq!(record.history.len(), 1);
+        assert_eq!(record.history[0].version, "2.0.0");
+        assert_eq!(
+            fs::read_dir(store.versions_dir(&skill_id("demo")))
+                .unwrap()
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_legacy_layout_is_migrated() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+
+        // Recreate the in-place layout used by older stores
+        let skill_dir = store.skill_path(&skill_id("demo"));
+        fs::remove_file(&skill_dir).unwrap();
+        fs::remove_dir_all(store.versions_dir(&skill_id("demo"))).unwrap();
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "one").unwrap();
+
+        import_demo(&mut store, dir.path(), "2.0.0", "two").await;
+        assert!(store.skill_path(&skill_id("demo")).is_symlink());
+
+        store.rollback(&skill_id("demo"), None).unwrap();
+        assert_eq!(active_body(&store), "one");
+    }
+
+    #[test]
+    fn test_sync_state_round_trip() {
+        let (_dir, store) = temp_store();
+        assert!(store.load_sync_state(&SyncScope::Global).tools.is_empty());
+
+        let mut state = SyncState::default();
+        let mut skills = HashMap::new();
+        skills.insert(
+            skill_id("demo"),
+            SkillSyncStatus {
+                skill_id: skill_id("demo"),
+                version: SkillVersion::new("1.0.0", "abc"),
+                strategy: SyncStrategy::Link,
+                target_path: PathBuf::from("/tmp/demo"),
+                drift: None,
+                format: Default::default(),
+                rendered_hash: None,
+            },
+        );
+        state.tools.insert(
+            ToolType::Claude.to_string(),
+            ToolSyncState {
+                tool: ToolType::Claude,
+                skills,
+                last_sync: None,
+            },
+        );
+        store.save_sync_state(&state).unwrap();
+
+        let loaded = store.load_sync_state(&SyncScope::Global);
+        assert_eq!(loaded.schema_version, SYNC_STATE_SCHEMA_VERSION);
+        let tool_state = &loaded.tools[&ToolType::Claude.to_string()];
+        assert_eq!(tool_state.skills["demo"].strategy, SyncStrategy::Link);
+    }
+
+    #[test]
+    fn test_corrupt_sync_state_is_moved_aside() {
+        let (_dir, store) = temp_store();
+        fs::write(store.sync_state_path(&SyncScope::Global), "{ not json").unwrap();
+
+        let state = store.load_sync_state(&SyncScope::Global);
+        assert!(state.tools.is_empty());
+        assert!(!store.sync_state_path(&SyncScope::Global).exists());
+
+        let backups: Vec<_> = fs::read_dir(store.root_dir())
+            .unwrap()
+            .flatten()
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with("sync_state.json.corrupt-")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+    }
+
+    #[test]
+    fn test_project_sync_state_is_separate() {
+        let (dir, store) = temp_store();
+        let scope = SyncScope::Project {
+            root: dir.path().join("repo"),
+        };
+        store
+            .save_sync_state(&SyncState::new(scope.clone()))
+            .unwrap();
+
+        let project_path = store.sync_state_path(&scope);
+        assert!(project_path.exists());
+        assert_ne!(project_path, store.sync_state_path(&SyncScope::Global));
+        assert_eq!(store.load_sync_state(&scope).scope, scope);
+    }
+
+    fn reopen(store: LocalStore) -> LocalStore {
+        LocalStore::new(store.config.clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_record_is_reported_not_fatal() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+        let record_path = store.metadata_path(&skill_id("demo"));
+        fs::write(&record_path, "{ not json").unwrap();
+
+        let mut store = reopen(store);
+        assert!(!store.is_installed(&skill_id("demo")));
+        let mut report = store.verify().unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(
+            report.issues[0].issue_type,
+            IntegrityIssueType::UnreadableRecord
+        );
+
+        // Repair does not register the skill over the record
+        store.repair(&mut report, &AggregatedRegistry::new()).await;
+        assert_eq!(report.unresolved().count(), 1);
+        assert_eq!(fs::read_to_string(&record_path).unwrap(), "{ not json");
+    }
+
+    #[tokio::test]
+    async fn test_older_digests_are_migrated() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "# One").await;
+        import_demo(&mut store, dir.path(), "2.0.0", "# Two").await;
+
+        // Put the store back into the contents-only digest format
+        let mut record = store.get_record(&skill_id("demo")).unwrap().clone();
+        for version in std::iter::once(&mut record.version).chain(&mut record.history) {
+            let current_dir = store.version_path(&skill_id("demo"), &version.content_hash);
+            let legacy = hash::legacy_digest(&current_dir).unwrap();
+            fs::rename(&current_dir, store.version_path(&skill_id("demo"), &legacy)).unwrap();
+            fs::remove_file(store.manifest_path(&skill_id("demo"), &version.content_hash)).unwrap();
+            version.content_hash = legacy;
+        }
+        record.digest_version = 0;
+        store.save_record(&record).unwrap();
+        store
+            .activate_version(&skill_id("demo"), &record.version.content_hash)
+            .unwrap();
+
+        let mut state = SyncState::default();
+        let mut skills = HashMap::new();
+        skills.insert(
+            skill_id("demo"),
+            SkillSyncStatus {
+                skill_id: skill_id("demo"),
+                version: record.version.clone(),
+                strategy: SyncStrategy::Copy,
+                target_path: dir.path().join("claude").join("demo"),
+                drift: None,
+                format: Default::default(),
+                rendered_hash: None,
+            },
+        );
+        state.tools.insert(
+            ToolType::Claude.to_string(),
+            ToolSyncState {
+                tool: ToolType::Claude,
+                skills,
+                last_sync: None,
+            },
+        );
+        store.save_sync_state(&state).unwrap();
+
+        let mut store = reopen(store);
+        let migrated = store.get_record(&skill_id("demo")).unwrap().clone();
+        assert_eq!(migrated.digest_version, hash::DIGEST_VERSION);
+        assert_eq!(
+            migrated.version.content_hash,
+            hash::digest(&store.skill_path(&skill_id("demo"))).unwrap()
+        );
+        assert!(store.verify().unwrap().is_clean());
+
+        let state = store.load_sync_state(&SyncScope::Global);
+        let status = &state.tools[&ToolType::Claude.to_string()].skills["demo"];
+        assert_eq!(status.version.content_hash, migrated.version.content_hash);
+
+        // The older version was moved along and can still be restored
+        store.rollback(&skill_id("demo"), None).unwrap();
+        assert_eq!(active_body(&store), "# One");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_raw_skill_names_are_migrated() {
+        let (dir, mut store) = temp_store();
+        let source = dir.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "plugin").unwrap();
+
+        let id = skill_id("plugin@market:review");
+        let skill = Skill {
+            id: id.clone(),
+            name: "Review".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        store.import_skill(&skill, &source).await.unwrap();
+        let content_hash = store.get_record(&id).unwrap().version.content_hash.clone();
+
+        // Recreate the layout of stores that used the raw ID as the file name
+        let root = store.root_dir().to_path_buf();
+        for dir in ["versions", "manifests"] {
+            fs::rename(
+                root.join(dir).join(id.file_name()),
+                root.join(dir).join(id.as_str()),
+            )
+            .unwrap();
+        }
+        fs::remove_file(store.skill_path(&id)).unwrap();
+        std::os::unix::fs::symlink(
+            Path::new("../versions")
+                .join(id.as_str())
+                .join(version_dir_name(&content_hash)),
+            root.join("skills").join(id.as_str()),
+        )
+        .unwrap();
+        fs::rename(
+            store.metadata_path(&id),
+            root.join("metadata").join("plugin@market:review.json"),
+        )
+        .unwrap();
+
+        let mut store = reopen(store);
+        assert!(store.is_installed(&id));
+        assert!(store.metadata_path(&id).exists());
+        assert!(!root.join("skills").join(id.as_str()).is_symlink());
+        assert!(!root.join("versions").join(id.as_str()).exists());
+        assert_eq!(
+            fs::read_to_string(store.skill_path(&id).join("SKILL.md")).unwrap(),
+            "plugin"
+        );
+        assert!(store.verify().unwrap().is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_records_are_upgraded() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "# One").await;
+        let id = skill_id("demo");
+        let path = store.metadata_path(&id);
+
+        // Records written before versioning have no schema_version
+        let legacy = serde_json::to_string(store.get_record(&id).unwrap()).unwrap();
+        fs::write(&path, &legacy).unwrap();
+
+        let store = reopen(store);
+        assert!(store.is_installed(&id));
+        let upgraded: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(upgraded["schema_version"], 1);
+        let backup = path.with_file_name(format!("{}.json.v0.bak", id.file_name()));
+        assert_eq!(fs::read_to_string(backup).unwrap(), legacy);
+
+        // A damaged record is reported by verify instead of vanishing
+        fs::write(&path, "{").unwrap();
+        let mut store = reopen(store);
+        assert!(!store.is_installed(&id));
+        assert_eq!(
+            store.verify().unwrap().issues[0].issue_type,
+            IntegrityIssueType::UnreadableRecord
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aborted_install_leaves_nothing_behind() {
+        let (dir, mut store) = temp_store();
+        let source = dir.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "one").unwrap();
+
+        let skill = Skill {
+            id: skill_id("demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let staged = store.stage_skill(&skill, &source).unwrap();
+        assert!(staged.path().join("SKILL.md").exists());
+        assert!(!store.skill_path(&skill_id("demo")).exists());
+
+        store.abort_staged(staged).unwrap();
+        assert!(!store.is_installed(&skill_id("demo")));
+        assert!(store.load_journal().entries.is_empty());
+
+        // An install interrupted before commit is discarded on the next start
+        // (a crashed process releases its transaction lock)
+        let crashed = store.stage_skill(&skill, &source).unwrap();
+        drop(crashed);
+        let store = reopen(store);
+        assert!(!store.is_installed(&skill_id("demo")));
+        assert!(!store.skill_path(&skill_id("demo")).exists());
+        assert_eq!(fs::read_dir(store.staging_dir()).unwrap().count(), 1); // journal only
+    }
+
+    #[test]
+    fn test_scanned_import_blocks_flagged_content() {
+        let (dir, mut store) = temp_store();
+        let source = dir.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Demo").unwrap();
+        fs::write(source.join("helper.exe"), "MZ").unwrap();
+
+        let skill = Skill {
+            id: skill_id("demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let err = store.import_scanned(&skill, &source).unwrap_err();
+        assert!(matches!(err, Error::PolicyViolation(_)));
+        assert!(!store.is_installed(&skill_id("demo")));
+        assert!(store.load_journal().entries.is_empty());
+
+        fs::remove_file(source.join("helper.exe")).unwrap();
+        store.import_scanned(&skill, &source).unwrap();
+        assert!(store.is_installed(&skill_id("demo")));
+    }
+
+    #[tokio::test]
+    async fn test_ids_differing_only_in_case_are_rejected() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+
+        let source = dir.path().join("source").join("1.0.0");
+        let mut skill = Skill {
+            id: skill_id("Demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        assert!(store.import_skill(&skill, &source).await.is_err());
+        assert!(!store.is_installed(&skill_id("Demo")));
+
+        // Re-importing the installed ID is an update, not a collision
+        skill.id = skill_id("demo");
+        store.import_skill(&skill, &source).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_commit_is_rolled_forward() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+        let record = store.get_record(&skill_id("demo")).unwrap().clone();
+
+        // Simulate a crash after the files were moved but before activation
+        fs::remove_file(store.skill_path(&skill_id("demo"))).unwrap();
+        fs::remove_file(store.metadata_path(&skill_id("demo"))).unwrap();
+        store
+            .save_journal(&StagingJournal {
+                entries: vec![StagingEntry {
+                    txn_id: "demo-0-0-0".to_string(),
+                    skill_id: skill_id("demo"),
+                    started_at: timestamp_now(),
+                    commit: Some(record),
+                }],
+            })
+            .unwrap();
+
+        let store = reopen(store);
+        assert!(store.is_installed(&skill_id("demo")));
+        assert_eq!(active_body(&store), "one");
+        assert!(store.load_journal().entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_and_repairs_damage() {
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+        assert!(store.verify().unwrap().is_clean());
+
+        // Tampered files are re-fetched from the recorded local source
+        fs::write(store.skill_path(&skill_id("demo")).join("SKILL.md"), "evil").unwrap();
+        // A skill directory nobody recorded
+        let orphan = store.skills_dir().join("stray");
+        fs::create_dir_all(&orphan).unwrap();
+        fs::write(orphan.join("SKILL.md"), "stray").unwrap();
+
+        let mut report = store.verify().unwrap();
+        let mut kinds: Vec<_> = report.issues.iter().map(|i| i.issue_type).collect();
+        kinds.sort_by_key(|k| k.to_string());
+        assert_eq!(
+            kinds,
+            vec![IntegrityIssueType::Modified, IntegrityIssueType::Orphaned]
+        );
+
+        store.repair(&mut report, &AggregatedRegistry::new()).await;
+        assert_eq!(report.unresolved().count(), 0);
+        assert_eq!(active_body(&store), "one");
+        assert!(store.is_installed(&skill_id("stray")));
+        assert!(store.verify().unwrap().is_clean());
+
+        // Missing files come back from the intact stored version
+        remove_link(&store.skill_path(&skill_id("demo"))).unwrap();
+        let mut report = store.verify().unwrap();
+        assert_eq!(
+            report.issues[0].issue_type,
+            IntegrityIssueType::MissingFiles
+        );
+        store.repair(&mut report, &AggregatedRegistry::new()).await;
+        assert_eq!(active_body(&store), "one");
+
+        // Orphans the security scan blocks are not registered
+        let blocked = store.skills_dir().join("blocked");
+        fs::create_dir_all(&blocked).unwrap();
+        fs::write(blocked.join("SKILL.md"), "blocked").unwrap();
+        fs::write(blocked.join("helper.exe"), "MZ").unwrap();
+        let mut report = store.verify().unwrap();
+        store.repair(&mut report, &AggregatedRegistry::new()).await;
+        assert_eq!(report.unresolved().count(), 1);
+        assert!(!store.is_installed(&skill_id("blocked")));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_versions_share_objects() {
+        use std::os::unix::fs::MetadataExt;
+
+        let (dir, mut store) = temp_store();
+        import_demo(&mut store, dir.path(), "1.0.0", "one").await;
+        let old_hash = store
+            .get_record(&skill_id("demo"))
+            .unwrap()
+            .version
+            .content_hash
+            .clone();
+        import_demo(&mut store, dir.path(), "2.0.0", "two").await;
+        import_demo(&mut store, dir.path(), "3.0.0", "one").await;
+
+        // Identical content is materialised from the same object
+        let object = store
+            .objects()
+            .object_path(&hex::encode(Sha256::digest(b"one")));
+        let active = store.skill_path(&skill_id("demo")).join("SKILL.md");
+        assert_eq!(
+            fs::metadata(&object).unwrap().ino(),
+            fs::metadata(&active).unwrap().ino()
+        );
+        assert_eq!(store.objects().list().len(), 2);
+
+        // The manifest answers unchanged directories, edits fall back to hashing
+        assert_eq!(store.calculate_hash(&skill_id("demo")).unwrap(), old_hash);
+        fs::remove_file(&active).unwrap();
+        fs::write(&active, "edited").unwrap();
+        assert_ne!(store.calculate_hash(&skill_id("demo")).unwrap(), old_hash);
+
+        // A lost version directory is rebuilt from its manifest
+        let version_dir = store.version_path(&skill_id("demo"), &old_hash);
+        fs::remove_dir_all(&version_dir).unwrap();
+        let record = store.rollback(&skill_id("demo"), Some("2.0.0")).unwrap();
+        assert_eq!(record.version.version, "2.0.0");
+        store.rollback(&skill_id("demo"), Some("3.0.0")).unwrap();
+        assert_eq!(active_body(&store), "one");
+    }
+}
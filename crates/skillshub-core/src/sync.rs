@@ -1,33 +1,64 @@
 //! Sync Engine - multi-tool synchronization
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use crate::adapters::ToolAdapter;
+use crate::backup::{self, BackupEntry};
 use crate::error::{Error, Result};
+use crate::hash;
+use crate::index::{self, IndexEntry};
+use crate::lock::StoreLockGuard;
 use crate::models::{
-    DriftInfo, DriftType, HubSyncStatus, ScannedSkill, SkillSyncStatus, SyncAction, SyncActionType,
-    SyncPlan, SyncState, SyncStrategy, ToolProfile, ToolSyncState, ToolType,
+    CollectAction, CollectConflict, CollectVariant, ConflictResolution, DriftInfo, DriftType,
+    FileChange, FileDiff, HubSyncStatus, RenderFormat, ScannedSkill, Skill, SkillId, SkillSource,
+    SkillSyncStatus, SkillVersion, SyncAction, SyncActionType, SyncPlan, SyncScope, SyncState,
+    SyncStrategy, ToolProfile, ToolSyncState, ToolType,
 };
+use crate::registry::parse_skill_md;
+use crate::render;
 use crate::store::LocalStore;
 
 /// Sync engine for managing multi-tool synchronization
 pub struct SyncEngine {
     store: LocalStore,
     adapters: Vec<Box<dyn ToolAdapter>>,
+    profiles: HashMap<ToolType, ToolProfile>,
+    backup_unmanaged: bool,
+    backups: Vec<BackupEntry>,
+    scope: SyncScope,
     state: SyncState,
 }
 
 impl SyncEngine {
-    /// Create a new sync engine
+    /// Create a new sync engine for the tools' global directories,
+    /// loading the persisted sync state from the store
     pub fn new(store: LocalStore) -> Self {
+        Self::with_scope(store, SyncScope::Global)
+    }
+
+    /// Create a sync engine that projects into a repository's tool directories
+    /// (e.g. `<root>/.claude/skills/`), with its own sync state
+    pub fn for_project(store: LocalStore, project_root: impl Into<PathBuf>) -> Self {
+        let root = project_root.into();
+        let root = root.canonicalize().unwrap_or(root);
+        Self::with_scope(store, SyncScope::Project { root })
+    }
+
+    /// Create a sync engine for an explicit scope
+    pub fn with_scope(store: LocalStore, scope: SyncScope) -> Self {
+        let state = store.load_sync_state(&scope);
         Self {
             store,
             adapters: Vec::new(),
-            state: SyncState::default(),
+            profiles: HashMap::new(),
+            backup_unmanaged: true,
+            backups: Vec::new(),
+            scope,
+            state,
         }
     }
 
@@ -51,12 +82,85 @@ impl SyncEngine {
         &mut self.store
     }
 
+    /// Get the scope this engine projects into
+    pub fn scope(&self) -> &SyncScope {
+        &self.scope
+    }
+
+    /// Get the current sync state
+    pub fn state(&self) -> &SyncState {
+        &self.state
+    }
+
+    /// Choose what happens to entries SkillsHub did not create that sit where
+    /// a skill is synced: moved to a backup (the default) or reported as an
+    /// error and left alone
+    pub fn set_backup_unmanaged(&mut self, enabled: bool) {
+        self.backup_unmanaged = enabled;
+    }
+
+    /// Backups this engine made of unmanaged entries it replaced
+    pub fn backups(&self) -> &[BackupEntry] {
+        &self.backups
+    }
+
+    /// Persist the current sync state to the store
+    pub fn save_state(&self) -> Result<()> {
+        self.store.save_sync_state(&self.state)?;
+        self.update_indexes();
+        Ok(())
+    }
+
+    /// Regenerate the index of every tool whose [`SkillStructure`](crate::adapters::SkillStructure)
+    /// needs one, from the skills recorded for it in the current scope
+    ///
+    /// The index file is resolved against the tool's skills directory. Runs
+    /// after every change to the sync state; a failure to write an index is
+    /// logged and does not undo the sync. Returns the index files that changed.
+    pub fn update_indexes(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for adapter in &self.adapters {
+            let structure = adapter.skill_structure();
+            let Some(index_file) = structure.index_file.filter(|_| structure.needs_index) else {
+                continue;
+            };
+            let Ok(skills_dir) = self.target_dir_path(adapter.as_ref()) else {
+                continue;
+            };
+            let index_path = skills_dir.join(index_file);
+
+            let entries: Vec<IndexEntry> = self
+                .state
+                .tools
+                .get(&adapter.tool_type().to_string())
+                .map(|ts| {
+                    ts.skills
+                        .values()
+                        .filter(|status| status.target_path.exists())
+                        .map(|status| index::entry(&index_path, &status.target_path))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            match index::update(&index_path, &entries) {
+                Ok(true) => changed.push(index_path),
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Failed to update {}: {}", index_path.display(), e),
+            }
+        }
+        changed
+    }
+
     /// Detect which tools are available
     pub fn detect_tools(&self) -> Vec<ToolProfile> {
         let mut profiles = Vec::new();
 
         for adapter in &self.adapters {
-            let mut profile = ToolProfile::new(adapter.tool_type());
+            let mut profile = self
+                .profiles
+                .get(&adapter.tool_type())
+                .cloned()
+                .unwrap_or_else(|| ToolProfile::new(adapter.tool_type()));
             profile.detected = adapter.detect();
             profiles.push(profile);
         }
@@ -67,23 +171,34 @@ impl SyncEngine {
     /// Create a sync plan for a skill
     pub fn plan_sync(
         &self,
-        skill_id: &str,
+        skill_id: &SkillId,
         tools: &[ToolType],
         strategy: SyncStrategy,
     ) -> Result<SyncPlan> {
         let mut plan = SyncPlan::new();
 
-        // Check if skill exists in store
-        if !self.store.is_installed(skill_id) {
+        // Check if skill exists in store; skills deleted from it can still be
+        // removed from the tools they were projected into
+        let installed = self.store.is_installed(skill_id);
+        let projected = self
+            .state
+            .tools
+            .values()
+            .any(|ts| ts.skills.contains_key(skill_id));
+        if !installed && !projected {
             return Err(Error::SkillNotFound(skill_id.to_string()));
         }
 
+        // Disabled and deleted skills are only ever removed from tools
+        let removing = !installed || self.store.is_disabled(skill_id);
+
         for tool in tools {
             let adapter = self.get_adapter(*tool)?;
 
             if !adapter.detect() {
                 continue;
             }
+            let target_dir = self.target_dir_path(adapter)?;
 
             // Check current state
             let current = self
@@ -92,10 +207,22 @@ impl SyncEngine {
                 .get(&tool.to_string())
                 .and_then(|ts| ts.skills.get(skill_id));
 
+            let desired_path = self.projection_path(*tool, &target_dir, skill_id);
+            let mut target_path = current
+                .map(|status| status.target_path.clone())
+                .unwrap_or_else(|| desired_path.clone());
+
             let action = match current {
+                None if removing => continue,
+                Some(_) if removing => SyncActionType::Remove,
                 None => SyncActionType::Add,
+                // The tool's render format changed; the projection moves
+                Some(status) if status.format != self.render_format(*tool) => {
+                    target_path = desired_path;
+                    SyncActionType::Update
+                }
                 Some(status) => {
-                    if status.drift.is_some() {
+                    if status.drift.is_some() || self.detect_drift(skill_id, status).is_some() {
                         SyncActionType::Repair
                     } else {
                         // Check if version changed
@@ -109,19 +236,23 @@ impl SyncEngine {
                 }
             };
 
+            let overwrites = action != SyncActionType::Remove
+                && self.is_unmanaged(
+                    *tool,
+                    skill_id,
+                    &self.store.skill_path(skill_id),
+                    &target_path,
+                );
             let sync_action = SyncAction {
-                skill_id: skill_id.to_string(),
+                skill_id: skill_id.clone(),
                 tool: *tool,
                 action,
                 strategy,
+                target_path,
+                overwrites,
             };
 
-            match action {
-                SyncActionType::Add => plan.to_add.push(sync_action),
-                SyncActionType::Update => plan.to_update.push(sync_action),
-                SyncActionType::Repair => plan.to_repair.push(sync_action),
-                SyncActionType::Remove => plan.to_remove.push(sync_action),
-            }
+            plan.push(sync_action);
         }
 
         Ok(plan)
@@ -182,42 +313,43 @@ impl SyncEngine {
         Ok(results)
     }
 
+    /// Lock the store for a state change and pick up changes other processes made
+    fn begin_update(&mut self) -> Result<StoreLockGuard> {
+        let guard = self.store.lock_exclusive()?;
+        self.state = self.store.load_sync_state(&self.scope);
+        Ok(guard)
+    }
+
     /// Sync a skill to a specific tool
     pub fn sync_skill(
         &mut self,
-        skill_id: &str,
+        skill_id: &SkillId,
         tool: ToolType,
         strategy: SyncStrategy,
     ) -> Result<()> {
+        let _guard = self.begin_update()?;
         let adapter = self.get_adapter(tool)?;
         let source_path = self.store.skill_path(skill_id);
 
         if !source_path.exists() {
             return Err(Error::SkillNotFound(skill_id.to_string()));
         }
+        if self.store.is_disabled(skill_id) {
+            return Err(Error::SyncError(format!(
+                "{} is disabled; enable it before syncing",
+                skill_id
+            )));
+        }
 
-        let target_dir = adapter.skills_dir()?;
-        let target_path = target_dir.join(skill_id);
+        let format = self.render_format(tool);
+        let target_dir = self.target_dir_path(adapter)?;
+        let target_path = self.projection_path(tool, &target_dir, skill_id);
+        self.check_projection_free(tool, skill_id, &target_path)?;
+        self.release_previous(tool, skill_id, &target_path)?;
+        self.claim_target(tool, skill_id, &source_path, &target_path)?;
 
-        // Determine actual strategy to use
-        let actual_strategy = match strategy {
-            SyncStrategy::Auto => {
-                // Try link first
-                if self.try_link(&source_path, &target_path).is_ok() {
-                    SyncStrategy::Link
-                } else {
-                    SyncStrategy::Copy
-                }
-            }
-            SyncStrategy::Link => {
-                self.create_link(&source_path, &target_path)?;
-                SyncStrategy::Link
-            }
-            SyncStrategy::Copy => {
-                self.copy_skill(&source_path, &target_path)?;
-                SyncStrategy::Copy
-            }
-        };
+        let (actual_strategy, rendered_hash) =
+            self.place(skill_id, &source_path, &target_path, strategy, format)?;
 
         // Update state
         let record = self
@@ -226,47 +358,152 @@ impl SyncEngine {
             .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
 
         let status = SkillSyncStatus {
-            skill_id: skill_id.to_string(),
+            skill_id: skill_id.clone(),
             version: record.version.clone(),
             strategy: actual_strategy,
             target_path,
             drift: None,
+            format,
+            rendered_hash,
         };
 
         self.update_state(tool, skill_id, status);
-
-        Ok(())
+        self.save_state()
     }
 
     /// Remove a skill from a tool
-    pub fn unsync_skill(&mut self, skill_id: &str, tool: ToolType) -> Result<()> {
+    ///
+    /// Only what SkillsHub created is deleted (see [`remove_projection`]). If
+    /// something else now sits at the projection's path it is left in place,
+    /// the projection is forgotten and an error is returned.
+    pub fn unsync_skill(&mut self, skill_id: &SkillId, tool: ToolType) -> Result<()> {
+        let _guard = self.begin_update()?;
         let adapter = self.get_adapter(tool)?;
-        let target_dir = adapter.skills_dir()?;
-        let target_path = target_dir.join(skill_id);
-
-        if target_path.exists() {
-            if target_path.is_symlink() {
-                fs::remove_file(&target_path)?;
-            } else {
-                fs::remove_dir_all(&target_path)?;
+        let target_dir = self.target_dir_path(adapter)?;
+        let recorded = self
+            .state
+            .tools
+            .get(&tool.to_string())
+            .and_then(|ts| ts.skills.get(skill_id))
+            .cloned();
+
+        let (target_path, removed) = match recorded {
+            // Projections stay where they were made, even if the alias changed since
+            Some(status) => {
+                let removed = remove_projection(&status)?;
+                (status.target_path, removed)
             }
-        }
+            // Without a record, only a link into the store is known to be ours
+            None => {
+                let target_path = self.projection_path(tool, &target_dir, skill_id);
+                let removed = if links_to(&target_path, &self.store.skill_path(skill_id)) {
+                    fs::remove_file(&target_path)?;
+                    true
+                } else {
+                    !target_path.exists() && !target_path.is_symlink()
+                };
+                (target_path, removed)
+            }
+        };
 
         // Remove from state
         if let Some(tool_state) = self.state.tools.get_mut(&tool.to_string()) {
             tool_state.skills.remove(skill_id);
         }
+        self.save_state()?;
 
-        Ok(())
+        if removed {
+            Ok(())
+        } else {
+            Err(Error::SyncError(format!(
+                "{} was not created by SkillsHub and was left in place",
+                target_path.display()
+            )))
+        }
+    }
+
+    /// Put a backup made by [`Self::sync_skill`] back where it was
+    ///
+    /// A SkillsHub projection occupying the path is removed first; anything
+    /// else there makes the restore fail.
+    pub fn restore_backup(&mut self, id: &str) -> Result<BackupEntry> {
+        let _guard = self.begin_update()?;
+        let entry = backup::get(&self.store, id)?;
+        let key = entry.tool.to_string();
+        let projection = self.state.tools.get(&key).and_then(|ts| {
+            ts.skills
+                .iter()
+                .find(|(_, s)| s.target_path == entry.original_path)
+                .map(|(id, s)| (id.clone(), s.clone()))
+        });
+        if let Some((skill_id, status)) = projection {
+            if remove_projection(&status)? {
+                if let Some(tool_state) = self.state.tools.get_mut(&key) {
+                    tool_state.skills.remove(&skill_id);
+                }
+                self.save_state()?;
+            }
+        }
+        backup::restore(&self.store, id)
+    }
+
+    /// Bring existing projections of a skill up to the store's active version
+    ///
+    /// Linked projections already follow the store's stable skill path, so
+    /// only their recorded version changes; copies, hard-linked mirrors and
+    /// rendered files are rewritten. Returns the tools whose projections were
+    /// refreshed.
+    pub fn refresh_skill(&mut self, skill_id: &SkillId) -> Result<Vec<ToolType>> {
+        let _guard = self.begin_update()?;
+        let version = self
+            .store
+            .get_record(skill_id)
+            .map(|r| r.version.clone())
+            .ok_or_else(|| Error::SkillNotFound(skill_id.to_string()))?;
+        let source_path = self.store.skill_path(skill_id);
+
+        let mut mirrors = Vec::new();
+        let mut refreshed = Vec::new();
+        for tool_state in self.state.tools.values_mut() {
+            if let Some(status) = tool_state.skills.get_mut(skill_id) {
+                if status.strategy.is_mirror() {
+                    mirrors.push((tool_state.tool, status.clone()));
+                }
+                status.version = version.clone();
+                refreshed.push(tool_state.tool);
+            }
+        }
+
+        for (tool, mirror) in mirrors {
+            let (used, rendered_hash) = self.place(
+                skill_id,
+                &source_path,
+                &mirror.target_path,
+                mirror.strategy,
+                mirror.format,
+            )?;
+            if let Some(status) = self
+                .state
+                .tools
+                .get_mut(&tool.to_string())
+                .and_then(|ts| ts.skills.get_mut(skill_id))
+            {
+                status.strategy = used;
+                status.rendered_hash = rendered_hash;
+            }
+        }
+
+        self.save_state()?;
+        Ok(refreshed)
     }
 
     /// Check for drift in all synced skills
-    pub fn check_drift(&self) -> Vec<(String, ToolType, DriftInfo)> {
+    pub fn check_drift(&self) -> Vec<(SkillId, ToolType, DriftInfo)> {
         let mut drifts = Vec::new();
 
         for tool_state in self.state.tools.values() {
             for (skill_id, status) in &tool_state.skills {
-                if let Some(drift) = self.detect_drift(skill_id, &status.target_path) {
+                if let Some(drift) = self.detect_drift(skill_id, status) {
                     drifts.push((skill_id.clone(), tool_state.tool, drift));
                 }
             }
@@ -278,7 +515,7 @@ impl SyncEngine {
     /// Repair a skill that has drifted
     fn repair_skill(
         &mut self,
-        skill_id: &str,
+        skill_id: &SkillId,
         tool: ToolType,
         strategy: SyncStrategy,
     ) -> Result<()> {
@@ -296,51 +533,403 @@ impl SyncEngine {
             .ok_or_else(|| Error::ToolNotFound(tool.to_string()))
     }
 
-    fn try_link(&self, source: &Path, target: &Path) -> Result<()> {
-        if target.exists() {
-            fs::remove_dir_all(target)?;
+    /// Skills directory of a tool in the current scope; it is created when
+    /// something is projected into it
+    fn target_dir_path(&self, adapter: &dyn ToolAdapter) -> Result<PathBuf> {
+        match &self.scope {
+            SyncScope::Global => adapter.skills_dir_path(),
+            SyncScope::Project { root } => adapter.project_skills_dir(root).ok_or_else(|| {
+                Error::ToolNotFound(format!(
+                    "{} (no project-level skills directory)",
+                    adapter.tool_type()
+                ))
+            }),
+        }
+    }
+
+    /// Format a tool's projections are rendered in; formats the tool cannot
+    /// read fall back to skill folders
+    fn render_format(&self, tool: ToolType) -> RenderFormat {
+        self.profiles
+            .get(&tool)
+            .map(|p| p.render_format)
+            .filter(|format| format.supported_by(tool))
+            .unwrap_or_default()
+    }
+
+    /// Where a skill is projected for a tool (see [`LocalStore::projection_name`])
+    ///
+    /// Rendered files go to the format's directory beside the skills
+    /// directory, e.g. `.cursor/rules/<name>.mdc` for `.cursor/skills`.
+    fn projection_path(&self, tool: ToolType, target_dir: &Path, skill_id: &SkillId) -> PathBuf {
+        let format = self.render_format(tool);
+        let name = format.file_name(&self.store.projection_name(skill_id).file_name());
+        match (format.dir_name(), target_dir.parent()) {
+            (Some(dir), Some(parent)) => parent.join(dir).join(name),
+            _ => target_dir.join(name),
+        }
+    }
+
+    /// Remove a skill's projection for a tool if it is recorded at another
+    /// path than `target_path`, e.g. after the tool's render format changed
+    fn release_previous(
+        &mut self,
+        tool: ToolType,
+        skill_id: &SkillId,
+        target_path: &Path,
+    ) -> Result<()> {
+        let Some(previous) = self
+            .state
+            .tools
+            .get(&tool.to_string())
+            .and_then(|ts| ts.skills.get(skill_id))
+            .filter(|status| status.target_path != target_path)
+            .cloned()
+        else {
+            return Ok(());
+        };
+        if !remove_projection(&previous)? {
+            tracing::warn!(
+                "Leaving {} in place: it was not created by SkillsHub",
+                previous.target_path.display()
+            );
+        }
+        if let Some(tool_state) = self.state.tools.get_mut(&tool.to_string()) {
+            tool_state.skills.remove(skill_id);
+        }
+        Ok(())
+    }
+
+    /// Make sure the path a skill is synced to holds nothing but SkillsHub's
+    /// own projection of it
+    ///
+    /// Anything else found there is moved to a backup, or reported as a
+    /// conflict if backups are turned off.
+    fn claim_target(
+        &mut self,
+        tool: ToolType,
+        skill_id: &SkillId,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> Result<()> {
+        if !self.is_unmanaged(tool, skill_id, source_path, target_path) {
+            return Ok(());
+        }
+
+        if !self.backup_unmanaged {
+            return Err(Error::SyncError(format!(
+                "{} already exists and was not created by SkillsHub",
+                target_path.display()
+            )));
+        }
+        let entry = backup::create(&self.store, target_path, tool, skill_id)?;
+        tracing::info!(
+            "Moved {} to backup {} to sync {}",
+            target_path.display(),
+            entry.id,
+            skill_id
+        );
+        self.backups.push(entry);
+        Ok(())
+    }
+
+    /// Whether something SkillsHub did not create for this skill occupies
+    /// `target_path`
+    fn is_unmanaged(
+        &self,
+        tool: ToolType,
+        skill_id: &SkillId,
+        source_path: &Path,
+        target_path: &Path,
+    ) -> bool {
+        if !target_path.exists() && !target_path.is_symlink() {
+            return false;
+        }
+        let recorded = self
+            .state
+            .tools
+            .get(&tool.to_string())
+            .and_then(|ts| ts.skills.get(skill_id))
+            .filter(|status| status.target_path == target_path)
+            .is_some_and(is_projection);
+        !recorded && !links_to(target_path, source_path)
+    }
+
+    /// Refuse to overwrite another skill's projection that has the same name
+    fn check_projection_free(
+        &self,
+        tool: ToolType,
+        skill_id: &SkillId,
+        target_path: &Path,
+    ) -> Result<()> {
+        let owner = self.state.tools.get(&tool.to_string()).and_then(|ts| {
+            ts.skills
+                .values()
+                .find(|s| s.skill_id != *skill_id && s.target_path == target_path)
+        });
+        match owner {
+            Some(owner) => Err(Error::SyncError(format!(
+                "{} is already projected into {} as {}; give one of them an alias",
+                owner.skill_id,
+                tool.display_name(),
+                self.store.projection_name(skill_id)
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Change the name a skill is projected under and move its projections
+    pub fn set_alias(&mut self, skill_id: &SkillId, alias: Option<SkillId>) -> Result<()> {
+        self.store.set_alias(skill_id, alias)?;
+
+        let projected: Vec<(ToolType, SyncStrategy)> = self
+            .state
+            .tools
+            .values()
+            .filter_map(|ts| ts.skills.get(skill_id).map(|s| (ts.tool, s.strategy)))
+            .collect();
+        for (tool, strategy) in projected {
+            self.unsync_skill(skill_id, tool)?;
+            self.sync_skill(skill_id, tool, strategy)?;
+        }
+        Ok(())
+    }
+
+    /// Stop projecting a skill into any tool while keeping it in the store
+    ///
+    /// Returns the tools its projections were removed from.
+    pub fn disable_skill(&mut self, skill_id: &SkillId) -> Result<Vec<ToolType>> {
+        let _guard = self.begin_update()?;
+        self.store.set_disabled(skill_id, true)?;
+        self.unsync_all(skill_id)
+    }
+
+    /// Let a disabled skill be projected again and project it into its target tools
+    /// `strategy_resolver` returns the effective strategy for a given tool type
+    pub fn enable_skill<F>(
+        &mut self,
+        skill_id: &SkillId,
+        strategy_resolver: F,
+    ) -> Result<Vec<(SkillId, ToolType, bool)>>
+    where
+        F: Fn(ToolType) -> SyncStrategy,
+    {
+        let _guard = self.begin_update()?;
+        self.store.set_disabled(skill_id, false)?;
+        let mut plan = SyncPlan::new();
+        self.plan_targets(&mut plan, skill_id, &strategy_resolver)?;
+        Ok(self.distribute(&plan)?.0)
+    }
+
+    /// Remove every projection of a skill recorded in the current scope
+    ///
+    /// Returns the tools it was removed from.
+    pub fn unsync_all(&mut self, skill_id: &SkillId) -> Result<Vec<ToolType>> {
+        let projected: Vec<ToolType> = self
+            .state
+            .tools
+            .values()
+            .filter(|ts| ts.skills.contains_key(skill_id))
+            .map(|ts| ts.tool)
+            .collect();
+        for tool in &projected {
+            self.unsync_skill(skill_id, *tool)?;
+        }
+        Ok(projected)
+    }
+
+    /// Directories scanned for a tool's skills in the current scope
+    fn scan_dirs(&self, adapter: &dyn ToolAdapter) -> Vec<PathBuf> {
+        match &self.scope {
+            SyncScope::Global => adapter.skills_dirs(),
+            SyncScope::Project { root } => adapter.project_skills_dir(root).into_iter().collect(),
+        }
+    }
+
+    /// Directories whose changes affect this engine's projections: the
+    /// store's `skills/` directory and every tool directory it scans
+    pub fn watched_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![self.store.skills_dir()];
+        for adapter in &self.adapters {
+            for dir in self.scan_dirs(adapter.as_ref()) {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Remove every projection recorded in the current scope's sync state
+    /// Returns the (skill_id, tool) pairs that were removed; entries replaced
+    /// by something SkillsHub did not create are forgotten but kept on disk
+    pub fn clean_projections(&mut self) -> Result<Vec<(SkillId, ToolType)>> {
+        let _guard = self.begin_update()?;
+        let recorded: Vec<(SkillId, ToolType, SkillSyncStatus)> = self
+            .state
+            .tools
+            .values()
+            .flat_map(|ts| {
+                ts.skills
+                    .iter()
+                    .map(move |(id, status)| (id.clone(), ts.tool, status.clone()))
+            })
+            .collect();
+
+        let mut removed = Vec::new();
+        for (skill_id, tool, status) in recorded {
+            if remove_projection(&status)? {
+                removed.push((skill_id.clone(), tool));
+            } else {
+                tracing::warn!(
+                    "Leaving {} in place: it was not created by SkillsHub",
+                    status.target_path.display()
+                );
+            }
+            if let Some(tool_state) = self.state.tools.get_mut(&tool.to_string()) {
+                tool_state.skills.remove(&skill_id);
+            }
+        }
+
+        self.state.tools.retain(|_, ts| !ts.skills.is_empty());
+        self.save_state()?;
+        Ok(removed)
+    }
+
+    /// Project a skill in `format`, returning the strategy actually used and,
+    /// for rendered files, the hash of what was written
+    ///
+    /// Rendered files are always written out, so they are recorded as copies.
+    fn place(
+        &self,
+        skill_id: &SkillId,
+        source: &Path,
+        target: &Path,
+        strategy: SyncStrategy,
+        format: RenderFormat,
+    ) -> Result<(SyncStrategy, Option<String>)> {
+        if format == RenderFormat::SkillFolder {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            return Ok((self.project(source, target, strategy)?, None));
         }
+
+        let rendered = render::render(format, skill_id, source)?;
+        clear_projection(target)?;
         if let Some(parent) = target.parent() {
             fs::create_dir_all(parent)?;
         }
+        fs::write(target, &rendered)?;
+        Ok((
+            SyncStrategy::Copy,
+            Some(hash::hash_bytes(rendered.as_bytes())),
+        ))
+    }
 
-        #[cfg(unix)]
-        {
-            std::os::unix::fs::symlink(source, target)?;
+    /// Project a skill with `strategy`, returning the strategy actually used
+    fn project(
+        &self,
+        source: &Path,
+        target: &Path,
+        strategy: SyncStrategy,
+    ) -> Result<SyncStrategy> {
+        match strategy {
+            SyncStrategy::Auto => {
+                // Try link first, copy where links are not possible
+                if self.try_link(source, target).is_ok() {
+                    Ok(SyncStrategy::Link)
+                } else {
+                    self.copy_skill(source, target)?;
+                    Ok(SyncStrategy::Copy)
+                }
+            }
+            SyncStrategy::Link => {
+                self.create_link(source, target)?;
+                Ok(SyncStrategy::Link)
+            }
+            SyncStrategy::RelativeLink => {
+                self.link_relative(source, target)?;
+                Ok(SyncStrategy::RelativeLink)
+            }
+            SyncStrategy::Hardlink => self.hardlink_skill(source, target),
+            SyncStrategy::Copy => {
+                self.copy_skill(source, target)?;
+                Ok(SyncStrategy::Copy)
+            }
         }
-        #[cfg(windows)]
-        {
-            std::os::windows::fs::symlink_dir(source, target)?;
+    }
+
+    /// Link a skill into a tool directory, replacing the projection there
+    fn try_link(&self, source: &Path, target: &Path) -> Result<()> {
+        clear_projection(target)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
         }
+        symlink_dir(source, target)
+    }
 
-        Ok(())
+    /// Link a skill into a tool directory with a path relative to it
+    fn link_relative(&self, source: &Path, target: &Path) -> Result<()> {
+        clear_projection(target)?;
+        let parent = target
+            .parent()
+            .ok_or_else(|| Error::SyncError(format!("Invalid target {}", target.display())))?;
+        fs::create_dir_all(parent)?;
+        symlink_dir(&relative_path(parent, source)?, target)
+    }
+
+    /// Mirror a skill into a tool directory with hard-linked files
+    ///
+    /// Hard links cannot cross file systems; the skill is copied instead and
+    /// `Copy` is returned.
+    fn hardlink_skill(&self, source: &Path, target: &Path) -> Result<SyncStrategy> {
+        clear_projection(target)?;
+        match hardlink_dir_all(source, target) {
+            Ok(()) => Ok(SyncStrategy::Hardlink),
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                self.copy_skill(source, target)?;
+                Ok(SyncStrategy::Copy)
+            }
+            Err(e) => {
+                let _ = clear_projection(target);
+                Err(e)
+            }
+        }
     }
 
     fn create_link(&self, source: &Path, target: &Path) -> Result<()> {
         self.try_link(source, target)
     }
 
+    /// Copy a skill into a tool directory, replacing the projection there
     fn copy_skill(&self, source: &Path, target: &Path) -> Result<()> {
-        if target.exists() {
-            fs::remove_dir_all(target)?;
-        }
+        clear_projection(target)?;
         copy_dir_all(source, target)
     }
 
-    fn detect_drift(&self, skill_id: &str, target_path: &Path) -> Option<DriftInfo> {
-        if !target_path.exists() {
+    fn detect_drift(&self, skill_id: &SkillId, status: &SkillSyncStatus) -> Option<DriftInfo> {
+        // Plugin skills are projected from their plugin folder, which the
+        // store neither holds nor can repair from
+        if self.store.is_plugin_record(skill_id) {
+            return None;
+        }
+        let target_path = &status.target_path;
+
+        if !target_path.exists() && !target_path.is_symlink() {
             return Some(DriftInfo {
                 drift_type: DriftType::Missing,
                 description: "Skill directory not found".to_string(),
                 detected_at: timestamp_now(),
+                files: Vec::new(),
             });
         }
 
         if target_path.is_symlink() {
-            let link_target = fs::read_link(target_path).ok()?;
+            let link_target = link_destination(target_path)?;
             let expected = self.store.skill_path(skill_id);
 
-            if link_target != expected {
+            if !links_to(target_path, &expected) {
                 return Some(DriftInfo {
                     drift_type: DriftType::WrongTarget,
                     description: format!(
@@ -348,22 +937,94 @@ impl SyncEngine {
                         link_target, expected
                     ),
                     detected_at: timestamp_now(),
+                    files: Vec::new(),
                 });
             }
 
-            if !link_target.exists() {
+            if !target_path.exists() {
                 return Some(DriftInfo {
                     drift_type: DriftType::BrokenLink,
                     description: "Symlink target does not exist".to_string(),
                     detected_at: timestamp_now(),
+                    files: Vec::new(),
                 });
             }
+
+            return None;
+        }
+
+        // Copied or hard-linked target: compare against the version installed in the store
+        //
+        // Hard links share the store's files, so they only differ once a tool
+        // replaced a file or the store moved on to another version
+        let record = self.store.get_record(skill_id)?;
+        let store_path = self.store.skill_path(skill_id);
+
+        // Rendered files cannot be compared with the store's folder; they are
+        // compared with the hash recorded when they were written instead
+        if status.format != RenderFormat::SkillFolder {
+            let drift_type = if record.version.content_hash != status.version.content_hash {
+                DriftType::VersionMismatch
+            } else {
+                let written = fs::read(target_path).ok()?;
+                if status.rendered_hash.as_deref() == Some(&hash::hash_bytes(&written)) {
+                    return None;
+                }
+                DriftType::ContentModified
+            };
+            let description = match drift_type {
+                DriftType::VersionMismatch => format!(
+                    "Rendered version {} is older than the store's {}",
+                    status.version.version, record.version.version
+                ),
+                _ => format!("{} was edited after it was rendered", status.format),
+            };
+            return Some(DriftInfo {
+                drift_type,
+                description,
+                detected_at: timestamp_now(),
+                files: target_path
+                    .file_name()
+                    .map(PathBuf::from)
+                    .into_iter()
+                    .collect(),
+            });
+        }
+
+        if record.version.content_hash != status.version.content_hash {
+            return Some(DriftInfo {
+                drift_type: DriftType::VersionMismatch,
+                description: format!(
+                    "Synced version {} is older than the store's {}",
+                    status.version.version, record.version.version
+                ),
+                detected_at: timestamp_now(),
+                files: diff_dirs(&store_path, target_path).unwrap_or_default(),
+            });
         }
 
-        None
+        let target = hash::manifest(target_path).ok()?;
+        if target.digest == record.version.content_hash {
+            return None;
+        }
+
+        // The record hash may be stale (e.g. partial imports); the store content is authoritative
+        if self.store.calculate_hash(skill_id).ok().as_ref() == Some(&target.digest) {
+            return None;
+        }
+
+        let files = hash::manifest(&store_path)
+            .map(|expected| expected.changed_files(&target))
+            .unwrap_or_default();
+        Some(DriftInfo {
+            drift_type: DriftType::ContentModified,
+            description: format!("{} file(s) differ from the store", files.len()),
+            detected_at: timestamp_now(),
+            files,
+        })
     }
 
-    fn update_state(&mut self, tool: ToolType, skill_id: &str, status: SkillSyncStatus) {
+    fn update_state(&mut self, tool: ToolType, skill_id: &SkillId, status: SkillSyncStatus) {
         let tool_state =
             self.state
                 .tools
@@ -374,7 +1035,7 @@ impl SyncEngine {
                     last_sync: None,
                 });
 
-        tool_state.skills.insert(skill_id.to_string(), status);
+        tool_state.skills.insert(skill_id.clone(), status);
         tool_state.last_sync = Some(timestamp_now());
         self.state.last_sync = Some(timestamp_now());
     }
@@ -386,10 +1047,11 @@ impl SyncEngine {
     pub fn sync_plugin_skill(
         &mut self,
         source_path: &Path,
-        skill_id: &str,
+        skill_id: &SkillId,
         tool: ToolType,
         strategy: SyncStrategy,
     ) -> Result<()> {
+        let _guard = self.begin_update()?;
         if !source_path.exists() {
             return Err(Error::SkillNotFound(format!(
                 "Plugin skill not found: {}",
@@ -398,67 +1060,69 @@ impl SyncEngine {
         }
 
         let adapter = self.get_adapter(tool)?;
-        let target_dir = adapter.skills_dir()?;
-        let target_path = target_dir.join(skill_id);
+        let format = self.render_format(tool);
+        let target_dir = self.target_dir_path(adapter)?;
+        let target_path = self.projection_path(tool, &target_dir, skill_id);
+        self.check_projection_free(tool, skill_id, &target_path)?;
+        self.release_previous(tool, skill_id, &target_path)?;
+        self.claim_target(tool, skill_id, source_path, &target_path)?;
 
-        // Determine actual strategy to use
-        let actual_strategy = match strategy {
-            SyncStrategy::Auto => {
-                // Try link first
-                if self.try_link(source_path, &target_path).is_ok() {
-                    SyncStrategy::Link
-                } else {
-                    SyncStrategy::Copy
-                }
-            }
-            SyncStrategy::Link => {
-                self.create_link(source_path, &target_path)?;
-                SyncStrategy::Link
-            }
-            SyncStrategy::Copy => {
-                self.copy_skill(source_path, &target_path)?;
-                SyncStrategy::Copy
-            }
-        };
+        let (actual_strategy, rendered_hash) =
+            self.place(skill_id, source_path, &target_path, strategy, format)?;
 
         // Create a minimal version for plugin skills
-        let version = crate::models::SkillVersion::new("plugin", "from-claude-plugins");
+        let version = SkillVersion::new("plugin", "from-claude-plugins");
 
         let status = SkillSyncStatus {
-            skill_id: skill_id.to_string(),
+            skill_id: skill_id.clone(),
             version,
             strategy: actual_strategy,
             target_path,
             drift: None,
+            format,
+            rendered_hash,
         };
 
         self.update_state(tool, skill_id, status);
-
-        Ok(())
+        self.save_state()
     }
 
     /// Scan all tool directories for skills
     /// Returns a list of all skills found across all tools
     pub fn scan_all_tools(&self) -> Vec<ScannedSkill> {
         let mut all_skills = Vec::new();
-        let hub_skills = self.get_hub_skill_ids();
+        // Hub skills by the directory name they are projected under
+        let hub_skills: HashMap<SkillId, SkillId> = self
+            .get_hub_skill_ids()
+            .into_iter()
+            .map(|id| (self.store.projection_name(&id), id))
+            .collect();
         // O(1) dedup: (skill_id, tool_type) set
-        let mut seen: HashSet<(String, ToolType)> = HashSet::new();
+        let mut seen: HashSet<(SkillId, ToolType)> = HashSet::new();
 
         for adapter in &self.adapters {
             // Use skills_dirs() to scan multiple directories per tool
-            for skills_dir in adapter.skills_dirs() {
+            for skills_dir in self.scan_dirs(adapter.as_ref()) {
                 if skills_dir.exists() {
                     if let Ok(entries) = fs::read_dir(&skills_dir) {
                         for entry in entries.flatten() {
                             let path = entry.path();
                             if path.is_dir() || path.is_symlink() {
                                 if let Some(name) = path.file_name() {
-                                    let skill_id = name.to_string_lossy().to_string();
+                                    let name = name.to_string_lossy();
                                     // Skip hidden directories
-                                    if skill_id.starts_with('.') {
+                                    if name.starts_with('.') {
                                         continue;
                                     }
+                                    let Ok(dir_name) = SkillId::from_file_name(&name) else {
+                                        tracing::warn!(
+                                            "Skipping {}: not a valid skill directory name",
+                                            path.display()
+                                        );
+                                        continue;
+                                    };
+                                    let hub_id = hub_skills.get(&dir_name);
+                                    let skill_id = hub_id.cloned().unwrap_or(dir_name);
                                     // O(1) dedup check
                                     let key = (skill_id.clone(), adapter.tool_type());
                                     if !seen.insert(key) {
@@ -468,7 +1132,7 @@ impl SyncEngine {
                                         id: skill_id.clone(),
                                         path: path.clone(),
                                         tool: adapter.tool_type(),
-                                        in_hub: hub_skills.contains(&skill_id),
+                                        in_hub: hub_id.is_some(),
                                         is_link: path.is_symlink(),
                                     });
                                 }
@@ -483,18 +1147,16 @@ impl SyncEngine {
     }
 
     /// Get set of skill IDs in the hub (O(1) lookup)
-    fn get_hub_skill_ids(&self) -> HashSet<String> {
+    fn get_hub_skill_ids(&self) -> HashSet<SkillId> {
         let skills_dir = self.store.skills_dir();
         let mut ids = HashSet::new();
         if skills_dir.exists() {
             if let Ok(entries) = fs::read_dir(&skills_dir) {
                 for entry in entries.flatten() {
                     if entry.path().is_dir() {
-                        if let Some(name) = entry.path().file_name() {
-                            let id = name.to_string_lossy().to_string();
-                            if !id.starts_with('.') {
-                                ids.insert(id);
-                            }
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        if let Ok(id) = SkillId::from_file_name(&name) {
+                            ids.insert(id);
                         }
                     }
                 }
@@ -504,119 +1166,482 @@ impl SyncEngine {
     }
 
     /// Collect skills from tools into the hub
-    /// Copies skills that exist in tools but not in the hub
-    pub fn collect_to_hub(&mut self) -> Result<Vec<String>> {
-        let scanned = self.scan_all_tools();
-        let mut collected = Vec::new();
-
-        for skill in scanned {
-            if !skill.in_hub {
-                // Check if we already collected this skill
-                let hub_path = self.store.skills_dir().join(&skill.id);
-                if hub_path.exists() {
-                    continue;
-                }
+    /// Imports skills that exist in tools but not in the hub
+    ///
+    /// Each skill goes through the store's staging area, so an interrupted
+    /// collection never leaves a half-copied skill in the hub. Copies made by
+    /// SkillsHub itself are not collected. If tools hold differing copies of
+    /// a skill, none is collected and the differences are reported as a
+    /// [`CollectConflict`] to settle with [`Self::resolve_conflict`].
+    pub fn collect_to_hub(&mut self) -> Result<CollectReport> {
+        let mut report = CollectReport::default();
+
+        for (skill_id, mut variants) in self.collect_candidates() {
+            if variants.len() > 1 {
+                self.diff_variants(&mut variants)?;
+                report
+                    .conflicts
+                    .push(CollectConflict { skill_id, variants });
+                continue;
+            }
 
-                // Copy skill to hub
-                if skill.is_link {
-                    // Resolve the symlink and copy the actual content
-                    if let Ok(resolved) = fs::read_link(&skill.path) {
-                        let real_path = if resolved.is_absolute() {
-                            resolved
-                        } else {
-                            skill
-                                .path
-                                .parent()
-                                .unwrap_or(Path::new("/"))
-                                .join(&resolved)
-                        };
-                        if real_path.exists() {
-                            copy_dir_all(&real_path, &hub_path)?;
-                            collected.push(skill.id.clone());
-                        }
-                    }
-                } else if skill.path.is_dir() {
-                    copy_dir_all(&skill.path, &hub_path)?;
-                    collected.push(skill.id.clone());
-                }
+            if let Some(real_path) = real_skill_path(&variants[0].paths[0]) {
+                self.import_collected(&skill_id, &real_path)?;
+                report.collected.push(skill_id);
             }
         }
 
-        Ok(collected)
+        Ok(report)
     }
 
-    /// Distribute skills from hub to all tools
-    /// Creates symlinks (or copies if symlinks fail) in each tool's skills directory
-    /// `strategy_resolver` returns the effective strategy for a given tool type
-    pub fn distribute_from_hub<F>(
+    /// Settle a [`CollectConflict`] reported by [`Self::collect_to_hub`]
+    ///
+    /// The chosen copy is collected under the skill's ID and projected into
+    /// the tools holding the other copies, which are moved to backups (see
+    /// [`Self::sync_skill`]). With [`ConflictResolution::KeepBoth`] the other
+    /// variants are first collected as skills of their own. Returns the
+    /// skills added to the hub.
+    pub fn resolve_conflict<F>(
         &mut self,
+        skill_id: &SkillId,
+        resolution: ConflictResolution,
         strategy_resolver: F,
-    ) -> Result<Vec<(String, ToolType, bool)>>
+    ) -> Result<Vec<SkillId>>
     where
         F: Fn(ToolType) -> SyncStrategy,
     {
-        let hub_skill_ids = self.get_hub_skill_ids();
-        let mut results = Vec::new();
+        let mut variants = self
+            .collect_candidates()
+            .remove(skill_id)
+            .filter(|variants| variants.len() > 1)
+            .ok_or_else(|| {
+                Error::SyncError(format!("{} has no conflicting copies to resolve", skill_id))
+            })?;
+
+        let winner = match &resolution {
+            ConflictResolution::UseTool { tool } => variants
+                .iter()
+                .position(|v| v.tools.contains(tool))
+                .ok_or_else(|| {
+                    Error::SyncError(format!(
+                        "{} has no copy of {}",
+                        tool.display_name(),
+                        skill_id
+                    ))
+                })?,
+            ConflictResolution::KeepBoth => 0,
+        };
+        let chosen = variants.remove(winner);
 
-        for skill_id in hub_skill_ids {
-            let source = self.store.skills_dir().join(&skill_id);
-            if !source.exists() {
-                continue;
+        let mut collected = Vec::new();
+        if resolution == ConflictResolution::KeepBoth {
+            for variant in &variants {
+                let tool_key = variant.tools[0].key();
+                let mut id = SkillId::new(format!("{}-{}", skill_id, tool_key))?;
+                let mut n = 1;
+                while self.store.is_installed(&id) {
+                    n += 1;
+                    id = SkillId::new(format!("{}-{}-{}", skill_id, tool_key, n))?;
+                }
+                let real_path = real_skill_path(&variant.paths[0])
+                    .ok_or_else(|| Error::SkillNotFound(variant.paths[0].display().to_string()))?;
+                self.import_collected(&id, &real_path)?;
+                collected.push(id);
             }
+        }
 
-            for adapter in &self.adapters {
-                if let Ok(target_dir) = adapter.skills_dir() {
-                    let target = target_dir.join(&skill_id);
+        let real_path = real_skill_path(&chosen.paths[0])
+            .ok_or_else(|| Error::SkillNotFound(chosen.paths[0].display().to_string()))?;
+        self.import_collected(skill_id, &real_path)?;
+        collected.insert(0, skill_id.clone());
 
-                    // Skip if already exists
-                    if target.exists() || target.is_symlink() {
-                        continue;
-                    }
+        for tool in variants.iter().flat_map(|v| v.tools.iter().copied()) {
+            self.sync_skill(skill_id, tool, strategy_resolver(tool))?;
+        }
+        Ok(collected)
+    }
 
-                    // Create target directory's parent if needed
-                    if let Some(parent) = target.parent() {
-                        let _ = fs::create_dir_all(parent);
-                    }
+    /// Copies of skills not yet in the hub, grouped by skill and content
+    ///
+    /// Variants are ordered by how many tools hold them, then by tool order.
+    fn collect_candidates(&self) -> BTreeMap<SkillId, Vec<CollectVariant>> {
+        let projected: HashSet<&PathBuf> = self
+            .state
+            .tools
+            .values()
+            .flat_map(|ts| ts.skills.values().map(|s| &s.target_path))
+            .collect();
+
+        let mut candidates: BTreeMap<SkillId, Vec<CollectVariant>> = BTreeMap::new();
+        for skill in self.scan_all_tools() {
+            if skill.in_hub
+                || projected.contains(&skill.path)
+                || self.store.skill_path(&skill.id).exists()
+            {
+                continue;
+            }
+            let Some(real_path) = real_skill_path(&skill.path) else {
+                continue;
+            };
+            let content_hash = match hash::digest(&real_path) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    tracing::warn!("Skipping {}: {}", skill.path.display(), e);
+                    continue;
+                }
+            };
 
-                    // Apply per-tool sync strategy
-                    let strategy = strategy_resolver(adapter.tool_type());
-                    let success = match strategy {
-                        SyncStrategy::Auto => {
-                            // Try symlink first, fall back to copy
-                            self.try_link(&source, &target).is_ok()
-                                || copy_dir_all(&source, &target).is_ok()
-                        }
-                        SyncStrategy::Link => {
-                            // Always use symlink
-                            self.try_link(&source, &target).is_ok()
-                        }
-                        SyncStrategy::Copy => {
-                            // Always copy
-                            copy_dir_all(&source, &target).is_ok()
-                        }
-                    };
+            let variants = candidates.entry(skill.id).or_default();
+            match variants.iter_mut().find(|v| v.content_hash == content_hash) {
+                Some(variant) => {
+                    variant.tools.push(skill.tool);
+                    variant.paths.push(skill.path);
+                }
+                None => variants.push(CollectVariant {
+                    content_hash,
+                    tools: vec![skill.tool],
+                    paths: vec![skill.path],
+                    diffs: Vec::new(),
+                }),
+            }
+        }
+
+        for variants in candidates.values_mut() {
+            // Stable, so ties keep the tool order
+            variants.sort_by_key(|v| std::cmp::Reverse(v.tools.len()));
+        }
+        candidates
+    }
 
-                    results.push((skill_id.clone(), adapter.tool_type(), success));
+    /// Fill in each variant's differences from the first one
+    fn diff_variants(&self, variants: &mut [CollectVariant]) -> Result<()> {
+        let Some(base) = variants.first().and_then(|v| real_skill_path(&v.paths[0])) else {
+            return Ok(());
+        };
+        for variant in variants.iter_mut().skip(1) {
+            if let Some(other) = real_skill_path(&variant.paths[0]) {
+                variant.diffs = diff_skill_dirs(&base, &other)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Import a skill directory found in a tool into the hub
+    fn import_collected(&mut self, skill_id: &SkillId, path: &Path) -> Result<()> {
+        let staged = self
+            .store
+            .stage_skill(&collected_skill(skill_id, path), path)?;
+        self.store.commit_staged(staged)?;
+        Ok(())
+    }
+
+    /// Distribute skills from hub to their target tools
+    /// Creates symlinks (or copies if symlinks fail) in each tool's skills directory
+    /// `strategy_resolver` returns the effective strategy for a given tool type
+    ///
+    /// Only the tools chosen by [`Self::target_tools`] receive a skill.
+    /// Projections planned for removal (see [`Self::plan_full_sync`]) are
+    /// removed as well but not part of the returned list.
+    pub fn distribute_from_hub<F>(
+        &mut self,
+        strategy_resolver: F,
+    ) -> Result<Vec<(SkillId, ToolType, bool)>>
+    where
+        F: Fn(ToolType) -> SyncStrategy,
+    {
+        let _guard = self.begin_update()?;
+        let plan = self.plan_full_sync(strategy_resolver)?;
+        Ok(self.distribute(&plan)?.0)
+    }
+
+    /// Dry run of [`Self::full_sync`]: everything it would collect, project
+    /// and remove, without touching the hub or any tool
+    ///
+    /// Skills that would be collected are planned into the tools that lack
+    /// them as well.
+    pub fn preview_full_sync<F>(&self, strategy_resolver: F) -> Result<SyncPlan>
+    where
+        F: Fn(ToolType) -> SyncStrategy,
+    {
+        let mut plan = self.plan_full_sync(&strategy_resolver)?;
+        if !matches!(self.scope, SyncScope::Global) {
+            return Ok(plan);
+        }
+
+        for (skill_id, mut variants) in self.collect_candidates() {
+            if variants.len() > 1 {
+                self.diff_variants(&mut variants)?;
+                plan.conflicts.push(CollectConflict { skill_id, variants });
+                continue;
+            }
+            let variant = variants.remove(0);
+            let Some(real_path) = real_skill_path(&variant.paths[0]) else {
+                continue;
+            };
+
+            for tool in self.wanted_tools(&declared_tools(&real_path)) {
+                let Ok(target_dir) = self.target_dir_path(self.get_adapter(tool)?) else {
+                    continue;
+                };
+                let target_path = self.projection_path(tool, &target_dir, &skill_id);
+                if target_path.exists() || target_path.is_symlink() {
+                    continue;
                 }
+                plan.push(SyncAction {
+                    skill_id: skill_id.clone(),
+                    tool,
+                    action: SyncActionType::Add,
+                    strategy: strategy_resolver(tool),
+                    target_path,
+                    overwrites: false,
+                });
             }
+            plan.to_collect.push(CollectAction {
+                skill_id,
+                tools: variant.tools,
+                source_path: variant.paths[0].clone(),
+            });
         }
+        Ok(plan)
+    }
 
-        Ok(results)
+    /// Dry run of [`Self::clean_projections`]
+    pub fn plan_clean(&self) -> SyncPlan {
+        let mut plan = SyncPlan::new();
+        for tool_state in self.state.tools.values() {
+            for (skill_id, status) in &tool_state.skills {
+                plan.push(SyncAction {
+                    skill_id: skill_id.clone(),
+                    tool: tool_state.tool,
+                    action: SyncActionType::Remove,
+                    strategy: status.strategy,
+                    target_path: status.target_path.clone(),
+                    overwrites: false,
+                });
+            }
+        }
+        plan.to_remove
+            .sort_by(|a, b| (&a.skill_id, &a.target_path).cmp(&(&b.skill_id, &b.target_path)));
+        plan
     }
 
-    /// Full sync: collect from tools, then distribute to all tools
+    /// Plan what a full sync would project, without touching any tool
+    ///
+    /// Every hub skill is added to the target tools that lack it, updated where
+    /// its projection is behind the store, and repaired where it drifted.
+    /// Directories already present under the skill's name that SkillsHub did
+    /// not create are left alone. Recorded projections of skills that were
+    /// deleted from the store, disabled or are no longer assigned to the tool
+    /// are planned for removal.
+    pub fn plan_full_sync<F>(&self, strategy_resolver: F) -> Result<SyncPlan>
+    where
+        F: Fn(ToolType) -> SyncStrategy,
+    {
+        let mut skill_ids: Vec<SkillId> = self.get_hub_skill_ids().into_iter().collect();
+        skill_ids.sort();
+
+        let mut plan = SyncPlan::new();
+        for skill_id in &skill_ids {
+            self.plan_targets(&mut plan, skill_id, &strategy_resolver)?;
+        }
+
+        let mut stale: Vec<SyncAction> = self
+            .state
+            .tools
+            .values()
+            .filter(|ts| self.get_adapter(ts.tool).is_ok())
+            .flat_map(|ts| {
+                ts.skills
+                    .iter()
+                    .filter(|(id, _)| !self.target_tools(id).contains(&ts.tool))
+                    .map(|(id, status)| SyncAction {
+                        skill_id: id.clone(),
+                        tool: ts.tool,
+                        action: SyncActionType::Remove,
+                        strategy: status.strategy,
+                        target_path: status.target_path.clone(),
+                        overwrites: false,
+                    })
+            })
+            .collect();
+        stale.sort_by(|a, b| {
+            (&a.skill_id, a.tool.display_name()).cmp(&(&b.skill_id, b.tool.display_name()))
+        });
+        for action in stale {
+            plan.push(action);
+        }
+        Ok(plan)
+    }
+
+    /// Tools a skill should be projected into
+    ///
+    /// Explicit assignments ([`InstallRecord::projected_tools`]) come first;
+    /// without any, the `compatible_tools` declared in SKILL.md narrow the
+    /// set, and a skill declaring neither goes to every registered tool.
+    /// Disabled skills, and tools whose [`ToolProfile`] is not enabled, get
+    /// nothing.
+    ///
+    /// [`InstallRecord::projected_tools`]: crate::models::InstallRecord::projected_tools
+    pub fn target_tools(&self, skill_id: &SkillId) -> Vec<ToolType> {
+        let Some(record) = self.store.get_record(skill_id) else {
+            return Vec::new();
+        };
+        if record.disabled {
+            return Vec::new();
+        }
+
+        let wanted = if record.projected_tools.is_empty() {
+            declared_tools(&self.store.skill_path(skill_id))
+        } else {
+            record.projected_tools.clone()
+        };
+        self.wanted_tools(&wanted)
+    }
+
+    /// Enabled registered tools among `wanted`, or all of them if it is empty
+    fn wanted_tools(&self, wanted: &[String]) -> Vec<ToolType> {
+        self.adapters
+            .iter()
+            .map(|a| a.tool_type())
+            .filter(|tool| self.tool_enabled(*tool))
+            .filter(|tool| wanted.is_empty() || wanted.iter().any(|name| names_tool(name, *tool)))
+            .collect()
+    }
+
+    /// Add the actions that bring one skill's target tools up to date
+    fn plan_targets<F>(
+        &self,
+        plan: &mut SyncPlan,
+        skill_id: &SkillId,
+        strategy_resolver: &F,
+    ) -> Result<()>
+    where
+        F: Fn(ToolType) -> SyncStrategy,
+    {
+        let Some(record) = self.store.get_record(skill_id) else {
+            return Ok(());
+        };
+        if !self.store.skill_path(skill_id).exists() {
+            return Ok(());
+        }
+
+        for tool in self.target_tools(skill_id) {
+            let Ok(target_dir) = self.target_dir_path(self.get_adapter(tool)?) else {
+                continue;
+            };
+            let current = self
+                .state
+                .tools
+                .get(&tool.to_string())
+                .and_then(|ts| ts.skills.get(skill_id));
+
+            let desired_path = self.projection_path(tool, &target_dir, skill_id);
+            let mut target_path = current
+                .map(|status| status.target_path.clone())
+                .unwrap_or_else(|| desired_path.clone());
+
+            // Existing projections keep the strategy they were made with,
+            // unless the tool's render format changed and they move
+            let (action, strategy) = match current {
+                None => {
+                    if target_path.exists() || target_path.is_symlink() {
+                        continue;
+                    }
+                    (SyncActionType::Add, strategy_resolver(tool))
+                }
+                Some(status) if status.format != self.render_format(tool) => {
+                    target_path = desired_path;
+                    (SyncActionType::Update, strategy_resolver(tool))
+                }
+                Some(status)
+                    if status.drift.is_some() || self.detect_drift(skill_id, status).is_some() =>
+                {
+                    (SyncActionType::Repair, status.strategy)
+                }
+                Some(status) if status.version != record.version => {
+                    (SyncActionType::Update, status.strategy)
+                }
+                Some(_) => continue,
+            };
+
+            plan.push(SyncAction {
+                skill_id: skill_id.clone(),
+                tool,
+                action,
+                strategy,
+                target_path,
+                overwrites: false,
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove the projections [`Self::plan_full_sync`] plans for removal,
+    /// without adding or updating anything
+    ///
+    /// Returns `(skill, tool, success)` per removed projection.
+    pub fn prune_projections(&mut self) -> Result<Vec<(SkillId, ToolType, bool)>> {
+        let _guard = self.begin_update()?;
+        let plan = SyncPlan {
+            to_remove: self.plan_full_sync(|_| SyncStrategy::Auto)?.to_remove,
+            ..SyncPlan::new()
+        };
+        Ok(self.distribute(&plan)?.1)
+    }
+
+    /// Execute a distribution plan, reporting `(skill, tool, success)` for the
+    /// projections made and, separately, for those removed
+    fn distribute(&mut self, plan: &SyncPlan) -> Result<(Distributed, Distributed)> {
+        let (removed, distributed): (Vec<SyncResult>, Vec<SyncResult>) = self
+            .execute_plan(plan)?
+            .into_iter()
+            .partition(|result| result.action == SyncActionType::Remove);
+        let report = |results: Vec<SyncResult>| {
+            results
+                .into_iter()
+                .map(|result| (result.skill_id, result.tool, result.success))
+                .collect()
+        };
+        Ok((report(distributed), report(removed)))
+    }
+
+    /// Set the per-tool settings (see [`ToolProfile`]) used when planning syncs
+    pub fn set_tool_profiles(&mut self, profiles: impl IntoIterator<Item = ToolProfile>) {
+        for profile in profiles {
+            self.profiles.insert(profile.tool_type, profile);
+        }
+    }
+
+    /// Whether a tool takes part in full syncs; tools without a profile do
+    pub fn tool_enabled(&self, tool: ToolType) -> bool {
+        self.profiles.get(&tool).is_none_or(|p| p.enabled)
+    }
+
+    /// Full sync: collect from tools, then distribute to each skill's target tools
     /// `strategy_resolver` returns the effective strategy for a given tool type
+    ///
+    /// Project-scoped engines only distribute: repository skill directories are
+    /// never collected into the global hub.
     pub fn full_sync<F>(&mut self, strategy_resolver: F) -> Result<FullSyncResult>
     where
         F: Fn(ToolType) -> SyncStrategy,
     {
-        let collected = self.collect_to_hub()?;
-        let distributed = self.distribute_from_hub(strategy_resolver)?;
+        let report = match self.scope {
+            SyncScope::Global => self.collect_to_hub()?,
+            SyncScope::Project { .. } => CollectReport::default(),
+        };
+
+        let _guard = self.begin_update()?;
+        let plan = self.plan_full_sync(strategy_resolver)?;
+        let (distributed, removed) = self.distribute(&plan)?;
 
         Ok(FullSyncResult {
-            collected_count: collected.len(),
-            collected_skills: collected,
+            collected_count: report.collected.len(),
+            collected_skills: report.collected,
+            conflicts: report.conflicts,
             distributed,
+            removed,
+            plan,
         })
     }
 
@@ -624,60 +1649,225 @@ impl SyncEngine {
     pub fn get_hub_status(&self) -> Vec<HubSyncStatus> {
         let hub_skill_ids = self.get_hub_skill_ids();
         let scanned = self.scan_all_tools();
-        let all_tools: Vec<ToolType> = self.adapters.iter().map(|a| a.tool_type()).collect();
 
         // Build O(1) lookup: skill_id -> [tools where it exists]
-        let mut skill_tools: HashMap<String, Vec<ToolType>> = HashMap::new();
+        let mut skill_tools: HashMap<SkillId, Vec<ToolType>> = HashMap::new();
         for s in &scanned {
-            skill_tools
-                .entry(s.id.clone())
-                .or_default()
-                .push(s.tool);
+            skill_tools.entry(s.id.clone()).or_default().push(s.tool);
         }
 
         hub_skill_ids
             .iter()
             .map(|skill_id| {
-                let synced_to = skill_tools
-                    .get(skill_id)
-                    .cloned()
-                    .unwrap_or_default();
+                let synced_to = skill_tools.get(skill_id).cloned().unwrap_or_default();
 
-                let missing_in: Vec<ToolType> = all_tools
-                    .iter()
+                let disabled = self.store.is_disabled(skill_id);
+                let missing_in: Vec<ToolType> = self
+                    .target_tools(skill_id)
+                    .into_iter()
                     .filter(|t| !synced_to.contains(t))
-                    .cloned()
                     .collect();
 
                 HubSyncStatus {
                     skill_id: skill_id.clone(),
-                    hub_path: self.store.skills_dir().join(skill_id),
+                    hub_path: self.store.skill_path(skill_id),
                     synced_to,
                     missing_in,
+                    disabled,
                 }
             })
             .collect()
     }
 }
 
+/// Result of collecting skills from tools into the hub
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectReport {
+    /// Skills imported into the hub
+    pub collected: Vec<SkillId>,
+    /// Skills left out because tools hold differing copies
+    pub conflicts: Vec<CollectConflict>,
+}
+
 /// Result of a full sync operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FullSyncResult {
     pub collected_count: usize,
-    pub collected_skills: Vec<String>,
-    pub distributed: Vec<(String, ToolType, bool)>,
+    pub collected_skills: Vec<SkillId>,
+    /// Skills not collected because tools hold differing copies
+    #[serde(default)]
+    pub conflicts: Vec<CollectConflict>,
+    pub distributed: Vec<(SkillId, ToolType, bool)>,
+    /// Projections removed because their skill left the hub or the tool
+    #[serde(default)]
+    pub removed: Vec<(SkillId, ToolType, bool)>,
+    /// The plan that was executed for the distribution
+    pub plan: SyncPlan,
 }
 
 /// Result of a sync operation
 #[derive(Debug, Clone)]
 pub struct SyncResult {
-    pub skill_id: String,
+    pub skill_id: SkillId,
     pub tool: ToolType,
     pub action: SyncActionType,
     pub success: bool,
     pub error: Option<String>,
 }
 
+/// `(skill, tool, success)` per executed action
+type Distributed = Vec<(SkillId, ToolType, bool)>;
+
+/// Delete a projection if it is still the kind of entry SkillsHub created
+///
+/// Links must still be links, copies real directories and rendered files
+/// real files; anything else was put there by someone else and is left alone
+/// (returns `false`). A projection that is already gone counts as removed.
+fn remove_projection(status: &SkillSyncStatus) -> Result<bool> {
+    let path = &status.target_path;
+    if !path.exists() && !path.is_symlink() {
+        return Ok(true);
+    }
+    if !is_projection(status) {
+        return Ok(false);
+    }
+    clear_projection(path)?;
+    Ok(true)
+}
+
+/// Whether the entry at a projection's path is of the kind it creates: a
+/// link, a real directory for copies and hard-linked mirrors, or a real file
+/// for rendered formats
+fn is_projection(status: &SkillSyncStatus) -> bool {
+    let path = &status.target_path;
+    if path.is_symlink() {
+        return !status.strategy.is_mirror();
+    }
+    match status.format {
+        RenderFormat::SkillFolder => status.strategy.is_mirror() && path.is_dir(),
+        _ => path.is_file(),
+    }
+}
+
+/// Delete whatever is at a projection path; callers check ownership first
+fn clear_projection(path: &Path) -> Result<()> {
+    if path.is_symlink() {
+        fs::remove_file(path)?;
+    } else if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Create a directory symlink at `link` pointing to `destination`
+fn symlink_dir(destination: &Path, link: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(destination, link)?;
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_dir(destination, link)?;
+    }
+    Ok(())
+}
+
+/// Path to `to` relative to the directory `from`
+///
+/// Both parents are resolved first so links in either (e.g. a symlinked
+/// tool directory) do not throw off the `..` count; the last component of
+/// `to` is kept as is, since the store's skill path is itself a link.
+fn relative_path(from: &Path, to: &Path) -> Result<PathBuf> {
+    let from = from.canonicalize()?;
+    let to = resolve_parent(to);
+    let from_components: Vec<_> = from.components().collect();
+    let to_components: Vec<_> = to.components().collect();
+    let common = from_components
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    // Different drives on Windows have no relative path between them
+    if common == 0 {
+        return Ok(to);
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in common..from_components.len() {
+        relative.push("..");
+    }
+    for component in &to_components[common..] {
+        relative.push(component);
+    }
+    Ok(relative)
+}
+
+/// `path` with its parent directory resolved, falling back to `path` itself
+fn resolve_parent(path: &Path) -> PathBuf {
+    match (path.parent().map(Path::canonicalize), path.file_name()) {
+        (Some(Ok(parent)), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Where the link at `path` points, with relative links resolved against
+/// the directory holding them
+fn link_destination(path: &Path) -> Option<PathBuf> {
+    let destination = fs::read_link(path).ok()?;
+    if destination.is_absolute() {
+        return Some(destination);
+    }
+
+    let mut resolved = path.parent()?.canonicalize().ok()?;
+    for component in destination.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            component => resolved.push(component),
+        }
+    }
+    Some(resolved)
+}
+
+/// Whether `path` is a link, absolute or relative, to `destination`
+fn links_to(path: &Path, destination: &Path) -> bool {
+    link_destination(path).is_some_and(|d| d == destination || d == resolve_parent(destination))
+}
+
+/// Whether a tool name from an assignment or SKILL.md (`claude`, `Claude Code`) means `tool`
+fn names_tool(name: &str, tool: ToolType) -> bool {
+    ToolType::from_key(name) == Some(tool) || tool.display_name().eq_ignore_ascii_case(name.trim())
+}
+
+/// Describe a skill found in a tool directory for import into the store
+pub(crate) fn collected_skill(skill_id: &SkillId, path: &Path) -> Skill {
+    let skill_md_path = path.join("SKILL.md");
+    let metadata = parse_skill_md(&skill_md_path).unwrap_or_default();
+
+    Skill {
+        id: skill_id.clone(),
+        name: metadata.name.unwrap_or_else(|| skill_id.to_string()),
+        description: metadata.description.unwrap_or_default(),
+        author: metadata.author,
+        tags: metadata.tags,
+        compatible_tools: metadata.compatible_tools,
+        version: SkillVersion::new(
+            metadata.version.unwrap_or_else(|| "0.0.0".to_string()),
+            String::new(),
+        ),
+        source: SkillSource::Local {
+            path: path.to_path_buf(),
+        },
+        skill_md_path,
+        resources: Vec::new(),
+        metadata: HashMap::new(),
+    }
+}
+
 fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
@@ -690,11 +1880,104 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
             copy_dir_all(&src_path, &dst_path)?;
         } else {
             fs::copy(&src_path, &dst_path)?;
+            make_writable(&dst_path)?;
         }
     }
     Ok(())
 }
 
+/// Mirror a directory tree, hard-linking each file
+///
+/// The links share the store's read-only files, so they are left as they
+/// are rather than made writable like copies.
+fn hardlink_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            hardlink_dir_all(&src_path, &dst_path)?;
+        } else {
+            fs::hard_link(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Store files are read-only; copies made from them are meant to be editable
+#[cfg(unix)]
+pub(crate) fn make_writable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o200);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn make_writable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// List files (relative paths) that were added, removed or changed between two directories
+fn diff_dirs(expected: &Path, actual: &Path) -> Result<Vec<PathBuf>> {
+    Ok(hash::manifest(expected)?.changed_files(&hash::manifest(actual)?))
+}
+
+/// Tools a skill directory's SKILL.md declares itself compatible with
+fn declared_tools(skill_dir: &Path) -> Vec<String> {
+    parse_skill_md(&skill_dir.join("SKILL.md"))
+        .map(|metadata| metadata.compatible_tools)
+        .unwrap_or_default()
+}
+
+/// The directory a skill found in a tool actually lives in, following links
+fn real_skill_path(path: &Path) -> Option<PathBuf> {
+    let real_path = if path.is_symlink() {
+        link_destination(path)?
+    } else {
+        path.to_path_buf()
+    };
+    real_path.is_dir().then_some(real_path)
+}
+
+/// Per-file differences from one copy of a skill to another
+fn diff_skill_dirs(base: &Path, other: &Path) -> Result<Vec<FileDiff>> {
+    let base_manifest = hash::manifest(base)?;
+    let other_manifest = hash::manifest(other)?;
+
+    let mut diffs = Vec::new();
+    for path in base_manifest.changed_files(&other_manifest) {
+        let key = path.to_string_lossy().replace('\\', "/");
+        let change = match (
+            base_manifest.files.contains_key(&key),
+            other_manifest.files.contains_key(&key),
+        ) {
+            (false, _) => FileChange::Added,
+            (_, false) => FileChange::Removed,
+            _ => FileChange::Modified,
+        };
+        let read = |root: &Path| match change {
+            FileChange::Added if root == base => Some(String::new()),
+            FileChange::Removed if root == other => Some(String::new()),
+            _ => fs::read_to_string(root.join(&path)).ok(),
+        };
+        let diff = match (read(base), read(other)) {
+            (Some(old), Some(new)) => Some(
+                similar::TextDiff::from_lines(&old, &new)
+                    .unified_diff()
+                    .header(&format!("a/{}", key), &format!("b/{}", key))
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        diffs.push(FileDiff { path, change, diff });
+    }
+    Ok(diffs)
+}
+
 fn timestamp_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
@@ -702,3 +1985,840 @@ fn timestamp_now() -> String {
         .unwrap_or_default();
     format!("{}", duration.as_secs())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{ClaudeAdapter, CursorAdapter, SkillStructure};
+    use crate::store::StoreConfig;
+
+    fn skill_id(id: &str) -> SkillId {
+        SkillId::new(id).unwrap()
+    }
+
+    async fn engine_with_skill(root: &Path) -> SyncEngine {
+        let source = root.join("source").join("demo");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Demo\n\nA demo skill").unwrap();
+
+        let mut store = LocalStore::new(StoreConfig {
+            root_dir: root.join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let skill = Skill {
+            id: skill_id("demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", hash::digest(&source).unwrap()),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        store.import_skill(&skill, &source).await.unwrap();
+
+        let mut engine = SyncEngine::new(store);
+        engine.register_adapter(Box::new(ClaudeAdapter::with_path(root.join("claude"))));
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_copied_skill_content_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert!(engine.check_drift().is_empty());
+
+        let target = dir.path().join("claude").join("demo");
+        #[cfg(unix)]
+        {
+            // Copies are editable even though the store's files are read-only
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(target.join("SKILL.md"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_ne!(mode & 0o200, 0);
+        }
+        fs::write(target.join("SKILL.md"), "# Demo\n\nEdited in place").unwrap();
+        fs::write(target.join("notes.md"), "extra").unwrap();
+
+        let drifts = engine.check_drift();
+        assert_eq!(drifts.len(), 1);
+        let (_, _, drift) = &drifts[0];
+        assert_eq!(drift.drift_type, DriftType::ContentModified);
+        assert_eq!(
+            drift.files,
+            vec![PathBuf::from("SKILL.md"), PathBuf::from("notes.md")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drift_survives_engine_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        fs::remove_dir_all(dir.path().join("claude").join("demo")).unwrap();
+
+        let store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let engine = SyncEngine::new(store);
+        let drifts = engine.check_drift();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].2.drift_type, DriftType::Missing);
+    }
+
+    #[tokio::test]
+    async fn test_project_scope_projects_into_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        engine_with_skill(dir.path()).await;
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(&repo).unwrap();
+
+        let store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut engine = SyncEngine::for_project(store, &repo);
+        engine.register_adapter(Box::new(ClaudeAdapter::with_path(
+            dir.path().join("claude"),
+        )));
+
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        let target = repo.join(".claude").join("skills").join("demo");
+        assert!(target.join("SKILL.md").exists());
+        assert!(!dir.path().join("claude").join("demo").exists());
+        assert!(engine.check_drift().is_empty());
+
+        let removed = engine.clean_projections().unwrap();
+        assert_eq!(removed, vec![(skill_id("demo"), ToolType::Claude)]);
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn test_projections_follow_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine.register_adapter(Box::new(crate::adapters::CursorAdapter::with_path(
+            dir.path().join("cursor"),
+        )));
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Claude, SyncStrategy::Link)
+            .unwrap();
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Cursor, SyncStrategy::Copy)
+            .unwrap();
+
+        let source = dir.path().join("source").join("demo-v2");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Demo v2").unwrap();
+        let skill_v2 = Skill {
+            id: skill_id("demo"),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("2.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        engine
+            .store_mut()
+            .import_skill(&skill_v2, &source)
+            .await
+            .unwrap();
+
+        let linked = dir.path().join("claude").join("demo").join("SKILL.md");
+        let copied = dir.path().join("cursor").join("demo").join("SKILL.md");
+        assert_eq!(fs::read_to_string(&linked).unwrap(), "# Demo v2");
+
+        // Copies only change when refreshed
+        assert_eq!(
+            fs::read_to_string(&copied).unwrap(),
+            "# Demo\n\nA demo skill"
+        );
+        assert_eq!(engine.refresh_skill(&skill_id("demo")).unwrap().len(), 2);
+        assert_eq!(fs::read_to_string(&copied).unwrap(), "# Demo v2");
+
+        engine
+            .store_mut()
+            .rollback(&skill_id("demo"), None)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&linked).unwrap(),
+            "# Demo\n\nA demo skill"
+        );
+
+        // The copy is stale until refreshed
+        assert_eq!(engine.check_drift().len(), 1);
+        engine.refresh_skill(&skill_id("demo")).unwrap();
+        assert!(engine.check_drift().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relative_link_projection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine
+            .sync_skill(
+                &skill_id("demo"),
+                ToolType::Claude,
+                SyncStrategy::RelativeLink,
+            )
+            .unwrap();
+
+        let target = dir.path().join("claude").join("demo");
+        let link = fs::read_link(&target).unwrap();
+        assert!(link.is_relative());
+        assert_eq!(
+            link,
+            Path::new("..").join("store").join("skills").join("demo")
+        );
+        assert!(target.join("SKILL.md").exists());
+        assert!(engine.check_drift().is_empty());
+
+        // Moving the whole tree keeps the link working
+        let moved = dir.path().join("moved");
+        fs::create_dir_all(&moved).unwrap();
+        fs::rename(dir.path().join("claude"), moved.join("claude")).unwrap();
+        fs::rename(dir.path().join("store"), moved.join("store")).unwrap();
+        assert!(moved.join("claude").join("demo").join("SKILL.md").exists());
+        fs::rename(moved.join("claude"), dir.path().join("claude")).unwrap();
+        fs::rename(moved.join("store"), dir.path().join("store")).unwrap();
+
+        fs::remove_file(&target).unwrap();
+        symlink_dir(&dir.path().join("source").join("demo"), &target).unwrap();
+        let drifts = engine.check_drift();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].2.drift_type, DriftType::WrongTarget);
+
+        engine
+            .unsync_skill(&skill_id("demo"), ToolType::Claude)
+            .unwrap();
+        assert!(!target.is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_auto_projection_copies_when_linking_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = engine_with_skill(dir.path()).await;
+        let source = engine.store().skill_path(&skill_id("demo"));
+
+        // No symlink can be created at a path with a trailing slash, but a
+        // directory can
+        let target = dir.path().join("claude").join("demo/");
+        let strategy = engine
+            .project(&source, &target, SyncStrategy::Auto)
+            .unwrap();
+
+        assert_eq!(strategy, SyncStrategy::Copy);
+        let target = dir.path().join("claude").join("demo");
+        assert!(target.is_dir() && !target.is_symlink());
+        assert_eq!(
+            fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "# Demo\n\nA demo skill"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hardlink_projection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine
+            .sync_skill(&skill_id("demo"), ToolType::Claude, SyncStrategy::Hardlink)
+            .unwrap();
+
+        let target = dir.path().join("claude").join("demo");
+        assert!(target.is_dir() && !target.is_symlink());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let stored = engine
+                .store()
+                .skill_path(&skill_id("demo"))
+                .join("SKILL.md");
+            assert_eq!(
+                fs::metadata(target.join("SKILL.md")).unwrap().ino(),
+                fs::metadata(stored).unwrap().ino()
+            );
+        }
+        assert!(engine.check_drift().is_empty());
+
+        // A tool replacing a file breaks its link and shows up as drift
+        fs::remove_file(target.join("SKILL.md")).unwrap();
+        fs::write(target.join("SKILL.md"), "# Demo\n\nRewritten").unwrap();
+        let drifts = engine.check_drift();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].2.drift_type, DriftType::ContentModified);
+
+        assert_eq!(engine.refresh_skill(&skill_id("demo")).unwrap().len(), 1);
+        assert!(engine.check_drift().is_empty());
+        engine
+            .unsync_skill(&skill_id("demo"), ToolType::Claude)
+            .unwrap();
+        assert!(!target.exists());
+        assert!(engine
+            .store()
+            .skill_path(&skill_id("demo"))
+            .join("SKILL.md")
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_rendered_projection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let cursor = dir.path().join("cursor");
+        engine.register_adapter(Box::new(CursorAdapter::with_path(cursor.join("skills"))));
+        let mut profile = ToolProfile::new(ToolType::Cursor);
+        profile.render_format = RenderFormat::CursorRule;
+        engine.set_tool_profiles([profile.clone()]);
+
+        let id = skill_id("demo");
+        engine
+            .sync_skill(&id, ToolType::Cursor, SyncStrategy::Auto)
+            .unwrap();
+        let rule = cursor.join("rules").join("demo.mdc");
+        assert!(fs::read_to_string(&rule)
+            .unwrap()
+            .ends_with("# Demo\n\nA demo skill\n"));
+        assert!(!cursor.join("skills").join("demo").exists());
+        assert!(engine.check_drift().is_empty());
+
+        // Edits to the generated file are drift like edits to a copy
+        fs::write(&rule, "# Demo\n\nEdited").unwrap();
+        let drifts = engine.check_drift();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].2.drift_type, DriftType::ContentModified);
+        engine.refresh_skill(&id).unwrap();
+        assert!(engine.check_drift().is_empty());
+
+        // Switching back to skill folders moves the projection
+        profile.render_format = RenderFormat::SkillFolder;
+        engine.set_tool_profiles([profile]);
+        engine
+            .sync_skill(&id, ToolType::Cursor, SyncStrategy::Copy)
+            .unwrap();
+        assert!(!rule.exists());
+        assert!(cursor.join("skills").join("demo").join("SKILL.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_plugin_projection_is_not_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let plugin = dir.path().join("plugins").join("review");
+        fs::create_dir_all(&plugin).unwrap();
+        fs::write(plugin.join("SKILL.md"), "# Review").unwrap();
+
+        let id = skill_id("plugin@market:review");
+        engine
+            .sync_plugin_skill(&plugin, &id, ToolType::Claude, SyncStrategy::Link)
+            .unwrap();
+        engine
+            .store_mut()
+            .register_plugin_skill(&id, &plugin, vec!["claude".to_string()])
+            .unwrap();
+        assert!(engine.store().is_plugin_record(&id));
+        assert!(!engine.store().is_plugin_record(&skill_id("demo")));
+
+        // Neither the link into the plugin nor a copy of it is measured against the store
+        assert!(engine.check_drift().is_empty());
+        engine
+            .sync_plugin_skill(&plugin, &id, ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert!(engine.check_drift().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rendered_projection_of_namespaced_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let cursor = dir.path().join("cursor");
+        engine.register_adapter(Box::new(CursorAdapter::with_path(cursor.join("skills"))));
+        let mut profile = ToolProfile::new(ToolType::Cursor);
+        profile.render_format = RenderFormat::CursorRule;
+        engine.set_tool_profiles([profile]);
+
+        let id = skill_id("acme/lint: rust");
+        let source = dir.path().join("acme").join("lint");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Lint").unwrap();
+        let skill = collected_skill(&id, &source);
+        engine
+            .store_mut()
+            .import_skill(&skill, &source)
+            .await
+            .unwrap();
+
+        // The rule file carries the encoded name, like a skill folder would
+        engine
+            .sync_skill(&id, ToolType::Cursor, SyncStrategy::Auto)
+            .unwrap();
+        let rule = cursor.join("rules").join("lint%3A%20rust.mdc");
+        assert!(fs::read_to_string(&rule).unwrap().ends_with("# Lint\n"));
+        assert!(engine.check_drift().is_empty());
+    }
+
+    /// Adapter for a tool that only reads skills listed in an `AGENTS.md`
+    struct IndexedAdapter(PathBuf);
+
+    impl ToolAdapter for IndexedAdapter {
+        fn tool_type(&self) -> ToolType {
+            ToolType::Custom
+        }
+
+        fn detect(&self) -> bool {
+            true
+        }
+
+        fn skills_dir(&self) -> Result<PathBuf> {
+            Ok(self.0.clone())
+        }
+
+        fn skill_structure(&self) -> SkillStructure {
+            SkillStructure {
+                needs_index: true,
+                index_file: Some("../AGENTS.md".to_string()),
+                use_subdirectories: true,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_updates_tool_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let tool_dir = dir.path().join("custom");
+        engine.register_adapter(Box::new(IndexedAdapter(tool_dir.join("skills"))));
+        fs::create_dir_all(&tool_dir).unwrap();
+        let index = tool_dir.join("AGENTS.md");
+        fs::write(&index, "# Rules\n").unwrap();
+
+        let id = skill_id("demo");
+        engine
+            .sync_skill(&id, ToolType::Custom, SyncStrategy::Copy)
+            .unwrap();
+        let written = fs::read_to_string(&index).unwrap();
+        assert!(written.starts_with("# Rules\n\n<!-- skillshub:begin"));
+        assert!(written.contains("- [demo](skills/demo/SKILL.md): A demo skill\n"));
+
+        // Tools without an index get none, and regenerating changes nothing
+        assert!(!dir.path().join("AGENTS.md").exists());
+        assert!(engine.update_indexes().is_empty());
+
+        engine.unsync_skill(&id, ToolType::Custom).unwrap();
+        assert_eq!(fs::read_to_string(&index).unwrap(), "# Rules\n");
+    }
+
+    #[tokio::test]
+    async fn test_same_name_from_two_registries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        for registry in ["anthropics", "obra"] {
+            let source = dir.path().join(registry).join("pdf");
+            fs::create_dir_all(&source).unwrap();
+            fs::write(source.join("SKILL.md"), format!("# PDF from {}", registry)).unwrap();
+            let skill = collected_skill(&skill_id(&format!("{}/pdf", registry)), &source);
+            engine
+                .store_mut()
+                .import_skill(&skill, &source)
+                .await
+                .unwrap();
+        }
+
+        // Both installs coexist, and a short name no longer picks one silently
+        let store = engine.store();
+        assert!(store.is_installed(&skill_id("anthropics/pdf")));
+        assert!(store.is_installed(&skill_id("obra/pdf")));
+        assert!(matches!(
+            store.resolve("pdf"),
+            Err(Error::AmbiguousSkill(_))
+        ));
+        assert_eq!(store.resolve("demo").unwrap(), "demo");
+
+        engine
+            .sync_skill(
+                &skill_id("anthropics/pdf"),
+                ToolType::Claude,
+                SyncStrategy::Copy,
+            )
+            .unwrap();
+        let claude = dir.path().join("claude");
+        assert!(claude.join("pdf").join("SKILL.md").exists());
+        assert!(engine
+            .sync_skill(&skill_id("obra/pdf"), ToolType::Claude, SyncStrategy::Copy)
+            .is_err());
+
+        // An alias gives the second one its own directory
+        engine
+            .set_alias(&skill_id("obra/pdf"), Some(skill_id("obra-pdf")))
+            .unwrap();
+        engine
+            .sync_skill(&skill_id("obra/pdf"), ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(claude.join("obra-pdf").join("SKILL.md")).unwrap(),
+            "# PDF from obra"
+        );
+        assert_eq!(engine.store().resolve("obra-pdf").unwrap(), "obra/pdf");
+        assert_eq!(engine.store().resolve("pdf").unwrap(), "anthropics/pdf");
+        assert!(engine
+            .store_mut()
+            .set_alias(&skill_id("demo"), Some(skill_id("pdf")))
+            .is_err());
+
+        // Renaming moves existing projections, and scans map them back to the hub
+        engine
+            .set_alias(&skill_id("anthropics/pdf"), Some(skill_id("anthropic-pdf")))
+            .unwrap();
+        assert!(!claude.join("pdf").exists());
+        let mut scanned: Vec<_> = engine
+            .scan_all_tools()
+            .into_iter()
+            .map(|s| (s.id.to_string(), s.in_hub))
+            .collect();
+        scanned.sort();
+        assert_eq!(
+            scanned,
+            vec![
+                ("anthropics/pdf".to_string(), true),
+                ("obra/pdf".to_string(), true)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_skill_is_not_projected() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let id = skill_id("demo");
+        let target = dir.path().join("claude").join("demo");
+
+        engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert_eq!(engine.disable_skill(&id).unwrap(), vec![ToolType::Claude]);
+        assert!(!target.exists());
+        assert!(engine.store().is_installed(&id));
+
+        // Neither a full sync nor an explicit sync brings it back
+        let result = engine.full_sync(|_| SyncStrategy::Copy).unwrap();
+        assert!(result.distributed.is_empty());
+        assert!(!target.exists());
+        assert!(engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Copy)
+            .is_err());
+        assert!(engine.get_hub_status()[0].missing_in.is_empty());
+
+        let distributed = engine.enable_skill(&id, |_| SyncStrategy::Copy).unwrap();
+        assert_eq!(distributed, vec![(id.clone(), ToolType::Claude, true)]);
+        assert!(target.join("SKILL.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_unmanaged_target_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let id = skill_id("demo");
+        let target = dir.path().join("claude").join("demo");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "# Hand-written").unwrap();
+
+        // Refused while backups are off
+        engine.set_backup_unmanaged(false);
+        assert!(engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Copy)
+            .is_err());
+        assert_eq!(
+            fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "# Hand-written"
+        );
+
+        engine.set_backup_unmanaged(true);
+        engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert_eq!(engine.backups().len(), 1);
+        assert!(fs::read_to_string(target.join("SKILL.md"))
+            .unwrap()
+            .contains("A demo skill"));
+
+        // Syncing again replaces only SkillsHub's own copy
+        engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Copy)
+            .unwrap();
+        assert_eq!(engine.backups().len(), 1);
+
+        let backup_id = engine.backups()[0].id.clone();
+        engine.restore_backup(&backup_id).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "# Hand-written"
+        );
+        assert!(engine.state().tools.values().all(|ts| ts.skills.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_collect_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine.register_adapter(Box::new(crate::adapters::CursorAdapter::with_path(
+            dir.path().join("cursor"),
+        )));
+        let write = |tool: &str, name: &str, content: &str| {
+            let path = dir.path().join(tool).join(name);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("SKILL.md"), content).unwrap();
+        };
+        write("claude", "bar", "# Bar");
+        write("cursor", "bar", "# Bar");
+        write("claude", "foo", "# Foo\nfrom claude\n");
+        write("cursor", "foo", "# Foo\nfrom cursor\n");
+        write("claude", "baz", "# Baz\nfrom claude\n");
+        write("cursor", "baz", "# Baz\nfrom cursor\n");
+
+        // Identical copies are collected, differing ones are reported
+        let report = engine.collect_to_hub().unwrap();
+        assert_eq!(report.collected, vec![skill_id("bar")]);
+        let conflicts: Vec<&SkillId> = report.conflicts.iter().map(|c| &c.skill_id).collect();
+        assert_eq!(conflicts, vec![&skill_id("baz"), &skill_id("foo")]);
+        let foo = &report.conflicts[1];
+        assert_eq!(foo.variants[0].tools, vec![ToolType::Claude]);
+        let diff = &foo.variants[1].diffs[0];
+        assert_eq!(diff.path, PathBuf::from("SKILL.md"));
+        assert_eq!(diff.change, FileChange::Modified);
+        let text = diff.diff.as_deref().unwrap();
+        assert!(text.contains("-from claude") && text.contains("+from cursor"));
+        assert!(!engine.store().is_installed(&skill_id("foo")));
+
+        // Cursor's copy wins and replaces Claude Code's, which is backed up
+        let collected = engine
+            .resolve_conflict(
+                &skill_id("foo"),
+                ConflictResolution::UseTool {
+                    tool: ToolType::Cursor,
+                },
+                |_| SyncStrategy::Copy,
+            )
+            .unwrap();
+        assert_eq!(collected, vec![skill_id("foo")]);
+        let claude_foo = dir.path().join("claude").join("foo").join("SKILL.md");
+        assert!(fs::read_to_string(claude_foo)
+            .unwrap()
+            .contains("from cursor"));
+        assert_eq!(engine.backups().len(), 1);
+
+        let collected = engine
+            .resolve_conflict(&skill_id("baz"), ConflictResolution::KeepBoth, |_| {
+                SyncStrategy::Copy
+            })
+            .unwrap();
+        assert_eq!(collected, vec![skill_id("baz"), skill_id("baz-cursor")]);
+        let hub_copy = |id: &str| {
+            fs::read_to_string(engine.store().skill_path(&skill_id(id)).join("SKILL.md")).unwrap()
+        };
+        assert!(hub_copy("baz").contains("from claude"));
+        assert!(hub_copy("baz-cursor").contains("from cursor"));
+
+        let report = engine.collect_to_hub().unwrap();
+        assert!(report.collected.is_empty() && report.conflicts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preview_full_sync_touches_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine.register_adapter(Box::new(crate::adapters::CursorAdapter::with_path(
+            dir.path().join("cursor"),
+        )));
+        let foo = dir.path().join("cursor").join("foo");
+        fs::create_dir_all(&foo).unwrap();
+        fs::write(foo.join("SKILL.md"), "# Foo").unwrap();
+
+        let plan = engine.preview_full_sync(|_| SyncStrategy::Copy).unwrap();
+        assert_eq!(plan.to_collect.len(), 1);
+        assert_eq!(plan.to_collect[0].skill_id, skill_id("foo"));
+        assert_eq!(plan.to_collect[0].source_path, foo);
+        let added: Vec<(String, ToolType, PathBuf)> = plan
+            .to_add
+            .iter()
+            .map(|a| (a.skill_id.to_string(), a.tool, a.target_path.clone()))
+            .collect();
+        assert_eq!(
+            added,
+            vec![
+                (
+                    "demo".to_string(),
+                    ToolType::Claude,
+                    dir.path().join("claude").join("demo")
+                ),
+                (
+                    "demo".to_string(),
+                    ToolType::Cursor,
+                    dir.path().join("cursor").join("demo")
+                ),
+                (
+                    "foo".to_string(),
+                    ToolType::Claude,
+                    dir.path().join("claude").join("foo")
+                ),
+            ]
+        );
+        assert!(!dir.path().join("claude").join("demo").exists());
+        assert!(!engine.store().is_installed(&skill_id("foo")));
+
+        // Explicit syncs over a folder SkillsHub did not create say so
+        let demo = dir.path().join("claude").join("demo");
+        fs::create_dir_all(&demo).unwrap();
+        let plan = engine
+            .plan_sync(&skill_id("demo"), &[ToolType::Claude], SyncStrategy::Copy)
+            .unwrap();
+        assert!(plan.to_add[0].overwrites);
+        assert!(demo.exists());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_skill_is_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let id = skill_id("demo");
+        let target = dir.path().join("claude").join("demo");
+
+        engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Link)
+            .unwrap();
+        engine.store_mut().remove_skill(&id).unwrap();
+        assert!(target.is_symlink() && !target.exists());
+
+        let plan = engine.plan_full_sync(|_| SyncStrategy::Link).unwrap();
+        assert_eq!(plan.to_remove.len(), 1);
+        assert_eq!(plan.to_remove[0].strategy, SyncStrategy::Link);
+        let result = engine.full_sync(|_| SyncStrategy::Link).unwrap();
+        assert_eq!(result.removed, vec![(id.clone(), ToolType::Claude, true)]);
+        assert!(!target.is_symlink());
+        assert!(engine
+            .plan_full_sync(|_| SyncStrategy::Link)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_leaves_foreign_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let id = skill_id("demo");
+        let target = dir.path().join("claude").join("demo");
+
+        engine
+            .sync_skill(&id, ToolType::Claude, SyncStrategy::Link)
+            .unwrap();
+        engine.store_mut().remove_skill(&id).unwrap();
+        // Someone replaced the link with their own skill
+        fs::remove_file(&target).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "# Mine").unwrap();
+
+        let removed = engine.prune_projections().unwrap();
+        assert_eq!(removed, vec![(id.clone(), ToolType::Claude, false)]);
+        assert_eq!(
+            fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "# Mine"
+        );
+        // The projection is forgotten, so nothing is planned any more
+        assert!(engine.prune_projections().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_targets_per_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        engine.register_adapter(Box::new(crate::adapters::CursorAdapter::with_path(
+            dir.path().join("cursor"),
+        )));
+
+        let source = dir.path().join("source").join("review");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join("SKILL.md"),
+            "---\ncompatible_tools: [cursor]\n---\n# Review",
+        )
+        .unwrap();
+        let review = Skill {
+            id: skill_id("review"),
+            version: SkillVersion::new("1.0.0", ""),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            ..collected_skill(&skill_id("review"), &source)
+        };
+        engine
+            .store_mut()
+            .import_skill(&review, &source)
+            .await
+            .unwrap();
+        // demo is explicitly assigned to Claude Code, review declares Cursor
+        engine
+            .store_mut()
+            .update_projected_tools(&skill_id("demo"), vec!["claude".to_string()])
+            .unwrap();
+
+        let planned = |plan: &SyncPlan| -> Vec<(String, ToolType)> {
+            plan.to_add
+                .iter()
+                .map(|a| (a.skill_id.to_string(), a.tool))
+                .collect()
+        };
+        let plan = engine.plan_full_sync(|_| SyncStrategy::Copy).unwrap();
+        assert_eq!(
+            planned(&plan),
+            vec![
+                ("demo".to_string(), ToolType::Claude),
+                ("review".to_string(), ToolType::Cursor)
+            ]
+        );
+
+        // Disabled tools are skipped
+        let mut claude = ToolProfile::new(ToolType::Claude);
+        claude.enabled = false;
+        engine.set_tool_profiles([claude]);
+        let result = engine.full_sync(|_| SyncStrategy::Copy).unwrap();
+        assert_eq!(
+            planned(&result.plan),
+            vec![("review".to_string(), ToolType::Cursor)]
+        );
+        assert!(dir.path().join("cursor").join("review").exists());
+        assert!(!dir.path().join("cursor").join("demo").exists());
+        assert!(!dir.path().join("claude").join("demo").exists());
+
+        // Nothing left to do
+        assert!(engine
+            .plan_full_sync(|_| SyncStrategy::Copy)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -3,10 +3,13 @@
 //! Provides functionality to check for updates to installed skills by comparing
 //! local versions with remote registry versions.
 
+use std::fs;
+
 use serde::Serialize;
 
 use crate::error::Result;
-use crate::models::SkillVersion;
+use crate::gc::TEMP_INSTALL_PREFIX;
+use crate::models::{InstallRecord, Skill, SkillId, SkillVersion};
 use crate::registry::AggregatedRegistry;
 use crate::store::LocalStore;
 
@@ -14,7 +17,7 @@ use crate::store::LocalStore;
 #[derive(Debug, Clone, Serialize)]
 pub struct UpdateInfo {
     /// Skill ID
-    pub skill_id: String,
+    pub skill_id: SkillId,
     /// Currently installed version string
     pub current_version: String,
     /// Current content hash
@@ -31,9 +34,9 @@ pub struct UpdateInfo {
 
 impl UpdateInfo {
     /// Create an UpdateInfo indicating no update is available
-    pub fn no_update(skill_id: &str, version: &SkillVersion) -> Self {
+    pub fn no_update(skill_id: &SkillId, version: &SkillVersion) -> Self {
         Self {
-            skill_id: skill_id.to_string(),
+            skill_id: skill_id.clone(),
             current_version: version.version.clone(),
             current_hash: version.content_hash.clone(),
             latest_version: version.version.clone(),
@@ -45,13 +48,13 @@ impl UpdateInfo {
 
     /// Create an UpdateInfo indicating an update is available
     pub fn with_update(
-        skill_id: &str,
+        skill_id: &SkillId,
         current: &SkillVersion,
         latest: &SkillVersion,
         registry: Option<String>,
     ) -> Self {
         Self {
-            skill_id: skill_id.to_string(),
+            skill_id: skill_id.clone(),
             current_version: current.version.clone(),
             current_hash: current.content_hash.clone(),
             latest_version: latest.version.clone(),
@@ -83,7 +86,7 @@ pub async fn check_all_updates(
 
 /// Check for update for a single skill
 pub async fn check_skill_update(
-    skill_id: &str,
+    skill_id: &SkillId,
     current_version: &SkillVersion,
     registry: &AggregatedRegistry,
 ) -> UpdateInfo {
@@ -118,14 +121,47 @@ pub async fn get_available_updates(
     Ok(all_updates.into_iter().filter(|u| u.has_update).collect())
 }
 
+/// Fetch a skill from the registries and import it into the store
+///
+/// The fetched files are scanned before they are committed, see
+/// [`LocalStore::import_scanned`].
+///
+/// The skill is stored under `skill_id`, not the registry-qualified ID the
+/// registries report it as, so updating an installed skill replaces its
+/// record instead of adding a second one next to it.
+pub async fn install_from_registry(
+    store: &mut LocalStore,
+    registry: &AggregatedRegistry,
+    skill_id: &SkillId,
+) -> Result<InstallRecord> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("{}{}", TEMP_INSTALL_PREFIX, skill_id.file_name()));
+    let fetched = registry.fetch_skill(skill_id, &temp_dir).await;
+    let result = match fetched {
+        Ok((skill, path, _)) => {
+            let skill = Skill {
+                id: skill_id.clone(),
+                ..skill
+            };
+            store.import_scanned(&skill, &path)
+        }
+        Err(e) => Err(e),
+    };
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::LocalRegistry;
+    use crate::store::StoreConfig;
+    use tempfile::TempDir;
 
     #[test]
     fn test_update_info_no_update() {
         let version = SkillVersion::new("1.0.0", "abc123");
-        let info = UpdateInfo::no_update("test-skill", &version);
+        let info = UpdateInfo::no_update(&SkillId::new("test-skill").unwrap(), &version);
 
         assert_eq!(info.skill_id, "test-skill");
         assert!(!info.has_update);
@@ -138,7 +174,7 @@ mod tests {
         let current = SkillVersion::new("1.0.0", "abc123");
         let latest = SkillVersion::new("1.1.0", "def456");
         let info = UpdateInfo::with_update(
-            "test-skill",
+            &SkillId::new("test-skill").unwrap(),
             &current,
             &latest,
             Some("test-registry".to_string()),
@@ -149,4 +185,44 @@ mod tests {
         assert_eq!(info.latest_version, "1.1.0");
         assert_eq!(info.source_registry, Some("test-registry".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_update_keeps_installed_id() {
+        let dir = TempDir::new().unwrap();
+        let skill_dir = dir.path().join("registry").join("foo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "---\nversion: 1.0.0\n---\n").unwrap();
+        let mut registry = AggregatedRegistry::new();
+        registry.add_registry(Box::new(LocalRegistry::new(
+            "local",
+            dir.path().join("registry"),
+        )));
+        let mut store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let foo = SkillId::new("foo").unwrap();
+        install_from_registry(&mut store, &registry, &foo)
+            .await
+            .unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "---\nversion: 1.1.0\n---\n").unwrap();
+        let record = install_from_registry(&mut store, &registry, &foo)
+            .await
+            .unwrap();
+
+        assert_eq!(record.skill_id, foo);
+        assert_eq!(record.version.version, "1.1.0");
+        let installed = store.list_installed();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].skill_id, foo);
+
+        // An update the security scan blocks leaves the installed version active
+        fs::write(skill_dir.join("helper.exe"), "MZ").unwrap();
+        assert!(install_from_registry(&mut store, &registry, &foo)
+            .await
+            .is_err());
+        assert_eq!(store.get_record(&foo).unwrap().version.version, "1.1.0");
+    }
 }
@@ -0,0 +1,391 @@
+//! Keep tool directories in sync while the store or a tool directory changes
+//!
+//! [`watch`] listens for file system events in the directories returned by
+//! [`SyncEngine::watched_dirs`]. Events are debounced; once a burst has
+//! settled, [`sync_pass`] collects new tool-side skills into the hub, applies
+//! the full sync plan, prunes projections of removed skills and repairs
+//! drifted projections. The pass's own writes trigger one more pass, which
+//! finds nothing to do.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::models::{DriftType, SkillId, SyncStrategy, ToolType};
+use crate::sync::SyncEngine;
+
+/// Quiet period after the last event before a pass runs
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How often the stop flag is checked while no events arrive
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What one sync pass did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchReport {
+    /// Paths whose changes triggered the pass (empty for the initial pass)
+    pub changed: Vec<PathBuf>,
+    /// Skills collected from tool directories into the hub
+    pub collected: Vec<SkillId>,
+    /// Skills whose tool copies differ; they are left for the user to resolve
+    pub conflicts: Vec<SkillId>,
+    /// Projections created or refreshed: `(skill, tool, success)`
+    pub distributed: Vec<(SkillId, ToolType, bool)>,
+    /// Projections removed: `(skill, tool, success)`
+    pub removed: Vec<(SkillId, ToolType, bool)>,
+    /// Drifted projections re-synced: `(skill, tool, success)`
+    pub repaired: Vec<(SkillId, ToolType, bool)>,
+    /// Error that cut the pass short
+    pub error: Option<String>,
+}
+
+impl WatchReport {
+    /// Whether the pass changed nothing and hit no errors
+    pub fn is_empty(&self) -> bool {
+        self.collected.is_empty()
+            && self.conflicts.is_empty()
+            && self.distributed.is_empty()
+            && self.removed.is_empty()
+            && self.repaired.is_empty()
+            && self.error.is_none()
+    }
+}
+
+/// Bring the engine's scope in line with the store once
+///
+/// Copies edited inside a tool directory (`content modified`) are reported
+/// but not overwritten; `skillshub sync --reconcile` does that on request.
+pub fn sync_pass<F>(engine: &mut SyncEngine, strategy_resolver: F) -> Result<WatchReport>
+where
+    F: Fn(ToolType) -> SyncStrategy,
+{
+    let result = engine.full_sync(&strategy_resolver)?;
+    let mut report = WatchReport {
+        collected: result.collected_skills,
+        conflicts: result.conflicts.into_iter().map(|c| c.skill_id).collect(),
+        distributed: result.distributed,
+        removed: result.removed,
+        ..Default::default()
+    };
+    report.removed.extend(engine.prune_projections()?);
+
+    for (skill_id, tool, drift) in engine.check_drift() {
+        // Plugin skills are not in the store and cannot be re-synced from it
+        if engine.store().is_plugin_record(&skill_id) {
+            continue;
+        }
+        if drift.drift_type == DriftType::ContentModified {
+            tracing::warn!(
+                "{} in {} was edited in place ({} file(s)); leaving it alone",
+                skill_id,
+                tool.display_name(),
+                drift.files.len()
+            );
+            continue;
+        }
+        let success = match engine.sync_skill(&skill_id, tool, strategy_resolver(tool)) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to repair {} in {}: {}",
+                    skill_id,
+                    tool.display_name(),
+                    e
+                );
+                false
+            }
+        };
+        report.repaired.push((skill_id, tool, success));
+    }
+
+    log_report(&report);
+    Ok(report)
+}
+
+/// Watch the engine's directories until `stop` is set
+///
+/// Runs a pass on start and after every burst of changes, handing each report
+/// to `on_pass`. A failed pass (e.g. the store is busy) is reported and the
+/// watch goes on. Tool directories that appear later are picked up after the
+/// next pass.
+pub fn watch<F, R>(
+    engine: &mut SyncEngine,
+    debounce: Duration,
+    stop: &AtomicBool,
+    strategy_resolver: F,
+    mut on_pass: R,
+) -> Result<()>
+where
+    F: Fn(ToolType) -> SyncStrategy,
+    R: FnMut(&WatchReport),
+{
+    let (tx, rx) = mpsc::channel();
+    // Links are not followed, so projections do not report changes to the store's files
+    let mut watcher = RecommendedWatcher::new(tx, Config::default().with_follow_symlinks(false))
+        .map_err(|e| Error::System(format!("Cannot start file watcher: {}", e)))?;
+    let mut watched = HashSet::new();
+
+    let mut changed = BTreeSet::new();
+    loop {
+        let mut report = sync_pass(engine, &strategy_resolver).unwrap_or_else(|e| {
+            tracing::warn!("Sync pass failed: {}", e);
+            WatchReport {
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        });
+        report.changed = std::mem::take(&mut changed).into_iter().collect();
+        watch_new_dirs(engine, &mut watcher, &mut watched);
+        on_pass(&report);
+
+        // Wait for the first change, then until changes stop for `debounce`
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(event) => {
+                    add_changes(event, &mut changed);
+                    if !changed.is_empty() {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+        while let Ok(event) = rx.recv_timeout(debounce) {
+            add_changes(event, &mut changed);
+        }
+    }
+}
+
+/// Start watching directories that exist now but were not watched yet
+///
+/// The store's `skills/` directory only holds links, so it is watched on its
+/// own; tool directories are watched recursively to see skills being written.
+fn watch_new_dirs(
+    engine: &SyncEngine,
+    watcher: &mut RecommendedWatcher,
+    watched: &mut HashSet<PathBuf>,
+) {
+    let store_skills = engine.store().skills_dir();
+    for dir in engine.watched_dirs() {
+        if watched.contains(&dir) || !dir.is_dir() {
+            continue;
+        }
+        let mode = if dir == store_skills {
+            RecursiveMode::NonRecursive
+        } else {
+            RecursiveMode::Recursive
+        };
+        match watcher.watch(&dir, mode) {
+            Ok(()) => {
+                tracing::info!("Watching {}", dir.display());
+                watched.insert(dir);
+            }
+            Err(e) => tracing::warn!("Cannot watch {}: {}", dir.display(), e),
+        }
+    }
+}
+
+/// Record the paths of an event that can change what a sync does
+///
+/// Reads (which every pass does while hashing) are ignored so passes do not
+/// trigger each other.
+fn add_changes(event: notify::Result<Event>, changed: &mut BTreeSet<PathBuf>) {
+    match event {
+        Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+            tracing::debug!("{:?}: {:?}", event.kind, event.paths);
+            changed.extend(event.paths);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("File watcher error: {}", e),
+    }
+}
+
+/// Log every action of a pass
+fn log_report(report: &WatchReport) {
+    for skill_id in &report.collected {
+        tracing::info!("Collected {} into the hub", skill_id);
+    }
+    for skill_id in &report.conflicts {
+        tracing::warn!(
+            "Tools hold differing copies of {}; run `skillshub collect {}` to pick one",
+            skill_id,
+            skill_id
+        );
+    }
+    let actions = [
+        ("Synced", &report.distributed),
+        ("Removed", &report.removed),
+        ("Repaired", &report.repaired),
+    ];
+    for (verb, entries) in actions {
+        for (skill_id, tool, success) in entries {
+            if *success {
+                tracing::info!("{} {} in {}", verb, skill_id, tool.display_name());
+            } else {
+                tracing::warn!("{} {} in {} failed", verb, skill_id, tool.display_name());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::ClaudeAdapter;
+    use crate::models::{Skill, SkillSource, SkillVersion};
+    use crate::store::{LocalStore, StoreConfig};
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Engine with a `demo` skill in its store and a Claude directory
+    async fn engine_with_skill(root: &Path) -> SyncEngine {
+        let source = root.join("source").join("demo");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Demo").unwrap();
+
+        let mut store = LocalStore::new(StoreConfig {
+            root_dir: root.join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let skill = Skill {
+            id: SkillId::new("demo").unwrap(),
+            name: "Demo".to_string(),
+            description: String::new(),
+            author: None,
+            tags: Vec::new(),
+            compatible_tools: Vec::new(),
+            version: SkillVersion::new("1.0.0", crate::hash::digest(&source).unwrap()),
+            source: SkillSource::Local {
+                path: source.clone(),
+            },
+            skill_md_path: source.join("SKILL.md"),
+            resources: Vec::new(),
+            metadata: Default::default(),
+        };
+        store.import_skill(&skill, &source).await.unwrap();
+
+        let claude = root.join("claude");
+        fs::create_dir_all(&claude).unwrap();
+        let mut engine = SyncEngine::new(store);
+        engine.register_adapter(Box::new(ClaudeAdapter::with_path(claude)));
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_pass_collects_and_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let claude = dir.path().join("claude");
+
+        // The first pass projects the hub's skills
+        let first = sync_pass(&mut engine, |_| SyncStrategy::Copy).unwrap();
+        assert_eq!(first.distributed.len(), 1);
+        assert!(claude.join("demo").join("SKILL.md").exists());
+
+        // A skill written into a tool directory is collected
+        let written = claude.join("written");
+        fs::create_dir_all(&written).unwrap();
+        fs::write(written.join("SKILL.md"), "# Written").unwrap();
+        let second = sync_pass(&mut engine, |_| SyncStrategy::Copy).unwrap();
+        assert_eq!(second.collected, vec![SkillId::new("written").unwrap()]);
+
+        // Once everything is in line a pass does nothing
+        assert!(sync_pass(&mut engine, |_| SyncStrategy::Copy)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Relies on file system events arriving in time, so it only runs on request
+    #[tokio::test]
+    #[ignore]
+    async fn test_watch_collects_and_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_skill(dir.path()).await;
+        let claude = dir.path().join("claude");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        let handle = {
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                watch(
+                    &mut engine,
+                    Duration::from_millis(100),
+                    &stop,
+                    |_| SyncStrategy::Copy,
+                    |report| tx.send(report.clone()).unwrap(),
+                )
+            })
+        };
+
+        // The initial pass projects the hub's skills
+        let first = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(first.distributed.len(), 1);
+
+        // A skill written into a tool directory is collected
+        let written = claude.join("written");
+        fs::create_dir_all(&written).unwrap();
+        fs::write(written.join("SKILL.md"), "# Written").unwrap();
+        let collected = loop {
+            let report = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            if !report.collected.is_empty() {
+                break report;
+            }
+        };
+        assert_eq!(collected.collected, vec![SkillId::new("written").unwrap()]);
+        assert!(!collected.changed.is_empty());
+
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_pass_leaves_plugin_skills_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(StoreConfig {
+            root_dir: dir.path().join("store"),
+            ..Default::default()
+        })
+        .unwrap();
+        let claude = dir.path().join("claude");
+        fs::create_dir_all(&claude).unwrap();
+        let mut engine = SyncEngine::new(store);
+        engine.register_adapter(Box::new(ClaudeAdapter::with_path(claude.clone())));
+
+        let plugin = dir.path().join("plugins").join("review");
+        fs::create_dir_all(&plugin).unwrap();
+        fs::write(plugin.join("SKILL.md"), "# Review").unwrap();
+        let id = SkillId::new("plugin@market:review").unwrap();
+        engine
+            .sync_plugin_skill(&plugin, &id, ToolType::Claude, SyncStrategy::Link)
+            .unwrap();
+        engine
+            .store_mut()
+            .register_plugin_skill(&id, &plugin, vec!["claude".to_string()])
+            .unwrap();
+        let projection = engine.state().tools[&ToolType::Claude.to_string()].skills[&id]
+            .target_path
+            .clone();
+
+        // The link into the plugin is neither repaired from the store nor replaced
+        let report = sync_pass(&mut engine, |_| SyncStrategy::Copy).unwrap();
+        assert!(report.repaired.is_empty());
+        assert!(projection.is_symlink());
+        assert_eq!(
+            fs::read_to_string(projection.join("SKILL.md")).unwrap(),
+            "# Review"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Backup commands - entries in tool directories moved aside by syncs
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::backup::{self, BackupEntry};
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<BackupEntry>, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    backup::list(&store).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn restore_backup(backup_id: String) -> Result<BackupEntry, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.restore_backup(&backup_id).map_err(|e| e.to_string())
+}
@@ -6,8 +6,10 @@ use skillshub_core::cloud_sync::{
     CloudSyncEngine, CloudSyncResult as CoreCloudSyncResult, DetectedCloudDrive,
 };
 use skillshub_core::config::AppConfig;
+use tauri::AppHandle;
 
 use super::types::CloudSyncResponse;
+use super::watch;
 
 impl From<CoreCloudSyncResult> for CloudSyncResponse {
     fn from(r: CoreCloudSyncResult) -> Self {
@@ -53,13 +55,16 @@ pub async fn cloud_sync_pull() -> Result<CloudSyncResponse, String> {
 }
 
 #[tauri::command]
-pub async fn cloud_sync_full() -> Result<CloudSyncResponse, String> {
+pub async fn cloud_sync_full(app: AppHandle) -> Result<CloudSyncResponse, String> {
     let engine = create_cloud_engine()?;
     let result = engine.sync().map_err(|e| e.to_string())?;
 
-    let mut config = AppConfig::load_or_default();
+    // A config that fails to load must not be replaced by defaults on save
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
     config.cloud_sync.last_sync = Some(CloudSyncEngine::now_timestamp());
     config.save().map_err(|e| e.to_string())?;
+    // Like every config save, restart a running watcher so it reads the saved file
+    watch::restart(app)?;
 
     Ok(result.into())
 }
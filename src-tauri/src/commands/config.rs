@@ -3,8 +3,10 @@
 use std::path::PathBuf;
 
 use skillshub_core::config::AppConfig;
+use tauri::AppHandle;
 
 use super::types::StoreInfo;
+use super::watch;
 
 #[tauri::command]
 pub async fn get_app_config() -> Result<AppConfig, String> {
@@ -12,8 +14,10 @@ pub async fn get_app_config() -> Result<AppConfig, String> {
 }
 
 #[tauri::command]
-pub async fn save_app_config(config: AppConfig) -> Result<(), String> {
-    config.save().map_err(|e| e.to_string())
+pub async fn save_app_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
+    config.save().map_err(|e| e.to_string())?;
+    // A running watcher resolves strategies and profiles from the config it started with
+    watch::restart(app)
 }
 
 fn calc_dir_size(path: &std::path::Path) -> u64 {
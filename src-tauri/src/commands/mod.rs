@@ -2,8 +2,10 @@
 //!
 //! Split into focused modules for maintainability:
 //! - `types` - Shared response types and utility functions
+//! - `backups` - Restoring entries moved aside by syncs
 //! - `skills` - Skill CRUD, detail, and update checking
 //! - `sync` - Multi-tool synchronization and drift detection
+//! - `watch` - Background watcher keeping tools in sync
 //! - `security` - Security scanning and scan records
 //! - `tools` - Tool detection and custom tool management
 //! - `registry` - Registry search and management
@@ -11,6 +13,7 @@
 //! - `cloud` - Cloud drive sync operations
 //! - `config` - App configuration, store info, and utilities
 
+pub mod backups;
 pub mod cloud;
 pub mod config;
 pub mod plugins;
@@ -20,8 +23,10 @@ pub mod skills;
 pub mod sync;
 pub mod tools;
 pub mod types;
+pub mod watch;
 
 // Re-export all commands for use in lib.rs
+pub use backups::*;
 pub use cloud::*;
 pub use config::*;
 pub use plugins::*;
@@ -30,3 +35,4 @@ pub use security::*;
 pub use skills::*;
 pub use sync::*;
 pub use tools::*;
+pub use watch::*;
@@ -5,7 +5,7 @@ use std::path::PathBuf;
 
 use skillshub_core::adapters::create_default_adapters;
 use skillshub_core::config::AppConfig;
-use skillshub_core::models::ToolType;
+use skillshub_core::models::{SkillId, ToolType};
 use skillshub_core::plugins::{PluginScanner, PluginSkill};
 use skillshub_core::store::LocalStore;
 use skillshub_core::sync::SyncEngine;
@@ -27,7 +27,8 @@ pub struct PluginSkillInfo {
 impl From<PluginSkill> for PluginSkillInfo {
     fn from(skill: PluginSkill) -> Self {
         Self {
-            id: skill.id(),
+            // Plugins with an unsafe ID are listed but cannot be synced
+            id: skill.id().map(String::from).unwrap_or_default(),
             plugin_name: skill.plugin_name,
             marketplace: skill.marketplace,
             skill_name: skill.skill_name,
@@ -100,12 +101,15 @@ pub async fn sync_plugin_skill(
     skillId: String,
     tools: Vec<String>,
 ) -> Result<Vec<SyncResult>, String> {
+    let skill_id = SkillId::new(&skillId).map_err(|e| e.to_string())?;
     let engine_store = LocalStore::default_store().map_err(|e| e.to_string())?;
     let mut engine = SyncEngine::new(engine_store);
 
     for adapter in create_default_adapters() {
         engine.register_adapter(adapter);
     }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
 
     let source = PathBuf::from(&skillPath);
     let mut results = Vec::new();
@@ -122,11 +126,9 @@ pub async fn sync_plugin_skill(
             continue;
         }
 
-        let config = AppConfig::load_or_default();
-        let tool_key = format!("{:?}", tool).to_lowercase();
-        let strategy = config.strategy_for_tool(&tool_key);
+        let strategy = config.strategy_for_tool(tool.key());
 
-        let result = engine.sync_plugin_skill(&source, &skillId, tool, strategy);
+        let result = engine.sync_plugin_skill(&source, &skill_id, tool, strategy);
         let success = result.is_ok();
         if success {
             synced_tools.push(tool_str.clone());
@@ -140,9 +142,9 @@ pub async fn sync_plugin_skill(
     }
 
     if !synced_tools.is_empty() {
-        if let Ok(mut register_store) = LocalStore::default_store() {
-            let _ = register_store.register_plugin_skill(&skillId, &source, synced_tools);
-        }
+        let _ = engine
+            .store_mut()
+            .register_plugin_skill(&skill_id, &source, synced_tools);
     }
 
     Ok(results)
@@ -27,7 +27,7 @@ pub async fn search_skills(query: String) -> Result<Vec<SkillInfo>, String> {
     Ok(listings
         .into_iter()
         .map(|l| SkillInfo {
-            id: l.id,
+            id: l.id.to_string(),
             name: l.name,
             version: l.version,
             description: l.description,
@@ -35,6 +35,7 @@ pub async fn search_skills(query: String) -> Result<Vec<SkillInfo>, String> {
             installed_at: String::new(),
             scan_passed: false,
             synced_tools: Vec::new(),
+            disabled: false,
             author: l.author,
             tags: l.tags,
             downloads: l.downloads,
@@ -2,13 +2,17 @@
 
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+use skillshub_core::models::SkillId;
 use skillshub_core::scanner::SecurityScanner;
+use skillshub_core::schema::{self, Versioned};
 use skillshub_core::store::LocalStore;
 
 use super::types::{Finding, ScanResult, SecurityRuleInfo, SecurityScanRecord};
 
 #[tauri::command]
 pub async fn scan_skill(skill_id: String) -> Result<ScanResult, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
     let skill_path = store.skill_path(&skill_id);
 
@@ -18,11 +22,11 @@ pub async fn scan_skill(skill_id: String) -> Result<ScanResult, String> {
 
     let scanner = SecurityScanner::new();
     let report = scanner
-        .scan(&skill_id, &skill_path)
+        .scan(skill_id.as_str(), &skill_path)
         .map_err(|e| e.to_string())?;
 
     Ok(ScanResult {
-        skill_id,
+        skill_id: skill_id.to_string(),
         passed: report.passed,
         overall_risk: report.overall_risk.to_string(),
         findings: report
@@ -64,27 +68,37 @@ fn security_scan_records_path() -> Result<PathBuf, String> {
         .join("security_scan_records.json"))
 }
 
+/// Contents of `security_scan_records.json`
+#[derive(Serialize, Deserialize)]
+struct SecurityScanRecordsFile {
+    records: Vec<SecurityScanRecord>,
+}
+
+/// v0 was a bare list of records
+impl Versioned for SecurityScanRecordsFile {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(
+        _from: u32,
+        document: serde_json::Value,
+    ) -> skillshub_core::Result<serde_json::Value> {
+        schema::wrap_list(document, "records")
+    }
+}
+
 fn load_security_scan_records_from_file() -> Result<Vec<SecurityScanRecord>, String> {
     let path = security_scan_records_path()?;
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let content = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read security scan records: {}", e))?;
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse security scan records: {}", e))
+    let file = schema::load::<SecurityScanRecordsFile>(&path)
+        .map_err(|e| format!("Failed to load security scan records: {}", e))?;
+    Ok(file.map(|file| file.records).unwrap_or_default())
 }
 
 fn save_security_scan_records_to_file(records: &[SecurityScanRecord]) -> Result<(), String> {
     let path = security_scan_records_path()?;
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
-    }
-    let content = serde_json::to_string_pretty(records)
-        .map_err(|e| format!("Failed to serialize security scan records: {}", e))?;
-    std::fs::write(&path, content)
-        .map_err(|e| format!("Failed to write security scan records: {}", e))
+    let file = SecurityScanRecordsFile {
+        records: records.to_vec(),
+    };
+    schema::save(&path, &file).map_err(|e| format!("Failed to write security scan records: {}", e))
 }
 
 #[tauri::command]
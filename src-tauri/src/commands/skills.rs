@@ -1,10 +1,16 @@
 //! Skill CRUD and detail commands
 
 use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::config::AppConfig;
+use skillshub_core::models::{IntegrityIssueType, SkillId, VerifyReport};
 use skillshub_core::registry::{AggregatedRegistry, RegistryManager};
 use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::update::install_from_registry;
 
-use super::types::{SkillDetailInfo, SkillFileInfo, SkillInfo, SyncedToolInfo, UpdateCheckInfo};
+use super::types::{
+    DistributedSkill, SkillDetailInfo, SkillFileInfo, SkillInfo, SyncedToolInfo, UpdateCheckInfo,
+};
 
 #[tauri::command]
 pub async fn list_installed_skills() -> Result<Vec<SkillInfo>, String> {
@@ -14,14 +20,15 @@ pub async fn list_installed_skills() -> Result<Vec<SkillInfo>, String> {
     Ok(installed
         .into_iter()
         .map(|r| SkillInfo {
-            id: r.skill_id.clone(),
-            name: r.skill_id.clone(),
+            id: r.skill_id.to_string(),
+            name: r.skill_id.to_string(),
             version: r.version.version.clone(),
             description: String::new(),
             source: r.source.display(),
             installed_at: r.installed_at.clone(),
             scan_passed: r.scan_passed,
             synced_tools: r.projected_tools.clone(),
+            disabled: r.disabled,
             author: None,
             tags: Vec::new(),
             downloads: None,
@@ -32,18 +39,20 @@ pub async fn list_installed_skills() -> Result<Vec<SkillInfo>, String> {
 
 #[tauri::command]
 pub async fn get_skill_info(skill_id: String) -> Result<SkillInfo, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
 
     if let Some(record) = store.get_record(&skill_id) {
         return Ok(SkillInfo {
-            id: record.skill_id.clone(),
-            name: record.skill_id.clone(),
+            id: record.skill_id.to_string(),
+            name: record.skill_id.to_string(),
             version: record.version.version.clone(),
             description: String::new(),
             source: record.source.display(),
             installed_at: record.installed_at.clone(),
             scan_passed: record.scan_passed,
             synced_tools: record.projected_tools.clone(),
+            disabled: record.disabled,
             author: None,
             tags: Vec::new(),
             downloads: None,
@@ -65,14 +74,15 @@ pub async fn get_skill_info(skill_id: String) -> Result<SkillInfo, String> {
             .unwrap_or_default();
 
         return Ok(SkillInfo {
-            id: skill_id.clone(),
-            name: skill_id.clone(),
+            id: skill_id.to_string(),
+            name: skill_id.to_string(),
             version: "unknown".to_string(),
             description: String::new(),
             source: "scanned".to_string(),
             installed_at,
             scan_passed: true,
             synced_tools: Vec::new(),
+            disabled: false,
             author: None,
             tags: Vec::new(),
             downloads: None,
@@ -85,6 +95,7 @@ pub async fn get_skill_info(skill_id: String) -> Result<SkillInfo, String> {
 
 #[tauri::command]
 pub async fn get_skill_detail(skill_id: String) -> Result<SkillDetailInfo, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
     let skill_path = store.skill_path(&skill_id);
 
@@ -124,7 +135,7 @@ pub async fn get_skill_detail(skill_id: String) -> Result<SkillDetailInfo, Strin
     for adapter in adapters {
         if adapter.detect() {
             let tool_name = adapter.tool_type().display_name().to_string();
-            let tool_type = format!("{:?}", adapter.tool_type()).to_lowercase();
+            let tool_type = adapter.tool_type().key().to_string();
 
             let tool_dirs = adapter.skills_dirs();
             let mut is_synced = false;
@@ -132,7 +143,7 @@ pub async fn get_skill_detail(skill_id: String) -> Result<SkillDetailInfo, Strin
             let mut synced_path: Option<String> = None;
 
             for dir in tool_dirs {
-                let potential_path = dir.join(&skill_id);
+                let potential_path = dir.join(skill_id.file_name());
                 if potential_path.exists() {
                     is_synced = true;
                     is_link = potential_path.is_symlink();
@@ -152,8 +163,8 @@ pub async fn get_skill_detail(skill_id: String) -> Result<SkillDetailInfo, Strin
     }
 
     Ok(SkillDetailInfo {
-        id: skill_id.clone(),
-        name: skill_id,
+        id: skill_id.to_string(),
+        name: skill_id.to_string(),
         skill_path: skill_path.display().to_string(),
         skill_md_content,
         files,
@@ -163,6 +174,7 @@ pub async fn get_skill_detail(skill_id: String) -> Result<SkillDetailInfo, Strin
 
 #[tauri::command]
 pub async fn install_skill(skill_id: String, _tools: Vec<String>) -> Result<String, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let manager = RegistryManager::new().map_err(|e| e.to_string())?;
     let mut aggregated = AggregatedRegistry::new();
 
@@ -174,38 +186,40 @@ pub async fn install_skill(skill_id: String, _tools: Vec<String>) -> Result<Stri
         }
     }
 
-    match aggregated.get_skill(&skill_id).await {
-        Ok(remote_skill) => {
-            let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
-
-            let skill_path = store.skill_path(&skill_id);
-            if skill_path.exists() {
-                return Err(format!("Skill '{}' is already installed", skill_id));
-            }
-
-            store
-                .import_skill(&remote_skill, &remote_skill.skill_md_path)
-                .await
-                .map_err(|e| e.to_string())?;
+    let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    if store.is_installed(&skill_id) {
+        return Err(format!("Skill '{}' is already installed", skill_id));
+    }
 
-            Ok(format!(
-                "Skill '{}' v{} installed successfully",
-                skill_id, remote_skill.version.version
-            ))
-        }
+    match install_from_registry(&mut store, &aggregated, &skill_id).await {
+        Ok(record) => Ok(format!(
+            "Skill '{}' v{} installed successfully",
+            skill_id, record.version.version
+        )),
         Err(e) => Err(format!("Failed to fetch skill '{}': {}", skill_id, e)),
     }
 }
 
 #[tauri::command]
 pub async fn uninstall_skill(skill_id: String) -> Result<(), String> {
-    let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
-    store.remove_skill(&skill_id).map_err(|e| e.to_string())?;
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.unsync_all(&skill_id).map_err(|e| e.to_string())?;
+    engine
+        .store_mut()
+        .remove_skill(&skill_id)
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
 #[tauri::command]
 pub async fn update_skill(skill_id: String) -> Result<String, String> {
+    let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let skill_id = store.resolve(&skill_id).map_err(|e| e.to_string())?;
     let manager = RegistryManager::new().map_err(|e| e.to_string())?;
     let mut aggregated = AggregatedRegistry::new();
 
@@ -217,27 +231,128 @@ pub async fn update_skill(skill_id: String) -> Result<String, String> {
         }
     }
 
-    match aggregated.get_skill(&skill_id).await {
-        Ok(remote_skill) => {
-            let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    // The previous version is retained in the store for rollback
+    match install_from_registry(&mut store, &aggregated, &skill_id).await {
+        Ok(record) => Ok(format!(
+            "Skill '{}' updated to version {}",
+            skill_id, record.version.version
+        )),
+        Err(e) => Err(format!("Failed to fetch update for '{}': {}", skill_id, e)),
+    }
+}
 
-            let skill_path = store.skill_path(&skill_id);
-            if skill_path.exists() {
-                store.remove_skill(&skill_id).map_err(|e| e.to_string())?;
-            }
+#[tauri::command]
+pub async fn rollback_skill(skill_id: String, version: Option<String>) -> Result<String, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
+    let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let record = store
+        .rollback(&skill_id, version.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    // Linked projections follow the store; copies need rewriting
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.refresh_skill(&skill_id).map_err(|e| e.to_string())?;
 
-            store
-                .import_skill(&remote_skill, &remote_skill.skill_md_path)
-                .await
-                .map_err(|e| e.to_string())?;
+    Ok(format!(
+        "Skill '{}' rolled back to version {}",
+        skill_id, record.version.version
+    ))
+}
 
-            Ok(format!(
-                "Skill '{}' updated to version {}",
-                skill_id, remote_skill.version.version
-            ))
+/// Set (or with `None`, clear) the directory name a skill has in tools
+#[tauri::command]
+pub async fn set_skill_alias(skill_id: String, alias: Option<String>) -> Result<String, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let skill_id = store.resolve(&skill_id).map_err(|e| e.to_string())?;
+    let alias = alias
+        .map(SkillId::new)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
+    // Existing projections are moved to the new name
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    engine.set_tool_profiles(AppConfig::load_or_default().tool_profiles());
+    engine
+        .set_alias(&skill_id, alias)
+        .map_err(|e| e.to_string())?;
+
+    Ok(engine.store().projection_name(&skill_id).to_string())
+}
+
+/// Keep a skill installed but remove it from every tool
+///
+/// Returns the tools it was removed from.
+#[tauri::command]
+pub async fn disable_skill(skill_id: String) -> Result<Vec<String>, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let skill_id = store.resolve(&skill_id).map_err(|e| e.to_string())?;
+
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let removed = engine.disable_skill(&skill_id).map_err(|e| e.to_string())?;
+
+    Ok(removed.iter().map(|t| format!("{:?}", t)).collect())
+}
+
+/// Let a disabled skill be synced again and project it into its target tools
+#[tauri::command]
+pub async fn enable_skill(skill_id: String) -> Result<Vec<DistributedSkill>, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let skill_id = store.resolve(&skill_id).map_err(|e| e.to_string())?;
+
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    let distributed = engine
+        .enable_skill(&skill_id, |tool| config.strategy_for_tool(tool.key()))
+        .map_err(|e| e.to_string())?;
+
+    Ok(distributed
+        .into_iter()
+        .map(|(skill, tool, success)| DistributedSkill {
+            skill_id: skill.to_string(),
+            tool: format!("{:?}", tool),
+            success,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn verify_store(repair: bool) -> Result<VerifyReport, String> {
+    let mut store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut report = store.verify().map_err(|e| e.to_string())?;
+    if !repair || report.is_clean() {
+        return Ok(report);
+    }
+
+    let manager = RegistryManager::new().map_err(|e| e.to_string())?;
+    store.repair(&mut report, &manager.aggregated()).await;
+
+    // Copies in tool directories still hold the damaged files
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    for issue in &report.issues {
+        if issue.repaired && issue.issue_type != IntegrityIssueType::Orphaned {
+            engine
+                .refresh_skill(&issue.skill_id)
+                .map_err(|e| e.to_string())?;
         }
-        Err(e) => Err(format!("Failed to fetch update for '{}': {}", skill_id, e)),
     }
+
+    Ok(report)
 }
 
 #[tauri::command]
@@ -262,7 +377,7 @@ pub async fn check_skill_updates() -> Result<Vec<UpdateCheckInfo>, String> {
             Ok(remote_skill) => {
                 let has_update = remote_skill.version.content_hash != record.version.content_hash;
                 UpdateCheckInfo {
-                    skill_id: record.skill_id.clone(),
+                    skill_id: record.skill_id.to_string(),
                     current_version: record.version.version.clone(),
                     current_hash: record.version.content_hash.clone(),
                     latest_version: remote_skill.version.version.clone(),
@@ -272,7 +387,7 @@ pub async fn check_skill_updates() -> Result<Vec<UpdateCheckInfo>, String> {
                 }
             }
             Err(_) => UpdateCheckInfo {
-                skill_id: record.skill_id.clone(),
+                skill_id: record.skill_id.to_string(),
                 current_version: record.version.version.clone(),
                 current_hash: record.version.content_hash.clone(),
                 latest_version: record.version.version.clone(),
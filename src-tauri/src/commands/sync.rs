@@ -2,6 +2,7 @@
 
 use skillshub_core::adapters::create_default_adapters;
 use skillshub_core::config::AppConfig;
+use skillshub_core::models::{ConflictResolution, SkillId, SyncPlan};
 use skillshub_core::store::LocalStore;
 use skillshub_core::sync::SyncEngine;
 
@@ -21,23 +22,25 @@ pub async fn sync_skills(
     for adapter in create_default_adapters() {
         engine.register_adapter(adapter);
     }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
 
     let mut results = Vec::new();
     let mut successful_tools_by_skill: std::collections::HashMap<
-        String,
+        SkillId,
         std::collections::HashSet<String>,
     > = std::collections::HashMap::new();
 
     for skill_id in skill_ids {
+        let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
         for tool_str in &tools {
             let tool = match parse_tool_type(tool_str) {
                 Some(t) => t,
                 None => continue,
             };
 
-            let config = AppConfig::load_or_default();
-            let tool_key = format!("{:?}", tool).to_lowercase();
-            let strategy = config.strategy_for_tool(&tool_key);
+            let strategy = config.strategy_for_tool(tool.key());
 
             let result = engine.sync_skill(&skill_id, tool, strategy);
             if result.is_ok() {
@@ -47,7 +50,7 @@ pub async fn sync_skills(
                     .insert(tool_str.to_lowercase());
             }
             results.push(SyncResult {
-                skill_id: skill_id.clone(),
+                skill_id: skill_id.to_string(),
                 tool: tool_str.clone(),
                 success: result.is_ok(),
                 error: result.err().map(|e| e.to_string()),
@@ -56,9 +59,8 @@ pub async fn sync_skills(
     }
 
     if !successful_tools_by_skill.is_empty() {
-        let mut record_store = LocalStore::default_store().map_err(|e| e.to_string())?;
         for (skill_id, synced_tools) in successful_tools_by_skill {
-            if let Some(record) = record_store.get_record(&skill_id) {
+            if let Some(record) = engine.store().get_record(&skill_id) {
                 let mut merged_tools: std::collections::HashSet<String> = record
                     .projected_tools
                     .iter()
@@ -68,7 +70,8 @@ pub async fn sync_skills(
 
                 let mut projected_tools: Vec<String> = merged_tools.into_iter().collect();
                 projected_tools.sort();
-                record_store
+                engine
+                    .store_mut()
                     .update_projected_tools(&skill_id, projected_tools)
                     .map_err(|e| e.to_string())?;
             }
@@ -86,18 +89,27 @@ pub async fn check_drift() -> Result<Vec<(String, String, String)>, String> {
     let drifts = engine.check_drift();
     Ok(drifts
         .into_iter()
-        .map(|(skill, tool, drift)| (skill, tool.to_string(), drift.drift_type.to_string()))
+        .map(|(skill, tool, drift)| {
+            (
+                skill.to_string(),
+                tool.to_string(),
+                drift.drift_type.to_string(),
+            )
+        })
         .collect())
 }
 
 #[tauri::command]
 pub async fn sync_single_skill(skill_id: String) -> Result<Vec<SyncResult>, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
     let mut engine = SyncEngine::new(store);
 
     let adapters = create_default_adapters();
 
     let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
 
     let mut results = Vec::new();
     let mut synced_tools = Vec::new();
@@ -105,7 +117,7 @@ pub async fn sync_single_skill(skill_id: String) -> Result<Vec<SyncResult>, Stri
     for adapter in &adapters {
         if adapter.detect() {
             let tool = adapter.tool_type();
-            let tool_name = format!("{:?}", tool).to_lowercase();
+            let tool_name = tool.key().to_string();
 
             let strategy = config.strategy_for_tool(&tool_name);
             let result = engine.sync_skill(&skill_id, tool, strategy);
@@ -114,7 +126,7 @@ pub async fn sync_single_skill(skill_id: String) -> Result<Vec<SyncResult>, Stri
                 synced_tools.push(tool_name.clone());
             }
             results.push(SyncResult {
-                skill_id: skill_id.clone(),
+                skill_id: skill_id.to_string(),
                 tool: tool_name,
                 success,
                 error: result.err().map(|e| e.to_string()),
@@ -123,8 +135,7 @@ pub async fn sync_single_skill(skill_id: String) -> Result<Vec<SyncResult>, Stri
     }
 
     if !synced_tools.is_empty() {
-        let mut record_store = LocalStore::default_store().map_err(|e| e.to_string())?;
-        if let Some(record) = record_store.get_record(&skill_id) {
+        if let Some(record) = engine.store().get_record(&skill_id) {
             let mut all_tools: std::collections::HashSet<String> = record
                 .projected_tools
                 .iter()
@@ -133,7 +144,8 @@ pub async fn sync_single_skill(skill_id: String) -> Result<Vec<SyncResult>, Stri
             all_tools.extend(synced_tools.into_iter());
             let mut projected_tools: Vec<String> = all_tools.into_iter().collect();
             projected_tools.sort();
-            record_store
+            engine
+                .store_mut()
                 .update_projected_tools(&skill_id, projected_tools)
                 .map_err(|e| e.to_string())?;
         }
@@ -148,6 +160,7 @@ pub async fn toggle_skill_tool_sync(
     tool_type: String,
     enable: bool,
 ) -> Result<SyncResult, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
     let skill_path = store.skill_path(&skill_id);
 
@@ -155,8 +168,8 @@ pub async fn toggle_skill_tool_sync(
         return Err(format!("Skill '{}' not found in store", skill_id));
     }
 
-    let tool = parse_tool_type(&tool_type)
-        .ok_or_else(|| format!("Unknown tool type: {}", tool_type))?;
+    let tool =
+        parse_tool_type(&tool_type).ok_or_else(|| format!("Unknown tool type: {}", tool_type))?;
 
     if enable {
         let mut engine = SyncEngine::new(store);
@@ -165,14 +178,15 @@ pub async fn toggle_skill_tool_sync(
         }
 
         let config = AppConfig::load_or_default();
+        engine.set_tool_profiles(config.tool_profiles());
+        engine.set_backup_unmanaged(config.backup_unmanaged);
         let tool_key = tool_type.to_lowercase();
         let strategy = config.strategy_for_tool(&tool_key);
 
         let result = engine.sync_skill(&skill_id, tool, strategy);
 
         if result.is_ok() {
-            let mut record_store = LocalStore::default_store().map_err(|e| e.to_string())?;
-            if let Some(record) = record_store.get_record(&skill_id) {
+            if let Some(record) = engine.store().get_record(&skill_id) {
                 let mut all_tools: std::collections::HashSet<String> = record
                     .projected_tools
                     .iter()
@@ -181,67 +195,49 @@ pub async fn toggle_skill_tool_sync(
                 all_tools.insert(tool_type.to_lowercase());
                 let mut projected_tools: Vec<String> = all_tools.into_iter().collect();
                 projected_tools.sort();
-                record_store
+                engine
+                    .store_mut()
                     .update_projected_tools(&skill_id, projected_tools)
                     .map_err(|e| e.to_string())?;
             }
         }
 
         Ok(SyncResult {
-            skill_id,
+            skill_id: skill_id.to_string(),
             tool: tool_type,
             success: result.is_ok(),
             error: result.err().map(|e| e.to_string()),
         })
     } else {
-        let adapters = create_default_adapters();
-        let adapter = adapters
-            .iter()
-            .find(|a| a.tool_type() == tool)
-            .ok_or_else(|| format!("Adapter for tool '{}' not found", tool_type))?;
-
-        let tool_dirs = adapter.skills_dirs();
-        let mut removed = false;
-
-        for dir in tool_dirs {
-            let skill_in_tool = dir.join(&skill_id);
-            if skill_in_tool.exists() {
-                if skill_in_tool.is_symlink() {
-                    std::fs::remove_file(&skill_in_tool)
-                        .map_err(|e| format!("Failed to remove symlink: {}", e))?;
-                } else {
-                    std::fs::remove_dir_all(&skill_in_tool)
-                        .map_err(|e| format!("Failed to remove directory: {}", e))?;
-                }
-                removed = true;
-                break;
-            }
+        let mut engine = SyncEngine::new(store);
+        for adapter in create_default_adapters() {
+            engine.register_adapter(adapter);
         }
 
+        // Only removes what SkillsHub created; other entries are left alone
+        let result = engine.unsync_skill(&skill_id, tool);
+        let removed = result.is_ok();
+
         if removed {
-            let mut record_store = LocalStore::default_store().map_err(|e| e.to_string())?;
-            if let Some(record) = record_store.get_record(&skill_id) {
+            if let Some(record) = engine.store().get_record(&skill_id) {
                 let projected_tools: Vec<String> = record
                     .projected_tools
                     .iter()
                     .filter(|t| t.to_lowercase() != tool_type.to_lowercase())
                     .cloned()
                     .collect();
-                record_store
+                engine
+                    .store_mut()
                     .update_projected_tools(&skill_id, projected_tools)
                     .map_err(|e| e.to_string())?;
             }
         }
 
         Ok(SyncResult {
-            skill_id,
+            skill_id: skill_id.to_string(),
             tool: tool_type,
             success: removed,
-            error: if removed {
-                None
-            } else {
-                Some("Skill not found in tool directory".to_string())
-            },
+            error: result.err().map(|e| e.to_string()),
         })
     }
 }
@@ -260,7 +256,7 @@ pub async fn scan_all_skills() -> Result<Vec<ScannedSkillInfo>, String> {
     Ok(scanned
         .into_iter()
         .map(|s| ScannedSkillInfo {
-            id: s.id,
+            id: s.id.to_string(),
             path: s.path.to_string_lossy().to_string(),
             tool: format!("{:?}", s.tool),
             in_hub: s.in_hub,
@@ -279,30 +275,78 @@ pub async fn full_sync_skills() -> Result<FullSyncResponse, String> {
     }
 
     let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
 
     let result = engine
-        .full_sync(|tool| {
-            let tool_key = format!("{:?}", tool).to_lowercase();
-            config.strategy_for_tool(&tool_key)
-        })
+        .full_sync(|tool| config.strategy_for_tool(tool.key()))
         .map_err(|e| e.to_string())?;
 
     Ok(FullSyncResponse {
         collected_count: result.collected_count,
-        collected_skills: result.collected_skills,
+        collected_skills: result
+            .collected_skills
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        conflicts: result.conflicts,
         distributed_count: result.distributed.len(),
         distributed: result
             .distributed
             .into_iter()
             .map(|(skill, tool, success)| DistributedSkill {
-                skill_id: skill,
+                skill_id: skill.to_string(),
                 tool: format!("{:?}", tool),
                 success,
             })
             .collect(),
+        plan: result.plan,
     })
 }
 
+#[tauri::command]
+pub async fn preview_full_sync() -> Result<SyncPlan, String> {
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut engine = SyncEngine::new(store);
+
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    engine
+        .preview_full_sync(|tool| config.strategy_for_tool(tool.key()))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resolve_collect_conflict(
+    skill_id: String,
+    resolution: ConflictResolution,
+) -> Result<Vec<String>, String> {
+    let skill_id = SkillId::new(skill_id).map_err(|e| e.to_string())?;
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut engine = SyncEngine::new(store);
+
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    let collected = engine
+        .resolve_conflict(&skill_id, resolution, |tool| {
+            config.strategy_for_tool(tool.key())
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(collected.iter().map(ToString::to_string).collect())
+}
+
 #[tauri::command]
 pub async fn get_hub_status() -> Result<Vec<HubStatusInfo>, String> {
     let store = LocalStore::default_store().map_err(|e| e.to_string())?;
@@ -312,12 +356,13 @@ pub async fn get_hub_status() -> Result<Vec<HubStatusInfo>, String> {
         engine.register_adapter(adapter);
     }
 
+    engine.set_tool_profiles(AppConfig::load_or_default().tool_profiles());
     let status = engine.get_hub_status();
 
     Ok(status
         .into_iter()
         .map(|s| HubStatusInfo {
-            skill_id: s.skill_id,
+            skill_id: s.skill_id.to_string(),
             hub_path: s.hub_path.to_string_lossy().to_string(),
             synced_to: s
                 .synced_to
@@ -329,6 +374,7 @@ pub async fn get_hub_status() -> Result<Vec<HubStatusInfo>, String> {
                 .into_iter()
                 .map(|t| format!("{:?}", t))
                 .collect(),
+            disabled: s.disabled,
         })
         .collect())
 }
@@ -2,7 +2,9 @@
 
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
 use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::schema::{self, Versioned};
 
 use super::types::{CustomToolConfig, ToolInfo};
 
@@ -35,7 +37,7 @@ pub async fn list_tools() -> Result<Vec<ToolInfo>, String> {
 
             ToolInfo {
                 name: adapter.tool_type().display_name().to_string(),
-                tool_type: format!("{:?}", adapter.tool_type()).to_lowercase(),
+                tool_type: adapter.tool_type().key().to_string(),
                 detected,
                 skills_dir: skills_dir.map(|p| p.display().to_string()),
                 skills_dirs,
@@ -57,27 +59,37 @@ fn custom_tools_config_path() -> Result<PathBuf, String> {
     Ok(data_dir.join("skillshub").join("custom_tools.json"))
 }
 
+/// Contents of `custom_tools.json`
+#[derive(Serialize, Deserialize)]
+struct CustomToolsFile {
+    tools: Vec<CustomToolConfig>,
+}
+
+/// v0 was a bare list of tools
+impl Versioned for CustomToolsFile {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrate(
+        _from: u32,
+        document: serde_json::Value,
+    ) -> skillshub_core::Result<serde_json::Value> {
+        schema::wrap_list(document, "tools")
+    }
+}
+
 fn load_custom_tools_from_file() -> Result<Vec<CustomToolConfig>, String> {
     let path = custom_tools_config_path()?;
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let content = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read custom tools config: {}", e))?;
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse custom tools config: {}", e))
+    let file = schema::load::<CustomToolsFile>(&path)
+        .map_err(|e| format!("Failed to load custom tools config: {}", e))?;
+    Ok(file.map(|file| file.tools).unwrap_or_default())
 }
 
 fn save_custom_tools_to_file(tools: &[CustomToolConfig]) -> Result<(), String> {
     let path = custom_tools_config_path()?;
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
-    }
-    let content = serde_json::to_string_pretty(tools)
-        .map_err(|e| format!("Failed to serialize custom tools: {}", e))?;
-    std::fs::write(&path, content)
-        .map_err(|e| format!("Failed to write custom tools config: {}", e))
+    let file = CustomToolsFile {
+        tools: tools.to_vec(),
+    };
+    schema::save(&path, &file).map_err(|e| format!("Failed to write custom tools config: {}", e))
 }
 
 #[tauri::command]
@@ -1,7 +1,7 @@
 //! Shared response types and utility functions for Tauri commands
 
 use serde::{Deserialize, Serialize};
-use skillshub_core::models::ToolType;
+use skillshub_core::models::{CollectConflict, SyncPlan, ToolType};
 
 #[derive(Debug, Serialize)]
 pub struct SkillInfo {
@@ -13,6 +13,7 @@ pub struct SkillInfo {
     pub installed_at: String,
     pub scan_passed: bool,
     pub synced_tools: Vec<String>,
+    pub disabled: bool,
     pub author: Option<String>,
     pub tags: Vec<String>,
     pub downloads: Option<u64>,
@@ -114,8 +115,10 @@ pub struct ScannedSkillInfo {
 pub struct FullSyncResponse {
     pub collected_count: usize,
     pub collected_skills: Vec<String>,
+    pub conflicts: Vec<CollectConflict>,
     pub distributed_count: usize,
     pub distributed: Vec<DistributedSkill>,
+    pub plan: SyncPlan,
 }
 
 #[derive(Debug, Serialize)]
@@ -131,6 +134,7 @@ pub struct HubStatusInfo {
     pub hub_path: String,
     pub synced_to: Vec<String>,
     pub missing_in: Vec<String>,
+    pub disabled: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -0,0 +1,104 @@
+//! Watch commands - background service keeping tools in sync while the app runs
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use skillshub_core::adapters::create_default_adapters;
+use skillshub_core::config::AppConfig;
+use skillshub_core::store::LocalStore;
+use skillshub_core::sync::SyncEngine;
+use skillshub_core::watch;
+use tauri::{AppHandle, Emitter};
+
+/// Stop flag and thread of the running watcher
+static WATCHER: Mutex<Option<(Arc<AtomicBool>, JoinHandle<()>)>> = Mutex::new(None);
+
+/// Start the watcher unless it is already running
+///
+/// Every pass that changed something is emitted as a `watch-pass` event
+/// carrying its `WatchReport`; a watcher that cannot run emits `watch-error`.
+pub fn start(app: AppHandle) -> Result<(), String> {
+    let mut running = WATCHER.lock().map_err(|e| e.to_string())?;
+    if running
+        .as_ref()
+        .is_some_and(|(_, thread)| !thread.is_finished())
+    {
+        return Ok(());
+    }
+
+    let store = LocalStore::default_store().map_err(|e| e.to_string())?;
+    let mut engine = SyncEngine::new(store);
+    for adapter in create_default_adapters() {
+        engine.register_adapter(adapter);
+    }
+    let config = AppConfig::load_or_default();
+    engine.set_tool_profiles(config.tool_profiles());
+    engine.set_backup_unmanaged(config.backup_unmanaged);
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let stop = stop.clone();
+        std::thread::spawn(move || {
+            let result = watch::watch(
+                &mut engine,
+                watch::DEFAULT_DEBOUNCE,
+                &stop,
+                |tool| config.strategy_for_tool(tool.key()),
+                |report| {
+                    if !report.is_empty() {
+                        let _ = app.emit("watch-pass", report);
+                    }
+                },
+            );
+            if let Err(e) = result {
+                let _ = app.emit("watch-error", e.to_string());
+            }
+        })
+    };
+    *running = Some((stop, thread));
+    Ok(())
+}
+
+/// Stop the watcher; returns whether it was running
+fn stop() -> Result<bool, String> {
+    let running = WATCHER.lock().map_err(|e| e.to_string())?.take();
+    let Some((stop, thread)) = running else {
+        return Ok(false);
+    };
+    let was_running = !thread.is_finished();
+    stop.store(true, Ordering::Relaxed);
+    thread
+        .join()
+        .map_err(|_| "Watcher stopped unexpectedly".to_string())?;
+    Ok(was_running)
+}
+
+/// Restart a running watcher so it picks up a changed AppConfig
+///
+/// The config is read once when the watcher starts; its tool profiles,
+/// strategies and backup setting stay fixed until then.
+pub fn restart(app: AppHandle) -> Result<(), String> {
+    if stop()? {
+        start(app)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn start_watch(app: AppHandle) -> Result<(), String> {
+    start(app)
+}
+
+#[tauri::command]
+pub async fn stop_watch() -> Result<(), String> {
+    stop().map(|_| ())
+}
+
+#[tauri::command]
+pub async fn is_watching() -> Result<bool, String> {
+    let running = WATCHER.lock().map_err(|e| e.to_string())?;
+    Ok(running
+        .as_ref()
+        .is_some_and(|(_, thread)| !thread.is_finished()))
+}
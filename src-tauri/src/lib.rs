@@ -11,6 +11,13 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            if skillshub_core::AppConfig::load_or_default().watch_in_background {
+                // A watcher that fails to start must not keep the app from opening
+                let _ = watch::start(app.handle().clone());
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Skill commands
             list_installed_skills,
@@ -19,12 +26,24 @@ pub fn run() {
             install_skill,
             uninstall_skill,
             update_skill,
+            rollback_skill,
+            set_skill_alias,
+            disable_skill,
+            enable_skill,
+            verify_store,
             check_skill_updates,
             // Sync commands
             sync_skills,
             sync_single_skill,
             toggle_skill_tool_sync,
             check_drift,
+            // Watch commands
+            start_watch,
+            stop_watch,
+            is_watching,
+            // Backup commands
+            list_backups,
+            restore_backup,
             // Scan commands
             scan_skill,
             list_security_rules,
@@ -50,6 +69,8 @@ pub fn run() {
             // Hub sync commands
             scan_all_skills,
             full_sync_skills,
+            preview_full_sync,
+            resolve_collect_conflict,
             get_hub_status,
             // Config commands
             get_app_config,
@@ -0,0 +1,240 @@
+import { useState, useEffect } from "react";
+import { createPortal } from "react-dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useTranslation } from "../i18n";
+import { Badge } from "./ui/Badge";
+import { Button } from "./ui/Button";
+
+// Mirrors skillshub_core::models::SyncPlan
+interface SyncAction {
+    skill_id: string;
+    tool: string;
+    action: "add" | "update" | "remove" | "repair";
+    strategy: string;
+    target_path: string;
+    overwrites: boolean;
+}
+
+interface CollectAction {
+    skill_id: string;
+    tools: string[];
+    source_path: string;
+}
+
+interface CollectConflict {
+    skill_id: string;
+    variants: {
+        content_hash: string;
+        tools: string[];
+        paths: string[];
+        diffs: { path: string; change: "added" | "removed" | "modified" }[];
+    }[];
+}
+
+// Mirrors skillshub_core::models::ConflictResolution
+type ConflictResolution = { type: "use_tool"; tool: string } | { type: "keep_both" };
+
+interface SyncPlan {
+    to_collect: CollectAction[];
+    conflicts: CollectConflict[];
+    to_add: SyncAction[];
+    to_update: SyncAction[];
+    to_remove: SyncAction[];
+    to_repair: SyncAction[];
+}
+
+export interface FullSyncResponse {
+    collected_count: number;
+    collected_skills: string[];
+    conflicts: CollectConflict[];
+    distributed_count: number;
+    distributed: { skill_id: string; tool: string; success: boolean }[];
+    plan: SyncPlan;
+}
+
+interface FullSyncModalProps {
+    onClose: () => void;
+    onApplied: (result: FullSyncResponse) => void;
+    onError: (error: unknown) => void;
+    onResolved?: (collected: string[]) => void;
+}
+
+// Shows what a full sync would do and runs it once confirmed
+//
+// Skills whose tool copies differ are not collected by the sync; they stay
+// listed until the user picks a copy or keeps both.
+export default function FullSyncModal({ onClose, onApplied, onError, onResolved }: FullSyncModalProps) {
+    const t = useTranslation();
+    const [plan, setPlan] = useState<SyncPlan | null>(null);
+    const [conflicts, setConflicts] = useState<CollectConflict[]>([]);
+    const [applying, setApplying] = useState(false);
+    const [applied, setApplied] = useState(false);
+    const [resolving, setResolving] = useState<string | null>(null);
+
+    useEffect(() => {
+        invoke<SyncPlan>("preview_full_sync")
+            .then((plan) => {
+                setPlan(plan);
+                setConflicts(plan.conflicts);
+            })
+            .catch((error) => {
+                onError(error);
+                onClose();
+            });
+    }, []);
+
+    async function apply() {
+        setApplying(true);
+        try {
+            const result = await invoke<FullSyncResponse>("full_sync_skills");
+            onApplied(result);
+            if (result.conflicts.length === 0) {
+                onClose();
+                return;
+            }
+            setConflicts(result.conflicts);
+            setApplied(true);
+        } catch (error) {
+            onError(error);
+        }
+        setApplying(false);
+    }
+
+    async function resolve(skillId: string, resolution: ConflictResolution) {
+        setResolving(skillId);
+        try {
+            const collected = await invoke<string[]>("resolve_collect_conflict", { skillId, resolution });
+            setConflicts((prev) => prev.filter((c) => c.skill_id !== skillId));
+            onResolved?.(collected);
+        } catch (error) {
+            onError(error);
+        }
+        setResolving(null);
+    }
+
+    const sections: [string, SyncAction[]][] = plan && !applied
+        ? [
+            [t.syncPreview.add, plan.to_add],
+            [t.syncPreview.update, plan.to_update],
+            [t.syncPreview.repair, plan.to_repair],
+            [t.syncPreview.remove, plan.to_remove],
+        ]
+        : [];
+    const toCollect = plan && !applied ? plan.to_collect : [];
+    const isEmpty = plan !== null && toCollect.length === 0 && conflicts.length === 0
+        && sections.every(([, actions]) => actions.length === 0);
+
+    return createPortal(
+        <div className="modal modal-open">
+            <div className="modal-box glass-panel max-w-2xl">
+                <h3 className="font-bold text-lg mb-6">{t.syncPreview.title}</h3>
+
+                {plan === null ? (
+                    <div className="flex justify-center py-8">
+                        <span className="loading loading-spinner loading-md"></span>
+                    </div>
+                ) : isEmpty ? (
+                    <p className="text-base-content/60">{t.syncPreview.nothingToDo}</p>
+                ) : (
+                    <div className="space-y-4 max-h-[60vh] overflow-y-auto">
+                        {toCollect.length > 0 && (
+                            <div>
+                                <h4 className="font-bold mb-2">{t.syncPreview.collect}</h4>
+                                <div className="space-y-1">
+                                    {toCollect.map((c) => (
+                                        <div key={c.skill_id} className="text-sm">
+                                            <span className="font-medium">{c.skill_id}</span>
+                                            <span className="text-base-content/50"> ← {c.tools.join(", ")}</span>
+                                        </div>
+                                    ))}
+                                </div>
+                            </div>
+                        )}
+                        {sections.filter(([, actions]) => actions.length > 0).map(([title, actions]) => (
+                            <div key={title}>
+                                <h4 className="font-bold mb-2">{title}</h4>
+                                <div className="space-y-1">
+                                    {actions.map((a) => (
+                                        <div key={`${a.skill_id}-${a.tool}`} className="text-sm">
+                                            <div className="flex items-center gap-2">
+                                                <span className="font-medium">{a.skill_id}</span>
+                                                <span className="text-base-content/50">→ {a.tool} ({a.strategy})</span>
+                                                {a.overwrites && (
+                                                    <Badge variant="warning" size="xs">{t.syncPreview.overwrites}</Badge>
+                                                )}
+                                            </div>
+                                            <code className="text-xs text-base-content/50 font-mono break-all">{a.target_path}</code>
+                                        </div>
+                                    ))}
+                                </div>
+                            </div>
+                        ))}
+                        {conflicts.length > 0 && (
+                            <div>
+                                <h4 className="font-bold mb-1">{t.syncPreview.conflicts}</h4>
+                                <p className="text-xs text-base-content/50 mb-2">{t.syncPreview.conflictsHint}</p>
+                                <div className="space-y-3">
+                                    {conflicts.map((c) => (
+                                        <div key={c.skill_id} className="bg-base-200/50 rounded-lg p-3 border border-base-300/50 text-sm">
+                                            <div className="font-medium mb-2">{c.skill_id}</div>
+                                            <div className="space-y-2">
+                                                {c.variants.map((v) => (
+                                                    <div key={v.content_hash} className="flex items-start justify-between gap-2">
+                                                        <div className="min-w-0">
+                                                            <div>{v.tools.join(", ")}</div>
+                                                            {v.diffs.map((d) => (
+                                                                <code key={d.path} className="block text-xs text-base-content/50 font-mono break-all">
+                                                                    {d.change} {d.path}
+                                                                </code>
+                                                            ))}
+                                                        </div>
+                                                        <Button
+                                                            variant="outline"
+                                                            size="xs"
+                                                            disabled={resolving !== null}
+                                                            onClick={() => resolve(c.skill_id, { type: "use_tool", tool: v.tools[0] })}
+                                                        >
+                                                            {t.syncPreview.useCopy}
+                                                        </Button>
+                                                    </div>
+                                                ))}
+                                            </div>
+                                            <Button
+                                                variant="ghost"
+                                                size="xs"
+                                                className="mt-2"
+                                                disabled={resolving !== null}
+                                                loading={resolving === c.skill_id}
+                                                onClick={() => resolve(c.skill_id, { type: "keep_both" })}
+                                            >
+                                                {t.syncPreview.keepBoth}
+                                            </Button>
+                                        </div>
+                                    ))}
+                                </div>
+                            </div>
+                        )}
+                    </div>
+                )}
+
+                <div className="modal-action">
+                    <Button variant="ghost" onClick={onClose} disabled={applying}>
+                        {applied ? t.common.close : t.common.cancel}
+                    </Button>
+                    {!applied && (
+                        <Button
+                            variant="primary"
+                            onClick={apply}
+                            disabled={plan === null || isEmpty || resolving !== null}
+                            loading={applying}
+                        >
+                            {t.syncPreview.apply}
+                        </Button>
+                    )}
+                </div>
+            </div>
+            <div className="modal-backdrop bg-base-100/80 backdrop-blur-sm" onClick={() => !applying && onClose()} />
+        </div>,
+        document.body
+    );
+}
@@ -87,6 +87,22 @@ export const de: Translations = {
         loadFailed: "Laden fehlgeschlagen: {error}",
     },
 
+    syncPreview: {
+        title: "Sync-Vorschau",
+        nothingToDo: "Alles ist aktuell.",
+        collect: "In den Hub sammeln",
+        add: "Hinzufügen",
+        update: "Aktualisieren",
+        repair: "Reparieren",
+        remove: "Entfernen",
+        overwrites: "Sichert Vorhandenes",
+        conflicts: "Konflikte (nicht gesammelt)",
+        conflictsHint: "Die Tools enthalten unterschiedliche Kopien dieser Skills. Wählen Sie die zu sammelnde Kopie oder behalten Sie beide unter eigenen IDs.",
+        useCopy: "Diese Kopie verwenden",
+        keepBoth: "Beide behalten",
+        apply: "Anwenden",
+    },
+
     discover: {
         title: "Skills Entdecken",
         description: "Finden und installieren Sie Skills aus Registern",
@@ -197,6 +213,8 @@ export const de: Translations = {
         defaultSyncStrategy: "Standard-Sync-Strategie",
         autoLinkFirst: "Auto (Link bevorzugt)",
         alwaysLink: "Immer verlinken",
+        relativeLink: "Relativ verlinken",
+        alwaysHardlink: "Immer Hardlink",
         alwaysCopy: "Immer kopieren",
         toolSyncStrategy: "Sync-Strategie",
         useGlobalStrategy: "Globalen Standard verwenden",
@@ -88,6 +88,23 @@ export const en = {
         loadFailed: "Failed to load: {error}",
     },
 
+    // 同步预览
+    syncPreview: {
+        title: "Sync Preview",
+        nothingToDo: "Everything is up to date.",
+        collect: "Collect into Hub",
+        add: "Add",
+        update: "Update",
+        repair: "Repair",
+        remove: "Remove",
+        overwrites: "Backs up existing",
+        conflicts: "Conflicts (not collected)",
+        conflictsHint: "Tools hold different copies of these skills. Pick the copy to collect, or keep both under separate IDs.",
+        useCopy: "Use this copy",
+        keepBoth: "Keep both",
+        apply: "Apply",
+    },
+
     // 发现页面
     discover: {
         title: "Discover Skills",
@@ -202,6 +219,8 @@ export const en = {
         defaultSyncStrategy: "Default Sync Strategy",
         autoLinkFirst: "Auto (Link-first)",
         alwaysLink: "Always Link",
+        relativeLink: "Relative Link",
+        alwaysHardlink: "Always Hardlink",
         alwaysCopy: "Always Copy",
         toolSyncStrategy: "Sync Strategy",
         useGlobalStrategy: "Use Global Default",
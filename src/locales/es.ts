@@ -87,6 +87,22 @@ export const es: Translations = {
         loadFailed: "Error al cargar: {error}",
     },
 
+    syncPreview: {
+        title: "Vista previa de la sincronización",
+        nothingToDo: "Todo está actualizado.",
+        collect: "Recopilar en el Hub",
+        add: "Añadir",
+        update: "Actualizar",
+        repair: "Reparar",
+        remove: "Eliminar",
+        overwrites: "Respalda lo existente",
+        conflicts: "Conflictos (no se recopilan)",
+        conflictsHint: "Las herramientas tienen copias distintas de estas skills. Elige la copia que se recopila o conserva ambas con ID distintos.",
+        useCopy: "Usar esta copia",
+        keepBoth: "Conservar ambas",
+        apply: "Aplicar",
+    },
+
     discover: {
         title: "Descubrir Skills",
         description: "Buscar e instalar Skills desde los registros",
@@ -197,6 +213,8 @@ export const es: Translations = {
         defaultSyncStrategy: "Estrategia de sync predeterminada",
         autoLinkFirst: "Auto (Enlace primero)",
         alwaysLink: "Siempre enlazar",
+        relativeLink: "Enlace relativo",
+        alwaysHardlink: "Siempre enlace duro",
         alwaysCopy: "Siempre copiar",
         toolSyncStrategy: "Estrategia de sincronización",
         useGlobalStrategy: "Usar predeterminado global",
@@ -87,6 +87,22 @@ export const fr: Translations = {
         loadFailed: "Échec du chargement : {error}",
     },
 
+    syncPreview: {
+        title: "Aperçu de la synchronisation",
+        nothingToDo: "Tout est à jour.",
+        collect: "Collecter dans le Hub",
+        add: "Ajouter",
+        update: "Mettre à jour",
+        repair: "Réparer",
+        remove: "Supprimer",
+        overwrites: "Sauvegarde l'existant",
+        conflicts: "Conflits (non collectés)",
+        conflictsHint: "Les outils contiennent des copies différentes de ces skills. Choisissez la copie à collecter ou gardez les deux sous des ID distincts.",
+        useCopy: "Utiliser cette copie",
+        keepBoth: "Garder les deux",
+        apply: "Appliquer",
+    },
+
     discover: {
         title: "Découvrir des Skills",
         description: "Rechercher et installer des Skills depuis les registres",
@@ -197,6 +213,8 @@ export const fr: Translations = {
         defaultSyncStrategy: "Stratégie de sync par défaut",
         autoLinkFirst: "Auto (Lien prioritaire)",
         alwaysLink: "Toujours lier",
+        relativeLink: "Lien relatif",
+        alwaysHardlink: "Toujours lien physique",
         alwaysCopy: "Toujours copier",
         toolSyncStrategy: "Stratégie de sync",
         useGlobalStrategy: "Utiliser le défaut global",
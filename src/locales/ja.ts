@@ -87,6 +87,22 @@ export const ja: Translations = {
         loadFailed: "読み込みに失敗しました：{error}",
     },
 
+    syncPreview: {
+        title: "同期プレビュー",
+        nothingToDo: "すべて最新です。",
+        collect: "Hub に収集",
+        add: "追加",
+        update: "更新",
+        repair: "修復",
+        remove: "削除",
+        overwrites: "既存をバックアップ",
+        conflicts: "競合（収集されません）",
+        conflictsHint: "これらのスキルはツールごとに内容が異なります。収集するコピーを選ぶか、別々の ID で両方を残してください。",
+        useCopy: "このコピーを使用",
+        keepBoth: "両方を残す",
+        apply: "適用",
+    },
+
     discover: {
         title: "Skills を探索",
         description: "レジストリから Skills を検索してインストール",
@@ -197,6 +213,8 @@ export const ja: Translations = {
         defaultSyncStrategy: "デフォルト同期戦略",
         autoLinkFirst: "自動（リンク優先）",
         alwaysLink: "常にリンク",
+        relativeLink: "相対リンク",
+        alwaysHardlink: "常にハードリンク",
         alwaysCopy: "常にコピー",
         toolSyncStrategy: "同期方式",
         useGlobalStrategy: "グローバルデフォルトを使用",
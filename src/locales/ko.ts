@@ -87,6 +87,22 @@ export const ko: Translations = {
         loadFailed: "로딩 실패: {error}",
     },
 
+    syncPreview: {
+        title: "동기화 미리보기",
+        nothingToDo: "모두 최신 상태입니다.",
+        collect: "Hub로 수집",
+        add: "추가",
+        update: "업데이트",
+        repair: "복구",
+        remove: "제거",
+        overwrites: "기존 항목 백업",
+        conflicts: "충돌 (수집되지 않음)",
+        conflictsHint: "도구마다 이 스킬의 내용이 다릅니다. 수집할 사본을 선택하거나 별도 ID로 모두 유지하세요.",
+        useCopy: "이 사본 사용",
+        keepBoth: "모두 유지",
+        apply: "적용",
+    },
+
     discover: {
         title: "Skills 탐색",
         description: "레지스트리에서 Skills 검색 및 설치",
@@ -197,6 +213,8 @@ export const ko: Translations = {
         defaultSyncStrategy: "기본 동기화 전략",
         autoLinkFirst: "자동 (링크 우선)",
         alwaysLink: "항상 링크",
+        relativeLink: "상대 링크",
+        alwaysHardlink: "항상 하드 링크",
         alwaysCopy: "항상 복사",
         toolSyncStrategy: "동기화 방식",
         useGlobalStrategy: "글로벌 기본값 사용",
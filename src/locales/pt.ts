@@ -87,6 +87,22 @@ export const pt: Translations = {
         loadFailed: "Falha ao carregar: {error}",
     },
 
+    syncPreview: {
+        title: "Pré-visualização da sincronização",
+        nothingToDo: "Tudo está atualizado.",
+        collect: "Coletar no Hub",
+        add: "Adicionar",
+        update: "Atualizar",
+        repair: "Reparar",
+        remove: "Remover",
+        overwrites: "Faz backup do existente",
+        conflicts: "Conflitos (não coletados)",
+        conflictsHint: "As ferramentas têm cópias diferentes destas skills. Escolha a cópia a coletar ou mantenha ambas com IDs separados.",
+        useCopy: "Usar esta cópia",
+        keepBoth: "Manter ambas",
+        apply: "Aplicar",
+    },
+
     discover: {
         title: "Descobrir Skills",
         description: "Encontre e instale skills de registros",
@@ -197,6 +213,8 @@ export const pt: Translations = {
         defaultSyncStrategy: "Estratégia de sync padrão",
         autoLinkFirst: "Auto (Link primeiro)",
         alwaysLink: "Sempre linkar",
+        relativeLink: "Link relativo",
+        alwaysHardlink: "Sempre hardlink",
         alwaysCopy: "Sempre copiar",
         toolSyncStrategy: "Estratégia de sincronização",
         useGlobalStrategy: "Usar padrão global",
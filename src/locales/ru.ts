@@ -87,6 +87,22 @@ export const ru: Translations = {
         loadFailed: "Ошибка загрузки: {error}",
     },
 
+    syncPreview: {
+        title: "Предпросмотр синхронизации",
+        nothingToDo: "Всё актуально.",
+        collect: "Собрать в Hub",
+        add: "Добавить",
+        update: "Обновить",
+        repair: "Восстановить",
+        remove: "Удалить",
+        overwrites: "Существующее будет сохранено в резервную копию",
+        conflicts: "Конфликты (не собираются)",
+        conflictsHint: "В инструментах разные копии этих навыков. Выберите копию для сбора или сохраните обе под разными ID.",
+        useCopy: "Использовать эту копию",
+        keepBoth: "Сохранить обе",
+        apply: "Применить",
+    },
+
     discover: {
         title: "Обзор Skills",
         description: "Поиск и установка Skills из реестров",
@@ -197,6 +213,8 @@ export const ru: Translations = {
         defaultSyncStrategy: "Стратегия синхронизации по умолчанию",
         autoLinkFirst: "Авто (Ссылка приоритет)",
         alwaysLink: "Всегда ссылка",
+        relativeLink: "Относительная ссылка",
+        alwaysHardlink: "Всегда жёсткая ссылка",
         alwaysCopy: "Всегда копия",
         toolSyncStrategy: "Стратегия синхронизации",
         useGlobalStrategy: "Использовать глобальную по умолчанию",
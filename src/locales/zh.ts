@@ -90,6 +90,23 @@ export const zh: Translations = {
         loadFailed: "加载失败：{error}",
     },
 
+    // 同步预览
+    syncPreview: {
+        title: "同步预览",
+        nothingToDo: "一切已是最新。",
+        collect: "收集到 Hub",
+        add: "添加",
+        update: "更新",
+        repair: "修复",
+        remove: "移除",
+        overwrites: "将备份现有内容",
+        conflicts: "冲突（不会收集）",
+        conflictsHint: "多个工具中的这些技能内容不同。请选择要收集的版本，或以不同 ID 同时保留。",
+        useCopy: "使用此版本",
+        keepBoth: "全部保留",
+        apply: "应用",
+    },
+
     // 发现页面
     discover: {
         title: "发现 Skills",
@@ -204,6 +221,8 @@ export const zh: Translations = {
         defaultSyncStrategy: "默认同步策略",
         autoLinkFirst: "自动（优先链接）",
         alwaysLink: "始终链接",
+        relativeLink: "相对链接",
+        alwaysHardlink: "始终硬链接",
         alwaysCopy: "始终复制",
         toolSyncStrategy: "同步策略",
         useGlobalStrategy: "使用全局默认",
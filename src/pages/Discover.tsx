@@ -5,6 +5,7 @@ import { useTranslation } from "../i18n";
 import { Card } from "../components/ui/Card";
 import { Badge } from "../components/ui/Badge";
 import { Button } from "../components/ui/Button";
+import FullSyncModal from "../components/FullSyncModal";
 
 interface SkillListing {
     id: string;
@@ -43,6 +44,7 @@ export default function Discover() {
     const loaderRef = useRef<HTMLDivElement>(null);
     const [installingSkills, setInstallingSkills] = useState<Set<string>>(new Set());
     const [installedSkills, setInstalledSkills] = useState<Set<string>>(new Set());
+    const [showSyncPreview, setShowSyncPreview] = useState(false);
 
     // Filtering
     const [registries, setRegistries] = useState<RegistryConfig[]>([]);
@@ -97,13 +99,9 @@ export default function Discover() {
 
             setInstalledSkills(prev => new Set(prev).add(skillId));
 
+            // The sync plan is shown for confirmation before anything is applied
             if (config.auto_sync_on_install) {
-                try {
-                    await invoke("full_sync_skills");
-                    console.log("Auto sync completed after install");
-                } catch (syncError) {
-                    console.error("Auto sync failed:", syncError);
-                }
+                setShowSyncPreview(true);
             }
         } catch (error) {
             console.error("Install failed:", error);
@@ -356,6 +354,14 @@ export default function Discover() {
                     <div className="divider text-xs text-base-content/30 w-full max-w-sm mx-auto">{t.discover.endOfResults}</div>
                 )}
             </div>
+
+            {showSyncPreview && (
+                <FullSyncModal
+                    onClose={() => setShowSyncPreview(false)}
+                    onApplied={() => console.log("Sync completed after install")}
+                    onError={(syncError) => console.error("Sync after install failed:", syncError)}
+                />
+            )}
         </div>
     );
 }
@@ -16,6 +16,7 @@ import { useUpdateCheck } from "../hooks/useUpdateCheck";
 import { Card } from "../components/ui/Card";
 import { Badge } from "../components/ui/Badge";
 import { Button } from "../components/ui/Button";
+import FullSyncModal, { type FullSyncResponse } from "../components/FullSyncModal";
 
 // Interfaces
 interface ScannedSkillInfo {
@@ -33,13 +34,6 @@ interface HubStatusInfo {
     missing_in: string[];
 }
 
-interface FullSyncResponse {
-    collected_count: number;
-    collected_skills: string[];
-    distributed_count: number;
-    distributed: { skill_id: string; tool: string; success: boolean }[];
-}
-
 interface PluginSkillInfo {
     id: string;
     plugin_name: string;
@@ -97,21 +91,18 @@ export default function Installed() {
         setLoading(false);
     }
 
-    async function handleFullSync() {
-        setSyncing(true);
-        showToast("info", t.installed.fullSyncing);
-        try {
-            const result = await invoke<FullSyncResponse>("full_sync_skills");
-            showToast(
-                "success",
-                t.installed.fullSyncSuccess.replace("{collected}", result.collected_count.toString()).replace("{distributed}", result.distributed_count.toString())
-            );
-            await loadAll();
-        } catch (error) {
-            console.error("Full sync failed:", error);
-            showToast("error", t.installed.fullSyncFailed.replace("{error}", String(error)));
-        }
-        setSyncing(false);
+    // The plan is previewed in FullSyncModal and applied from there
+    async function handleFullSyncApplied(result: FullSyncResponse) {
+        showToast(
+            "success",
+            t.installed.fullSyncSuccess.replace("{collected}", result.collected_count.toString()).replace("{distributed}", result.distributed_count.toString())
+        );
+        await loadAll();
+    }
+
+    function handleFullSyncError(error: unknown) {
+        console.error("Full sync failed:", error);
+        showToast("error", t.installed.fullSyncFailed.replace("{error}", String(error)));
     }
 
     // Group scanned skills
@@ -228,7 +219,7 @@ export default function Installed() {
                     <Button
                         variant="secondary"
                         size="sm"
-                        onClick={handleFullSync}
+                        onClick={() => setSyncing(true)}
                         isLoading={syncing}
                     >
                         <FolderSync className="w-4 h-4 mr-2" />
@@ -359,6 +350,15 @@ export default function Installed() {
                     </div>
                 )}
             </div>
+
+            {syncing && (
+                <FullSyncModal
+                    onClose={() => setSyncing(false)}
+                    onApplied={handleFullSyncApplied}
+                    onError={handleFullSyncError}
+                    onResolved={() => loadAll()}
+                />
+            )}
         </div>
     );
 }
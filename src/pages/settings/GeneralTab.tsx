@@ -109,6 +109,8 @@ export default function GeneralTab({
                         >
                             <option value="auto">{t.settings.autoLinkFirst}</option>
                             <option value="link">{t.settings.alwaysLink}</option>
+                            <option value="relative-link">{t.settings.relativeLink}</option>
+                            <option value="hardlink">{t.settings.alwaysHardlink}</option>
                             <option value="copy">{t.settings.alwaysCopy}</option>
                         </select>
                     </div>
@@ -162,6 +162,8 @@ function ToolItem({
                             <option value="">{t.settings.useGlobalStrategy}</option>
                             <option value="auto">{t.settings.autoLinkFirst}</option>
                             <option value="link">{t.settings.alwaysLink}</option>
+                            <option value="relative-link">{t.settings.relativeLink}</option>
+                            <option value="hardlink">{t.settings.alwaysHardlink}</option>
                             <option value="copy">{t.settings.alwaysCopy}</option>
                         </select>
                     </div>
@@ -111,5 +111,5 @@ export interface DetectedToolInfo {
     skill_count: number;
 }
 
-export const VALID_SYNC_STRATEGIES = new Set(["auto", "link", "copy"]);
+export const VALID_SYNC_STRATEGIES = new Set(["auto", "link", "relative-link", "hardlink", "copy"]);
 export const VALID_CLOUD_PROVIDERS = new Set(["ICloud", "GoogleDrive", "OneDrive", "Custom"]);